@@ -5,7 +5,7 @@ use core::ptr;
 use talc::{ClaimOnOom, Span, Talc, Talck};
 
 // Size (in bytes) of global memory allocator arena.
-const ARENA_SIZE: usize = 1 << 16; // 64 KiB
+pub(crate) const ARENA_SIZE: usize = 1 << 16; // 64 KiB
 
 // Talc global memory allocator
 static mut ARENA: [u8; ARENA_SIZE] = [0; ARENA_SIZE];
@@ -174,6 +174,57 @@ pub unsafe fn parse_argv_envp(
     Ok((argv, envp))
 }
 
+/// Splits the given string into fields on any character of `ifs`, following POSIX shell field
+/// splitting rules.
+///
+/// `IFS` characters that are whitespace are treated specially, like the shell treats its default
+/// `IFS` of space/tab/newline: leading and trailing `IFS` whitespace is ignored, and consecutive
+/// `IFS` whitespace counts as a single separator. Non-whitespace `IFS` characters (like the `:` in
+/// `PATH`) each delimit a field, so consecutive ones produce empty fields. An empty `ifs` disables
+/// splitting entirely.
+#[must_use]
+pub fn split_fields(s: &str, ifs: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    if ifs.is_empty() {
+        // No splitting; the whole string is one field.
+        if !s.is_empty() {
+            fields.push(s.to_string());
+        }
+        return fields;
+    }
+
+    let is_ifs = |c: char| ifs.contains(c);
+    let is_ifs_whitespace = |c: char| is_ifs(c) && c.is_whitespace();
+
+    let mut iter = s.chars().peekable();
+
+    // Ignore leading IFS whitespace.
+    while iter.next_if(|&c| is_ifs_whitespace(c)).is_some() {}
+
+    while iter.peek().is_some() {
+        // Collect the next field, which is empty if a non-whitespace separator comes right away.
+        let mut field = String::new();
+        while let Some(c) = iter.next_if(|&c| !is_ifs(c)) {
+            field.push(c);
+        }
+        fields.push(field);
+
+        if iter.peek().is_none() {
+            break;
+        }
+
+        // Consume one separator: a run of IFS whitespace, at most one non-whitespace IFS
+        // character, then any IFS whitespace stuck to it. A trailing separator doesn't produce an
+        // empty field.
+        while iter.next_if(|&c| is_ifs_whitespace(c)).is_some() {}
+        if iter.next_if(|&c| is_ifs(c) && !is_ifs_whitespace(c)).is_some() {
+            while iter.next_if(|&c| is_ifs_whitespace(c)).is_some() {}
+        }
+    }
+
+    fields
+}
+
 fn inc_total_size(total_size: usize, increase: usize) -> Result<usize, Errno> {
     let result = total_size + increase;
     if result > ARG_ENV_LIM {
@@ -219,6 +270,36 @@ mod tests {
     test_ev_from!(ev_empty_val("MY_KEY=".to_string()) => OK("MY_KEY", ""));
     test_ev_from!(ev_multibyte("我的叫=马克斯".to_string()) => OK("我的叫", "马克斯"));
 
+    #[test_case]
+    fn split_fields_default_ifs() {
+        assert_eq!(
+            split_fields("  foo  bar\tbaz\n", " \t\n"),
+            ["foo", "bar", "baz"]
+        );
+        assert!(split_fields("   ", " \t\n").is_empty());
+        assert!(split_fields("", " \t\n").is_empty());
+    }
+
+    #[test_case]
+    fn split_fields_colon_ifs() {
+        // `PATH`-style splitting: consecutive separators yield empty fields.
+        assert_eq!(
+            split_fields("/bin:/usr/bin::/sbin", ":"),
+            ["/bin", "/usr/bin", "", "/sbin"]
+        );
+        assert_eq!(split_fields(":head", ":"), ["", "head"]);
+        // A trailing separator doesn't produce an empty field.
+        assert_eq!(split_fields("tail:", ":"), ["tail"]);
+    }
+
+    #[test_case]
+    fn split_fields_mixed_ifs() {
+        // Whitespace around a non-whitespace separator is part of the same separator.
+        assert_eq!(split_fields(" a : b::c ", " :"), ["a", "b", "", "c"]);
+        // An empty IFS disables splitting.
+        assert_eq!(split_fields("one two", ""), ["one two"]);
+    }
+
     #[test_case]
     fn inc_total_size_under() {
         assert_eq!(inc_total_size(1, 1), Ok(2));
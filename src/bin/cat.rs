@@ -42,6 +42,9 @@ const HIGH_BIT: u8 = 0x80;
 
 const CARET_NOTATION_FLIP_BIT: u8 = 0x40;
 
+/// Size of the scratch buffer used when streaming in unbuffered mode.
+const UNBUFFERED_CHUNK_SIZE: usize = 1 << 12;
+
 core::arch::global_asm! {
     ".global _start",
     "_start:",
@@ -67,6 +70,9 @@ struct CatInputs {
     /// Display control characters (except for line feed and tab) using caret notation. Precede
     /// characters that have the high bit set with 'M-'.
     show_nonprinting: bool,
+    /// Stream each read chunk straight to stdout instead of buffering the whole input. The
+    /// formatting options above are ignored in this mode.
+    unbuffered: bool,
 }
 impl CatInputs {
     /// Applies the options to the given byte vector.
@@ -202,6 +208,9 @@ impl TryFrom<&[String]> for CatInputs {
                     cat_inputs.show_tabs = true;
                     cat_inputs.show_nonprinting = true;
                 }
+                Arg::Short('u') | Arg::Long("unbuffered") => {
+                    cat_inputs.unbuffered = true;
+                }
                 Arg::Short('T') | Arg::Long("show-tabs") => {
                     cat_inputs.show_tabs = true;
                 }
@@ -250,6 +259,12 @@ unsafe extern "C" fn start(stack_top: *const usize) -> ! {
 fn main(args: &[String], _env_vars: &[EnvVar]) -> ExitStatus {
     let cat_inputs = try_exit!(CatInputs::try_from(args));
 
+    // Unbuffered mode pushes input straight through rather than collecting it all first.
+    if cat_inputs.unbuffered {
+        try_exit!(stream_sources(&cat_inputs.files));
+        return ExitStatus::ExitSuccess;
+    }
+
     let mut output = try_exit!(concatenate(&cat_inputs.files));
 
     // Apply options to output
@@ -282,6 +297,53 @@ fn concatenate(files: &[String]) -> Result<Vec<u8>, Errno> {
     Ok(output)
 }
 
+/// Streams each input source straight to stdout, chunk by chunk.
+fn stream_sources(files: &[String]) -> Result<(), Errno> {
+    let to_stdout = |chunk: &[u8]| streams::STDOUT.lock().write(chunk);
+
+    // If empty, get stdin
+    if files.is_empty() {
+        return stream_unbuffered(|buf| streams::STDIN.lock().read(buf), to_stdout);
+    }
+    for file in files {
+        if file == STDIN_SYMBOL {
+            stream_unbuffered(|buf| streams::STDIN.lock().read(buf), to_stdout)?;
+        } else {
+            let file = fs::OpenOptions::new().open(file.as_str())?;
+            stream_unbuffered(|buf| file.read(buf), to_stdout)?;
+        }
+    }
+    Ok(())
+}
+
+/// Copies bytes from `read` to `write` one chunk at a time until `read` hits EOF, pushing each
+/// chunk out as soon as it arrives instead of waiting for the end of the input.
+fn stream_unbuffered<R, W>(mut read: R, mut write: W) -> Result<(), Errno>
+where
+    R: FnMut(&mut [u8]) -> Result<usize, Errno>,
+    W: FnMut(&[u8]) -> Result<usize, Errno>,
+{
+    while pump_chunk(&mut read, &mut write)? > 0 {}
+    Ok(())
+}
+
+/// Pumps at most one chunk from `read` to `write`, returning the number of bytes pumped. Zero
+/// means the input hit EOF.
+fn pump_chunk<R, W>(read: &mut R, write: &mut W) -> Result<usize, Errno>
+where
+    R: FnMut(&mut [u8]) -> Result<usize, Errno>,
+    W: FnMut(&[u8]) -> Result<usize, Errno>,
+{
+    let mut buffer = [0_u8; UNBUFFERED_CHUNK_SIZE];
+    let bytes_read = read(&mut buffer)?;
+
+    let mut written = 0;
+    while written < bytes_read {
+        written += write(&buffer[written..bytes_read])?;
+    }
+    Ok(bytes_read)
+}
+
 /// Appends standard input to a vector of bytes.
 fn append_stdin_bytes(buf: &mut Vec<u8>) -> Result<(), Errno> {
     buf.append(&mut streams::STDIN.lock().read_to_bytes()?);
@@ -290,7 +352,7 @@ fn append_stdin_bytes(buf: &mut Vec<u8>) -> Result<(), Errno> {
 
 /// Appends the file bytes to a vector of bytes.
 fn append_file_bytes(buf: &mut Vec<u8>, path: &str) -> Result<(), Errno> {
-    buf.append(&mut fs::OpenOptions::new().open(path)?.read_to_bytes()?);
+    fs::OpenOptions::new().open(path)?.read_to_end_into(buf)?;
     Ok(())
 }
 
@@ -492,6 +554,36 @@ mod tests {
         show_nonprinting: true,
     });
 
+    #[test_case]
+    fn unbuffered_flag_parsed() {
+        let input: &[String] = &["cat".to_string(), "-u".to_string()];
+        assert!(CatInputs::try_from(input).unwrap().unbuffered);
+        let input: &[String] = &["cat".to_string(), "--unbuffered".to_string()];
+        assert!(CatInputs::try_from(input).unwrap().unbuffered);
+    }
+
+    #[test_case]
+    fn unbuffered_pumps_before_eof() {
+        let (input_read, input_write) = tlenix_core::ipc::pipe().unwrap();
+        let (output_read, output_write) = tlenix_core::ipc::pipe().unwrap();
+
+        let mut read = |buf: &mut [u8]| input_read.read(buf);
+        let mut write = |chunk: &[u8]| output_write.write(chunk);
+
+        // The input's write end stays open, so no EOF is in sight — the partial input must still
+        // come out the other side after a single pump.
+        input_write.write(b"partial").unwrap();
+        assert_eq!(pump_chunk(&mut read, &mut write).unwrap(), 7);
+
+        let mut buffer = [0_u8; 7];
+        output_read.read_exact(&mut buffer).unwrap();
+        assert_eq!(&buffer, b"partial");
+
+        // Once the input is closed, the next pump reports EOF.
+        drop(input_write);
+        assert_eq!(pump_chunk(&mut read, &mut write).unwrap(), 0);
+    }
+
     #[test_case]
     fn check_concatenate() {
         const FILES: [&str; 3] = [
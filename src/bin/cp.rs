@@ -0,0 +1,624 @@
+//! Copies files and directories.
+
+#![warn(
+    missing_docs,
+    missing_debug_implementations,
+    rust_2018_idioms,
+    clippy::all,
+    clippy::pedantic
+)]
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![cfg_attr(test, test_runner(tlenix_core::custom_test_runner))]
+#![cfg_attr(test, reexport_test_harness_main = "test_main")]
+
+extern crate alloc;
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::panic::PanicInfo;
+
+use getargs::{Arg, Options};
+use tlenix_core::{
+    Console, EnvVar, Errno, eprintln,
+    fs::{self, FilePermissions, FileStats, FileType, OpenOptions},
+    parse_argv_envp, print,
+    process::{self, ExitStatus},
+    try_exit,
+};
+
+const PANIC_TITLE: &str = "cp";
+
+core::arch::global_asm! {
+    ".global _start",
+    "_start:",
+    "mov rdi, rsp",
+    "call start"
+}
+
+/// All the things that govern `cp`'s behaviour.
+#[derive(Debug, Default)]
+#[allow(clippy::struct_excessive_bools)]
+struct CpSettings<'a> {
+    paths: Vec<&'a str>,
+    /// Recurse into directories.
+    recursive: bool,
+    /// Prompt before overwriting an existing destination.
+    prompt_overwrite: bool,
+    /// Preserve the source's timestamps and ownership.
+    preserve: bool,
+    /// Recreate holes in sparse files instead of writing their zeros.
+    sparse: bool,
+}
+impl<'a> CpSettings<'a> {
+    fn from_cli(args: &'a [String]) -> Result<Self, Errno> {
+        let mut result = Self::default();
+
+        let mut opts = Options::new(args.iter().map(String::as_str).skip(1));
+        while let Some(arg) = opts.next_arg().map_err(|_| Errno::Einval)? {
+            match arg {
+                Arg::Short('r' | 'R') | Arg::Long("recursive") => {
+                    result.recursive = true;
+                }
+                Arg::Short('i') | Arg::Long("interactive") => {
+                    result.prompt_overwrite = true;
+                }
+                Arg::Short('p') | Arg::Long("preserve") => {
+                    result.preserve = true;
+                }
+                Arg::Long("sparse") => {
+                    result.sparse = true;
+                }
+                Arg::Positional(value) => {
+                    result.paths.push(value);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// Copies files and directories.
+///
+/// `-r`/`-R` recurses into directories, recreating the tree at the destination, and `-i` prompts
+/// before overwriting. Copies preserve the source's permission bits; `-p` additionally preserves
+/// timestamps and (if privileged) ownership, and `--sparse` recreates holes in sparse files
+/// instead of writing out their zeros. Multiple sources can be copied into a destination
+/// directory.
+///
+/// # Safety
+///
+/// This program must be passed appropriate `execve`-compatible args.
+#[unsafe(no_mangle)]
+#[allow(unused_variables)]
+unsafe extern "C" fn start(stack_top: *const usize) -> ! {
+    #[cfg(test)]
+    {
+        test_main();
+        process::exit(ExitStatus::ExitSuccess);
+    }
+
+    // HACK: This stops the compiler from complaining when building the test/debug target
+    #[allow(unreachable_code)]
+    #[allow(clippy::no_effect)]
+    ();
+
+    // SAFETY: This function is being called right at the start of execution before anything else.
+    // The stack pointer is retrieved directly from the function args.
+    let (argv, envp) = match unsafe { parse_argv_envp(stack_top) } {
+        Ok(argv_envp) => argv_envp,
+        Err(errno) => process::exit(ExitStatus::ExitFailure(errno as i32)),
+    };
+
+    let exit_code = main(&argv, &envp);
+
+    process::exit(exit_code);
+}
+
+fn main(args: &[String], _env_vars: &[EnvVar]) -> ExitStatus {
+    let settings = try_exit!(CpSettings::from_cli(args));
+    if settings.paths.len() < 2 {
+        eprintln!("Usage: 'cp [-rRi] <source>... <destination>'");
+        return ExitStatus::ExitFailure(255);
+    }
+
+    try_exit!(copy_files(&settings));
+
+    ExitStatus::ExitSuccess
+}
+
+fn copy_files(settings: &CpSettings<'_>) -> Result<(), Errno> {
+    if settings.paths.len() < 2 {
+        return Err(Errno::Einval);
+    }
+
+    let dest_path = settings.paths[settings.paths.len() - 1];
+    let dest_type = FileStats::try_from_path(dest_path)
+        .ok()
+        .and_then(|stats| stats.file_type);
+
+    if settings.paths.len() == 2 && dest_type != Some(FileType::Directory) {
+        // Copying a single thing to a (possibly new) name.
+        return copy_path(settings.paths[0], dest_path, settings);
+    }
+
+    // The destination must be a directory; each source is copied inside it.
+    if dest_type != Some(FileType::Directory) {
+        return Err(Errno::Enotdir);
+    }
+    for &source in settings.paths.iter().take(settings.paths.len() - 1) {
+        copy_path_inside_directory(source, dest_path, settings)?;
+    }
+    Ok(())
+}
+
+fn get_file_name(path: &str) -> Option<&str> {
+    // Trim trailing slashes
+    let trimmed_path = path.trim_end_matches('/');
+
+    // Split on '/' and filter out empty parts
+    let mut parts = trimmed_path.split('/').filter(|&s| !s.is_empty());
+
+    // Get the last non-empty part (if any)
+    let last_part = parts.next_back();
+
+    // Only return if it's not "." or ".."
+    match last_part {
+        Some("." | "..") | None => None,
+        Some(name) => Some(name),
+    }
+}
+
+/// Returns [`Errno::Einval`] if `source` has no usable file name.
+fn copy_path_inside_directory(
+    source: &str,
+    dir_path: &str,
+    settings: &CpSettings<'_>,
+) -> Result<(), Errno> {
+    let dest = dir_path.to_string() + "/" + get_file_name(source).ok_or(Errno::Einval)?;
+    copy_path(source, &dest, settings)
+}
+
+/// Copies one source (file or, with `-r`, directory tree) to the given destination path.
+fn copy_path(source: &str, destination: &str, settings: &CpSettings<'_>) -> Result<(), Errno> {
+    // Refuse to copy a file onto itself (`cp f f`, or two names for the same file); a missing
+    // destination simply fails the identity check.
+    if fs::same_file(source, destination).unwrap_or(false) {
+        eprintln!("cp failed: '{source}' and '{destination}' are the same file");
+        return Err(Errno::Einval);
+    }
+
+    let source_stats = FileStats::try_from_path(source).inspect_err(|&e| {
+        if e == Errno::Enoent {
+            eprintln!("cp failed: Source '{source}' does not exist");
+        }
+    })?;
+
+    if source_stats.file_type == Some(FileType::Directory) {
+        if !settings.recursive {
+            return Err(Errno::Eisdir);
+        }
+        return copy_dir_recursive(source, destination, settings);
+    }
+
+    if settings.prompt_overwrite
+        && FileStats::try_from_path(destination).is_ok()
+        && !prompt_overwrite(destination)?
+    {
+        return Ok(());
+    }
+
+    if settings.sparse {
+        copy_file_sparse(source, destination, source_stats.mode.unwrap_or_default())?;
+    } else {
+        copy_file_contents(source, destination, source_stats.mode.unwrap_or_default())?;
+    }
+    if settings.preserve {
+        preserve_metadata(&source_stats, destination)?;
+    }
+    Ok(())
+}
+
+/// Applies the source's mode, timestamps, and (where permitted) ownership to the copy at the given
+/// destination path.
+fn preserve_metadata(src: &FileStats, dst_path: &str) -> Result<(), Errno> {
+    if let Some(mode) = src.mode {
+        fs::chmod(dst_path, mode)?;
+    }
+    if let (Some(uid), Some(gid)) = (src.uid, src.gid) {
+        // Only a privileged user may hand files to other owners; quietly keep the copy's own
+        // ownership otherwise.
+        match fs::chown(dst_path, uid, gid) {
+            Ok(()) | Err(Errno::Eperm) => {}
+            Err(errno) => return Err(errno),
+        }
+    }
+    fs::set_times(
+        dst_path,
+        src.access_time.unwrap_or(fs::FileTimestamp::OMIT),
+        src.modification_time.unwrap_or(fs::FileTimestamp::OMIT),
+    )
+}
+
+/// Copies a regular file's bytes to a freshly truncated destination with the given mode.
+fn copy_file_contents(
+    source: &str,
+    destination: &str,
+    mode: FilePermissions,
+) -> Result<(), Errno> {
+    let contents = OpenOptions::new().open(source)?.read_to_bytes()?;
+    let dest_file = OpenOptions::new()
+        .write_only()
+        .create(true)
+        .truncate(true)
+        .open(destination)?;
+    let mut written = 0;
+    while written < contents.len() {
+        written += dest_file.write(&contents[written..])?;
+    }
+    // An existing destination keeps its old mode from `open`, so set it explicitly.
+    fs::chmod(destination, mode)
+}
+
+/// The buffer size used when copying the data runs of a sparse file.
+const SPARSE_COPY_CHUNK: usize = 1 << 12;
+
+/// Copies a regular file's bytes to a freshly truncated destination with the given mode, seeking
+/// over the source's holes instead of writing their zeros so the copy occupies as little disk as
+/// the original.
+fn copy_file_sparse(source: &str, destination: &str, mode: FilePermissions) -> Result<(), Errno> {
+    let src_file = OpenOptions::new().open(source)?;
+    let dest_file = OpenOptions::new()
+        .write_only()
+        .create(true)
+        .truncate(true)
+        .open(destination)?;
+
+    let mut offset = 0_u64;
+    loop {
+        // Find the next run of data; `Enxio` means nothing but a (possibly empty) trailing hole
+        // remains.
+        let data_start = match src_file.seek_data(offset) {
+            Ok(position) => position.ok_or(Errno::Espipe)? as u64,
+            Err(Errno::Enxio) => break,
+            Err(errno) => return Err(errno),
+        };
+        let hole_start = src_file.seek_hole(data_start)?.ok_or(Errno::Espipe)? as u64;
+
+        // Copy the data run to the same offset; the unwritten gap before it stays a hole.
+        src_file.set_cursor(i64::try_from(data_start).map_err(|_| Errno::Einval)?)?;
+        dest_file.set_cursor(i64::try_from(data_start).map_err(|_| Errno::Einval)?)?;
+        let mut remaining = usize::try_from(hole_start - data_start).map_err(|_| Errno::Einval)?;
+        let mut buffer = alloc::vec![0_u8; remaining.min(SPARSE_COPY_CHUNK)];
+        while remaining > 0 {
+            let chunk = remaining.min(buffer.len());
+            let read = src_file.read(&mut buffer[..chunk])?;
+            if read == 0 {
+                break;
+            }
+            let mut written = 0;
+            while written < read {
+                written += dest_file.write(&buffer[written..read])?;
+            }
+            remaining -= read;
+        }
+
+        offset = hole_start;
+    }
+
+    // Recreate the file's full length, leaving any trailing hole unwritten.
+    let size = FileStats::try_from_path(source)?.size.ok_or(Errno::Einval)?;
+    dest_file.set_len(size)?;
+
+    // An existing destination keeps its old mode from `open`, so set it explicitly.
+    fs::chmod(destination, mode)
+}
+
+/// Recreates the directory tree rooted at `source` underneath `destination`.
+fn copy_dir_recursive(
+    source: &str,
+    destination: &str,
+    settings: &CpSettings<'_>,
+) -> Result<(), Errno> {
+    let source_stats = FileStats::try_from_path(source)?;
+    match fs::mkdir(destination, source_stats.mode.unwrap_or_default()) {
+        // An already-existing destination directory is fine; copy into it.
+        Ok(()) | Err(Errno::Eexist) => {}
+        Err(errno) => return Err(errno),
+    }
+
+    for dent in OpenOptions::new().open(source)?.dir_ents()? {
+        if dent.name.as_str() == "." || dent.name.as_str() == ".." {
+            continue;
+        }
+        let ent_source = source.to_string() + "/" + dent.name.as_str();
+        let ent_dest = destination.to_string() + "/" + dent.name.as_str();
+        copy_path(&ent_source, &ent_dest, settings)?;
+    }
+    // Restore the directory's own metadata last, after the copies inside have touched it.
+    if settings.preserve {
+        preserve_metadata(&source_stats, destination)?;
+    }
+    Ok(())
+}
+
+/// Asks the user whether the given destination should be overwritten.
+fn prompt_overwrite(destination: &str) -> Result<bool, Errno> {
+    let console = Console::open()?;
+    print!("Overwrite '{destination}'? [y/N] ");
+    Ok(matches!(
+        String::from_utf8(console.read_line(4096)?)
+            .map_err(|_| Errno::Einval)?
+            .to_lowercase()
+            .as_str(),
+        "yes" | "y"
+    ))
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo<'_>) -> ! {
+    eprintln!("{PANIC_TITLE} {info}");
+    process::exit(ExitStatus::ExitFailure(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CP_TEST_DIR: &str = "/tmp/tlenix_cp_test_dir";
+
+    fn test_setup(test_name: &'static str) -> String {
+        let main_dir = CP_TEST_DIR.to_string() + "/" + test_name;
+        let _ = fs::mkdir(CP_TEST_DIR, fs::FilePermissions::from(0o777));
+        let _ = fs::mkdir(&main_dir, fs::FilePermissions::from(0o777));
+        main_dir
+    }
+
+    fn test_teardown(main_dir: &str) {
+        let _ = fs::rmdir(main_dir);
+        let _ = fs::rmdir(CP_TEST_DIR);
+    }
+
+    fn create_file_with_contents(path: &str, contents: &str) {
+        let f = OpenOptions::new()
+            .read_write()
+            .create(true)
+            .open(path)
+            .unwrap();
+        f.write(contents.as_bytes()).unwrap();
+    }
+
+    fn assert_contents(path: &str, expected: &str) {
+        let f = OpenOptions::new().open(path).unwrap();
+        assert_eq!(&f.read_to_string().unwrap(), expected);
+    }
+
+    fn settings<'a>(paths: &'a [&str], recursive: bool) -> CpSettings<'a> {
+        CpSettings {
+            paths: paths.to_vec(),
+            recursive,
+            prompt_overwrite: false,
+            preserve: false,
+            sparse: false,
+        }
+    }
+
+    #[test_case]
+    fn copy_file_to_new_name() {
+        let dir_path = test_setup("copy_file_to_new_name");
+
+        let f1 = dir_path.clone() + "/f1";
+        let f2 = dir_path.clone() + "/f2";
+        let contents = "copy me";
+        create_file_with_contents(&f1, contents);
+
+        let args = [f1.as_str(), f2.as_str()];
+        copy_files(&settings(&args, false)).unwrap();
+
+        // Both the source and the copy exist with the same contents.
+        assert_contents(&f1, contents);
+        assert_contents(&f2, contents);
+
+        fs::rm(&f1).unwrap();
+        fs::rm(&f2).unwrap();
+        test_teardown(&dir_path);
+    }
+
+    #[test_case]
+    fn copy_preserves_mode() {
+        let dir_path = test_setup("copy_preserves_mode");
+
+        let f1 = dir_path.clone() + "/f1";
+        let f2 = dir_path.clone() + "/f2";
+        OpenOptions::new()
+            .write_only()
+            .create(true)
+            .set_mode(0o700)
+            .open(&f1)
+            .unwrap();
+
+        let args = [f1.as_str(), f2.as_str()];
+        copy_files(&settings(&args, false)).unwrap();
+
+        assert_eq!(
+            FileStats::try_from_path(&f2).unwrap().mode,
+            Some(FilePermissions::from(0o700))
+        );
+
+        fs::rm(&f1).unwrap();
+        fs::rm(&f2).unwrap();
+        test_teardown(&dir_path);
+    }
+
+    #[test_case]
+    fn copy_file_into_dir() {
+        let dir_path = test_setup("copy_file_into_dir");
+
+        let f = dir_path.clone() + "/f";
+        let d = dir_path.clone() + "/d";
+        let expected = dir_path.clone() + "/d/f";
+        let contents = "into the directory";
+        create_file_with_contents(&f, contents);
+        fs::mkdir(&d, fs::FilePermissions::from(0o777)).unwrap();
+
+        let args = [f.as_str(), d.as_str()];
+        copy_files(&settings(&args, false)).unwrap();
+
+        assert_contents(&f, contents);
+        assert_contents(&expected, contents);
+
+        fs::rm(&f).unwrap();
+        fs::rm(&expected).unwrap();
+        fs::rmdir(&d).unwrap();
+        test_teardown(&dir_path);
+    }
+
+    #[test_case]
+    fn copy_dir_tree_recursive() {
+        let dir_path = test_setup("copy_dir_tree_recursive");
+
+        let src = dir_path.clone() + "/src";
+        let sub = src.clone() + "/sub";
+        fs::mkdir(&src, fs::FilePermissions::from(0o777)).unwrap();
+        fs::mkdir(&sub, fs::FilePermissions::from(0o777)).unwrap();
+        create_file_with_contents(&(src.clone() + "/f1"), "one");
+        create_file_with_contents(&(sub.clone() + "/f2"), "two");
+
+        let dst = dir_path.clone() + "/dst";
+        let args = [src.as_str(), dst.as_str()];
+        copy_files(&settings(&args, true)).unwrap();
+
+        assert_contents(&(dst.clone() + "/f1"), "one");
+        assert_contents(&(dst.clone() + "/sub/f2"), "two");
+
+        fs::rm(src.clone() + "/f1").unwrap();
+        fs::rm(sub.clone() + "/f2").unwrap();
+        fs::rmdir(&sub).unwrap();
+        fs::rmdir(&src).unwrap();
+        fs::rm(dst.clone() + "/f1").unwrap();
+        fs::rm(dst.clone() + "/sub/f2").unwrap();
+        fs::rmdir(dst.clone() + "/sub").unwrap();
+        fs::rmdir(&dst).unwrap();
+        test_teardown(&dir_path);
+    }
+
+    #[test_case]
+    fn dir_without_recursive_fails() {
+        let dir_path = test_setup("dir_without_recursive_fails");
+
+        let dst = dir_path.clone() + "/dst";
+        let args = [dir_path.as_str(), dst.as_str()];
+        assert_eq!(copy_files(&settings(&args, false)), Err(Errno::Eisdir));
+
+        test_teardown(&dir_path);
+    }
+
+    #[test_case]
+    fn same_file_refused() {
+        let dir_path = test_setup("same_file_refused");
+
+        let f = dir_path.clone() + "/f";
+        let contents = "don't truncate me";
+        create_file_with_contents(&f, contents);
+
+        let args = [f.as_str(), f.as_str()];
+        assert_eq!(copy_files(&settings(&args, false)), Err(Errno::Einval));
+
+        // The refused copy must leave the contents untouched.
+        assert_contents(&f, contents);
+
+        fs::rm(&f).unwrap();
+        test_teardown(&dir_path);
+    }
+
+    #[test_case]
+    fn preserve_keeps_mtime() {
+        let dir_path = test_setup("preserve_keeps_mtime");
+
+        let f1 = dir_path.clone() + "/f1";
+        let f2 = dir_path.clone() + "/f2";
+        let fixed_mtime = fs::FileTimestamp {
+            sec: 1_000_000_000,
+            nsec: 0,
+        };
+        create_file_with_contents(&f1, "old news");
+        fs::set_times(&f1, fs::FileTimestamp::OMIT, fixed_mtime).unwrap();
+
+        let args = [f1.as_str(), f2.as_str()];
+        let mut cps = settings(&args, false);
+        cps.preserve = true;
+        copy_files(&cps).unwrap();
+
+        assert_eq!(
+            FileStats::try_from_path(&f2).unwrap().modification_time,
+            Some(fixed_mtime)
+        );
+
+        fs::rm(&f1).unwrap();
+        fs::rm(&f2).unwrap();
+        test_teardown(&dir_path);
+    }
+
+    #[test_case]
+    fn sparse_copy_keeps_holes() {
+        const APPARENT_SIZE: u64 = 1 << 20;
+
+        let dir_path = test_setup("sparse_copy_keeps_holes");
+
+        let f1 = dir_path.clone() + "/f1";
+        let f2 = dir_path.clone() + "/f2";
+
+        // A few real bytes at the front, then a megabyte-wide trailing hole.
+        let src = OpenOptions::new()
+            .read_write()
+            .create(true)
+            .open(&f1)
+            .unwrap();
+        src.write(b"data!").unwrap();
+        src.set_len(APPARENT_SIZE).unwrap();
+        drop(src);
+
+        let args = [f1.as_str(), f2.as_str()];
+        let mut cps = settings(&args, false);
+        cps.sparse = true;
+        copy_files(&cps).unwrap();
+
+        let stats = FileStats::try_from_path(&f2).unwrap();
+        assert_eq!(stats.size, Some(APPARENT_SIZE));
+        // The hole must have been seeked over, not written out as a megabyte of zeros.
+        assert!(stats.disk_usage().unwrap() < APPARENT_SIZE / 4);
+        assert_eq!(stats.is_sparse(), Some(true));
+
+        // The real bytes still made it across.
+        let mut head = [0_u8; 5];
+        OpenOptions::new().open(&f2).unwrap().read(&mut head).unwrap();
+        assert_eq!(&head, b"data!");
+
+        fs::rm(&f1).unwrap();
+        fs::rm(&f2).unwrap();
+        test_teardown(&dir_path);
+    }
+
+    #[test_case]
+    fn settings_from_cli() {
+        let args = [
+            "cp".to_string(),
+            "-r".to_string(),
+            "a".to_string(),
+            "-ip".to_string(),
+            "--sparse".to_string(),
+            "b".to_string(),
+        ];
+        let result = CpSettings::from_cli(&args).unwrap();
+        assert_eq!(result.paths, ["a", "b"]);
+        assert!(result.recursive);
+        assert!(result.prompt_overwrite);
+        assert!(result.preserve);
+        assert!(result.sparse);
+    }
+}
@@ -0,0 +1,183 @@
+//! Prints the current date and time.
+
+#![warn(
+    missing_docs,
+    missing_debug_implementations,
+    rust_2018_idioms,
+    clippy::all,
+    clippy::pedantic
+)]
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![cfg_attr(test, test_runner(tlenix_core::custom_test_runner))]
+#![cfg_attr(test, reexport_test_harness_main = "test_main")]
+
+extern crate alloc;
+
+use alloc::string::String;
+use core::panic::PanicInfo;
+
+use tlenix_core::{
+    EnvVar, eprintln, format, parse_argv_envp, println,
+    process::{self, ExitStatus},
+    system::{self, ClockId},
+    try_exit,
+};
+
+const PANIC_TITLE: &str = "date";
+
+/// The number of seconds in a day.
+const SECS_PER_DAY: u64 = 86_400;
+
+core::arch::global_asm! {
+    ".global _start",
+    "_start:",
+    "mov rdi, rsp",
+    "call start"
+}
+
+/// A civil (proleptic Gregorian) date and time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CivilDateTime {
+    year: i64,
+    month: u64,
+    day: u64,
+    hour: u64,
+    minute: u64,
+    second: u64,
+}
+
+/// Converts seconds since the Unix epoch into the corresponding civil date and time in UTC.
+///
+/// Uses the days-to-civil-date algorithm built around 400-year Gregorian eras (146097 days each),
+/// so leap years are handled exactly.
+fn civil_from_unix(unix_secs: u64) -> CivilDateTime {
+    let secs_of_day = unix_secs % SECS_PER_DAY;
+
+    // OK to allow here. The day count stays far below the wrapping point for hundreds of millions
+    // of years.
+    #[allow(clippy::cast_possible_wrap)]
+    // Shift the epoch from 1970-01-01 to 0000-03-01, the start of a 400-year era. Starting years
+    // in March pushes the irregular leap day to the very end of the year.
+    let days = (unix_secs / SECS_PER_DAY) as i64 + 719_468;
+
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+
+    // OK to lose sign here; every intermediate value below is non-negative by construction.
+    #[allow(clippy::cast_sign_loss)]
+    let (day, month) = (
+        (day_of_year - (153 * month_index + 2) / 5 + 1) as u64,
+        if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        } as u64,
+    );
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    CivilDateTime {
+        year,
+        month,
+        day,
+        hour: secs_of_day / 3600,
+        minute: (secs_of_day / 60) % 60,
+        second: secs_of_day % 60,
+    }
+}
+
+/// Formats seconds since the Unix epoch as an ISO-8601 UTC string.
+fn fmt_iso8601(unix_secs: u64) -> String {
+    let dt = civil_from_unix(unix_secs);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        dt.year, dt.month, dt.day, dt.hour, dt.minute, dt.second
+    )
+}
+
+/// Prints the current UTC date and time in ISO-8601 format.
+///
+/// # Safety
+///
+/// This program must be passed appropriate `execve`-compatible args.
+#[unsafe(no_mangle)]
+#[allow(unused_variables)]
+unsafe extern "C" fn start(stack_top: *const usize) -> ! {
+    #[cfg(test)]
+    {
+        test_main();
+        process::exit(ExitStatus::ExitSuccess);
+    }
+
+    // HACK: This stops the compiler from complaining when building the test/debug target
+    #[allow(unreachable_code)]
+    #[allow(clippy::no_effect)]
+    ();
+
+    // SAFETY: This function is being called right at the start of execution before anything else.
+    // The stack pointer is retrieved directly from the function args.
+    let (argv, envp) = match unsafe { parse_argv_envp(stack_top) } {
+        Ok(argv_envp) => argv_envp,
+        Err(errno) => process::exit(ExitStatus::ExitFailure(errno as i32)),
+    };
+
+    let exit_code = main(&argv, &envp);
+
+    process::exit(exit_code);
+}
+
+fn main(_args: &[String], _env_vars: &[EnvVar]) -> ExitStatus {
+    let now = try_exit!(system::clock_gettime(ClockId::Realtime));
+    println!("{}", fmt_iso8601(now.as_secs()));
+    ExitStatus::ExitSuccess
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo<'_>) -> ! {
+    eprintln!("{PANIC_TITLE} {info}");
+    process::exit(ExitStatus::ExitFailure(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn epoch_start() {
+        assert_eq!(&fmt_iso8601(0), "1970-01-01T00:00:00Z");
+    }
+
+    #[test_case]
+    fn leap_day() {
+        // 2000 is one of the rare century leap years (divisible by 400).
+        assert_eq!(&fmt_iso8601(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(&fmt_iso8601(951_868_800), "2000-03-01T00:00:00Z");
+    }
+
+    #[test_case]
+    fn end_of_year() {
+        assert_eq!(&fmt_iso8601(1_609_459_199), "2020-12-31T23:59:59Z");
+        assert_eq!(&fmt_iso8601(1_609_459_200), "2021-01-01T00:00:00Z");
+    }
+
+    #[test_case]
+    fn civil_fields() {
+        let dt = civil_from_unix(1_234_567_890);
+        assert_eq!(
+            dt,
+            CivilDateTime {
+                year: 2009,
+                month: 2,
+                day: 13,
+                hour: 23,
+                minute: 31,
+                second: 30,
+            }
+        );
+    }
+}
@@ -0,0 +1,200 @@
+//! Display a line of text.
+
+#![warn(
+    missing_docs,
+    missing_debug_implementations,
+    rust_2018_idioms,
+    clippy::all,
+    clippy::pedantic
+)]
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![cfg_attr(test, test_runner(tlenix_core::custom_test_runner))]
+#![cfg_attr(test, reexport_test_harness_main = "test_main")]
+
+extern crate alloc;
+
+use alloc::string::String;
+use core::panic::PanicInfo;
+
+use tlenix_core::{
+    EnvVar, eprintln, parse_argv_envp, print,
+    process::{self, ExitStatus},
+};
+
+const PANIC_TITLE: &str = "echo";
+
+core::arch::global_asm! {
+    ".global _start",
+    "_start:",
+    "mov rdi, rsp",
+    "call start"
+}
+
+/// Joins its arguments with single spaces and prints them, followed by a newline.
+///
+/// `-n` suppresses the trailing newline, and `-e` enables interpretation of `\n`, `\t`, and `\\`
+/// escapes. Like GNU `echo`, anything that isn't one of these flags (including unknown flags) is
+/// printed as-is.
+///
+/// # Safety
+///
+/// This program must be passed appropriate `execve`-compatible args.
+#[unsafe(no_mangle)]
+#[allow(unused_variables)]
+unsafe extern "C" fn start(stack_top: *const usize) -> ! {
+    #[cfg(test)]
+    {
+        test_main();
+        process::exit(ExitStatus::ExitSuccess);
+    }
+
+    // HACK: This stops the compiler from complaining when building the test/debug target
+    #[allow(unreachable_code)]
+    #[allow(clippy::no_effect)]
+    ();
+
+    // SAFETY: This function is being called right at the start of execution before anything else.
+    // The stack pointer is retrieved directly from the function args.
+    let (argv, envp) = match unsafe { parse_argv_envp(stack_top) } {
+        Ok(argv_envp) => argv_envp,
+        Err(errno) => process::exit(ExitStatus::ExitFailure(errno as i32)),
+    };
+
+    let exit_code = main(&argv, &envp);
+
+    process::exit(exit_code);
+}
+
+fn main(args: &[String], _env_vars: &[EnvVar]) -> ExitStatus {
+    print!("{}", render_line(args));
+    ExitStatus::ExitSuccess
+}
+
+/// Renders the full output of `echo` for the given argv, flags and trailing newline included.
+fn render_line(args: &[String]) -> String {
+    let mut no_newline = false;
+    let mut escapes = false;
+
+    // Flags only count at the front; the first non-flag argument ends flag parsing, and anything
+    // unrecognised (e.g. `--n`) is treated as positional text.
+    let mut words = args.iter().map(String::as_str).skip(1).peekable();
+    while let Some(&word) = words.peek() {
+        match word {
+            "-n" => no_newline = true,
+            "-e" => escapes = true,
+            _ => break,
+        }
+        words.next();
+    }
+
+    let mut output = String::new();
+    let mut first = true;
+    for word in words {
+        if !first {
+            output.push(' ');
+        }
+        first = false;
+        if escapes {
+            output.push_str(&interpret_escapes(word));
+        } else {
+            output.push_str(word);
+        }
+    }
+    if !no_newline {
+        output.push('\n');
+    }
+    output
+}
+
+/// Replaces the `\n`, `\t`, and `\\` escape sequences with the characters they denote. Any other
+/// backslash sequence is left alone.
+fn interpret_escapes(word: &str) -> String {
+    let mut output = String::with_capacity(word.len());
+    let mut chars = word.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => output.push('\n'),
+            Some('t') => output.push('\t'),
+            Some('\\') => output.push('\\'),
+            other => {
+                output.push('\\');
+                if let Some(other) = other {
+                    output.push(other);
+                }
+            }
+        }
+    }
+    output
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo<'_>) -> ! {
+    eprintln!("{PANIC_TITLE} {info}");
+    process::exit(ExitStatus::ExitFailure(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    fn argv(words: &[&str]) -> alloc::vec::Vec<String> {
+        core::iter::once("echo")
+            .chain(words.iter().copied())
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test_case]
+    fn joins_with_single_spaces() {
+        assert_eq!(render_line(&argv(&["hello", "there", "world"])), "hello there world\n");
+    }
+
+    #[test_case]
+    fn no_args_prints_bare_newline() {
+        assert_eq!(render_line(&argv(&[])), "\n");
+    }
+
+    #[test_case]
+    fn n_flag_suppresses_newline() {
+        assert_eq!(render_line(&argv(&["-n", "hi"])), "hi");
+    }
+
+    #[test_case]
+    fn flags_after_text_are_positional() {
+        assert_eq!(render_line(&argv(&["hi", "-n"])), "hi -n\n");
+    }
+
+    #[test_case]
+    fn unknown_flag_is_positional() {
+        assert_eq!(render_line(&argv(&["--n", "hi"])), "--n hi\n");
+    }
+
+    #[test_case]
+    fn e_flag_enables_escapes() {
+        assert_eq!(render_line(&argv(&["-e", r"a\tb\n"])), "a\tb\n\n");
+    }
+
+    #[test_case]
+    fn escapes_off_by_default() {
+        assert_eq!(render_line(&argv(&[r"a\tb"])), "a\\tb\n");
+    }
+
+    #[test_case]
+    fn interpret_known_escapes() {
+        assert_eq!(interpret_escapes(r"a\nb\tc\\d"), "a\nb\tc\\d");
+    }
+
+    #[test_case]
+    fn interpret_leaves_unknown_escapes() {
+        assert_eq!(interpret_escapes(r"a\qb"), "a\\qb");
+        assert_eq!(interpret_escapes("trailing\\"), "trailing\\");
+    }
+}
@@ -0,0 +1,223 @@
+//! Runs a command with a modified environment.
+
+#![warn(
+    missing_docs,
+    missing_debug_implementations,
+    rust_2018_idioms,
+    clippy::all,
+    clippy::pedantic
+)]
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![cfg_attr(test, test_runner(tlenix_core::custom_test_runner))]
+#![cfg_attr(test, reexport_test_harness_main = "test_main")]
+
+extern crate alloc;
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::panic::PanicInfo;
+
+use tlenix_core::{
+    EnvVar, Errno, eprintln, parse_argv_envp, println,
+    process::{self, ExitStatus},
+    try_exit,
+};
+
+const PANIC_TITLE: &str = "env";
+
+core::arch::global_asm! {
+    ".global _start",
+    "_start:",
+    "mov rdi, rsp",
+    "call start"
+}
+
+/// All the things that govern `env`'s behaviour.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct EnvSettings<'a> {
+    /// Start from an empty environment instead of the inherited one.
+    ignore_env: bool,
+    /// Keys to remove from the environment.
+    unset: Vec<&'a str>,
+    /// Leading `KEY=value` tokens to set in the environment.
+    assignments: Vec<&'a str>,
+    /// The command to run (with its args), if any.
+    command: Vec<&'a str>,
+}
+impl<'a> EnvSettings<'a> {
+    /// Partitions the args into flags, leading `KEY=value` assignments, and the command. The first
+    /// token that is neither a flag nor an assignment starts the command; everything after it is
+    /// passed to the command verbatim.
+    fn from_cli(args: &'a [String]) -> Result<Self, Errno> {
+        let mut result = Self::default();
+
+        let mut iter = args.iter().map(String::as_str).skip(1);
+        while let Some(arg) = iter.next() {
+            match arg {
+                "-i" | "--ignore-environment" => result.ignore_env = true,
+                "-u" | "--unset" => result.unset.push(iter.next().ok_or(Errno::Einval)?),
+                _ if arg.contains('=') => result.assignments.push(arg),
+                _ => {
+                    result.command.push(arg);
+                    result.command.extend(iter);
+                    break;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// Builds the environment the command runs with: the inherited environment (or nothing with `-i`),
+/// minus the `-u` keys, with the `KEY=value` assignments applied on top.
+fn build_env(settings: &EnvSettings<'_>, env_vars: &[EnvVar]) -> Result<Vec<EnvVar>, Errno> {
+    let mut env: Vec<EnvVar> = if settings.ignore_env {
+        Vec::new()
+    } else {
+        env_vars.to_vec()
+    };
+    env.retain(|ev| !settings.unset.contains(&ev.key.as_str()));
+
+    for assignment in &settings.assignments {
+        let var = EnvVar::try_from((*assignment).to_string())?;
+        if let Some(existing) = env.iter_mut().find(|ev| ev.key == var.key) {
+            existing.value = var.value;
+        } else {
+            env.push(var);
+        }
+    }
+
+    Ok(env)
+}
+
+/// Runs a command with a modified environment, or prints the (modified) environment when no
+/// command is given.
+///
+/// Leading `KEY=value` args set variables, `-u KEY` unsets one, and `-i` starts from an empty
+/// environment.
+///
+/// # Safety
+///
+/// This program must be passed appropriate `execve`-compatible args.
+#[unsafe(no_mangle)]
+#[allow(unused_variables)]
+unsafe extern "C" fn start(stack_top: *const usize) -> ! {
+    #[cfg(test)]
+    {
+        test_main();
+        process::exit(ExitStatus::ExitSuccess);
+    }
+
+    // HACK: This stops the compiler from complaining when building the test/debug target
+    #[allow(unreachable_code)]
+    #[allow(clippy::no_effect)]
+    ();
+
+    // SAFETY: This function is being called right at the start of execution before anything else.
+    // The stack pointer is retrieved directly from the function args.
+    let (argv, envp) = match unsafe { parse_argv_envp(stack_top) } {
+        Ok(argv_envp) => argv_envp,
+        Err(errno) => process::exit(ExitStatus::ExitFailure(errno as i32)),
+    };
+
+    let exit_code = main(&argv, &envp);
+
+    process::exit(exit_code);
+}
+
+fn main(args: &[String], env_vars: &[EnvVar]) -> ExitStatus {
+    let settings = try_exit!(EnvSettings::from_cli(args));
+    let env = try_exit!(build_env(&settings, env_vars));
+
+    if settings.command.is_empty() {
+        for env_var in &env {
+            println!("{env_var}");
+        }
+        return ExitStatus::ExitSuccess;
+    }
+
+    let envp = env.iter().map(String::from).collect::<Vec<String>>();
+    try_exit!(
+        process::execute_process(&settings.command, &envp).inspect_err(|errno| {
+            eprintln!("env failed: '{}': {errno}", settings.command[0]);
+        })
+    )
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo<'_>) -> ! {
+    eprintln!("{PANIC_TITLE} {info}");
+    process::exit(ExitStatus::ExitFailure(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(words: &[&str]) -> Vec<String> {
+        core::iter::once("env")
+            .chain(words.iter().copied())
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    fn env_var(key: &str, value: &str) -> EnvVar {
+        EnvVar {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test_case]
+    fn assignment_then_command() {
+        let args = argv(&["A=b", "cmd", "arg1", "A=c"]);
+        let settings = EnvSettings::from_cli(&args).unwrap();
+        assert_eq!(settings.assignments, ["A=b"]);
+        // Everything from the command on is passed verbatim, assignments included.
+        assert_eq!(settings.command, ["cmd", "arg1", "A=c"]);
+        assert!(!settings.ignore_env);
+        assert!(settings.unset.is_empty());
+    }
+
+    #[test_case]
+    fn ignore_environment() {
+        let args = argv(&["-i", "cmd"]);
+        let settings = EnvSettings::from_cli(&args).unwrap();
+        assert!(settings.ignore_env);
+        assert_eq!(settings.command, ["cmd"]);
+    }
+
+    #[test_case]
+    fn unset_key() {
+        let args = argv(&["-u", "PATH", "cmd"]);
+        let settings = EnvSettings::from_cli(&args).unwrap();
+        assert_eq!(settings.unset, ["PATH"]);
+        assert_eq!(settings.command, ["cmd"]);
+    }
+
+    #[test_case]
+    fn unset_missing_key_rejected() {
+        let args = argv(&["-u"]);
+        assert_eq!(EnvSettings::from_cli(&args).unwrap_err(), Errno::Einval);
+    }
+
+    #[test_case]
+    fn build_env_applies_settings() {
+        let inherited = [env_var("PATH", "/bin"), env_var("HOME", "/root")];
+
+        let args = argv(&["-u", "PATH", "A=b", "HOME=/tmp", "cmd"]);
+        let settings = EnvSettings::from_cli(&args).unwrap();
+        let env = build_env(&settings, &inherited).unwrap();
+        assert_eq!(env, [env_var("HOME", "/tmp"), env_var("A", "b")]);
+
+        let args = argv(&["-i", "A=b", "cmd"]);
+        let settings = EnvSettings::from_cli(&args).unwrap();
+        let env = build_env(&settings, &inherited).unwrap();
+        assert_eq!(env, [env_var("A", "b")]);
+    }
+}
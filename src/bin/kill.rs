@@ -0,0 +1,231 @@
+//! Sends signals to processes.
+
+#![warn(
+    missing_docs,
+    missing_debug_implementations,
+    rust_2018_idioms,
+    clippy::all,
+    clippy::pedantic
+)]
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![cfg_attr(test, test_runner(tlenix_core::custom_test_runner))]
+#![cfg_attr(test, reexport_test_harness_main = "test_main")]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use core::panic::PanicInfo;
+
+use tlenix_core::{
+    EnvVar, Errno, eprintln,
+    ipc::{self, Signo},
+    parse_argv_envp,
+    process::{self, ExitStatus},
+    try_exit,
+};
+
+const PANIC_TITLE: &str = "kill";
+
+core::arch::global_asm! {
+    ".global _start",
+    "_start:",
+    "mov rdi, rsp",
+    "call start"
+}
+
+/// All the things that govern `kill`'s behaviour.
+#[derive(Debug, PartialEq, Eq)]
+struct KillSettings {
+    /// The signal to send.
+    signo: Signo,
+    /// The PIDs to signal.
+    pids: Vec<i32>,
+}
+impl KillSettings {
+    fn from_cli(args: &[String]) -> Result<Self, Errno> {
+        let mut result = Self {
+            signo: Signo::SigTerm,
+            pids: Vec::new(),
+        };
+
+        let mut iter = args.iter().map(String::as_str).skip(1);
+        while let Some(arg) = iter.next() {
+            if arg == "-s" || arg == "--signal" {
+                result.signo = parse_signo(iter.next().ok_or(Errno::Einval)?)?;
+            } else if let Some(spec) = arg.strip_prefix('-') {
+                result.signo = parse_signo(spec)?;
+            } else {
+                result.pids.push(arg.parse().map_err(|_| Errno::Einval)?);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// Parses a signal specifier, either by number (`9`) or by name with or without the `SIG` prefix
+/// (`SIGKILL`, `kill`).
+fn parse_signo(spec: &str) -> Result<Signo, Errno> {
+    if let Ok(number) = spec.parse::<i32>() {
+        return Signo::try_from(number).map_err(|_| Errno::Einval);
+    }
+
+    let name = spec.to_uppercase();
+    let name = name.strip_prefix("SIG").unwrap_or(&name);
+    match name {
+        "HUP" => Ok(Signo::SigHup),
+        "INT" => Ok(Signo::SigInt),
+        "QUIT" => Ok(Signo::SigQuit),
+        "ILL" => Ok(Signo::SigIll),
+        "TRAP" => Ok(Signo::SigTrap),
+        "ABRT" => Ok(Signo::SigAbrt),
+        "BUS" => Ok(Signo::SigBus),
+        "FPE" => Ok(Signo::SigFpe),
+        "KILL" => Ok(Signo::SigKill),
+        "USR1" => Ok(Signo::SigUsr1),
+        "SEGV" => Ok(Signo::SigSegv),
+        "USR2" => Ok(Signo::SigUsr2),
+        "PIPE" => Ok(Signo::SigPipe),
+        "ALRM" => Ok(Signo::SigAlrm),
+        "TERM" => Ok(Signo::SigTerm),
+        "STKFLT" => Ok(Signo::SigStkflt),
+        "CHLD" => Ok(Signo::SigChld),
+        "CONT" => Ok(Signo::SigCont),
+        "STOP" => Ok(Signo::SigStop),
+        "TSTP" => Ok(Signo::SigTstp),
+        "TTIN" => Ok(Signo::SigTtin),
+        "TTOU" => Ok(Signo::SigTtou),
+        "URG" => Ok(Signo::SigUrg),
+        "XCPU" => Ok(Signo::SigXcpu),
+        "XFSZ" => Ok(Signo::SigXfsz),
+        "VTALRM" => Ok(Signo::SigVtalrm),
+        "PROF" => Ok(Signo::SigProf),
+        "WINCH" => Ok(Signo::SigWinch),
+        "IO" => Ok(Signo::SigIo),
+        "PWR" => Ok(Signo::SigPwr),
+        "SYS" => Ok(Signo::SigSys),
+        _ => Err(Errno::Einval),
+    }
+}
+
+/// Sends a signal (`SIGTERM` unless overridden by `-9`, `-SIGKILL`, or `-s KILL` style specifiers)
+/// to each given PID.
+///
+/// # Safety
+///
+/// This program must be passed appropriate `execve`-compatible args.
+#[unsafe(no_mangle)]
+#[allow(unused_variables)]
+unsafe extern "C" fn start(stack_top: *const usize) -> ! {
+    #[cfg(test)]
+    {
+        test_main();
+        process::exit(ExitStatus::ExitSuccess);
+    }
+
+    // HACK: This stops the compiler from complaining when building the test/debug target
+    #[allow(unreachable_code)]
+    #[allow(clippy::no_effect)]
+    ();
+
+    // SAFETY: This function is being called right at the start of execution before anything else.
+    // The stack pointer is retrieved directly from the function args.
+    let (argv, envp) = match unsafe { parse_argv_envp(stack_top) } {
+        Ok(argv_envp) => argv_envp,
+        Err(errno) => process::exit(ExitStatus::ExitFailure(errno as i32)),
+    };
+
+    let exit_code = main(&argv, &envp);
+
+    process::exit(exit_code);
+}
+
+fn main(args: &[String], _env_vars: &[EnvVar]) -> ExitStatus {
+    let settings = try_exit!(KillSettings::from_cli(args).inspect_err(|_| {
+        eprintln!("Usage: 'kill [-<signal> | -s <signal>] <pid>...'");
+    }));
+    if settings.pids.is_empty() {
+        eprintln!("Usage: 'kill [-<signal> | -s <signal>] <pid>...'");
+        return ExitStatus::ExitFailure(255);
+    }
+
+    let mut success = true;
+    for &pid in &settings.pids {
+        if let Err(errno) = ipc::kill(pid, settings.signo) {
+            eprintln!("kill failed: '{pid}': {errno}");
+            success = false;
+        }
+    }
+
+    if success {
+        ExitStatus::ExitSuccess
+    } else {
+        ExitStatus::ExitFailure(1)
+    }
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo<'_>) -> ! {
+    eprintln!("{PANIC_TITLE} {info}");
+    process::exit(ExitStatus::ExitFailure(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    fn argv(words: &[&str]) -> Vec<String> {
+        core::iter::once("kill")
+            .chain(words.iter().copied())
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test_case]
+    fn numeric_specifier() {
+        let settings = KillSettings::from_cli(&argv(&["-9", "123", "456"])).unwrap();
+        assert_eq!(settings.signo, Signo::SigKill);
+        assert_eq!(settings.pids, [123, 456]);
+    }
+
+    #[test_case]
+    fn name_specifier() {
+        let settings = KillSettings::from_cli(&argv(&["-SIGINT", "1"])).unwrap();
+        assert_eq!(settings.signo, Signo::SigInt);
+        assert_eq!(settings.pids, [1]);
+    }
+
+    #[test_case]
+    fn separate_signal_arg() {
+        let settings = KillSettings::from_cli(&argv(&["-s", "TERM", "42"])).unwrap();
+        assert_eq!(settings.signo, Signo::SigTerm);
+        assert_eq!(settings.pids, [42]);
+    }
+
+    #[test_case]
+    fn default_is_sigterm() {
+        let settings = KillSettings::from_cli(&argv(&["7"])).unwrap();
+        assert_eq!(settings.signo, Signo::SigTerm);
+        assert_eq!(settings.pids, [7]);
+    }
+
+    #[test_case]
+    fn unknown_signal_rejected() {
+        assert_eq!(parse_signo("SIGWHAT"), Err(Errno::Einval));
+        assert_eq!(parse_signo("64"), Err(Errno::Einval));
+        assert_eq!(
+            KillSettings::from_cli(&argv(&["-SIGWHAT", "1"])).unwrap_err(),
+            Errno::Einval
+        );
+    }
+
+    #[test_case]
+    fn case_insensitive_names() {
+        assert_eq!(parse_signo("kill"), Ok(Signo::SigKill));
+        assert_eq!(parse_signo("sighup"), Ok(Signo::SigHup));
+    }
+}
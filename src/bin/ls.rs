@@ -21,7 +21,9 @@ use core::panic::PanicInfo;
 use getargs::{Arg, Options};
 
 use tlenix_core::{
-    EnvVar, Errno, eprintln, fs, parse_argv_envp, println,
+    EnvVar, Errno, eprintln, format,
+    fs::{self, FileType},
+    parse_argv_envp, println,
     process::{self, ExitStatus},
     try_exit,
 };
@@ -52,6 +54,8 @@ struct LsSettings<'a> {
     path: &'a str,
     /// The text which separates the directory entries.
     separator: &'static str,
+    /// Whether or not to show the long-format metadata columns.
+    long: bool,
     /// Whether or not to filter out hidden files.
     filter_hidden: bool,
     /// Whether or not to filter out "." and "..".
@@ -66,12 +70,19 @@ impl<'a> TryFrom<&'a [String]> for LsSettings<'a> {
         let mut separator = ENTRY_SEPARATOR;
         let mut path = DEFAULT_PATH;
         let mut got_path = false;
+        let mut long = false;
         let mut filter_dotfiles = true;
         let mut filter_implied = true;
 
         while let Some(arg) = opts.next_arg().map_err(|_| Errno::Einval)? {
             match arg {
-                Arg::Short('l') | Arg::Long("list" | "long") => separator = LIST_ENTRY_SEPARATOR,
+                Arg::Short('l') | Arg::Long("list" | "long") => {
+                    separator = LIST_ENTRY_SEPARATOR;
+                    long = true;
+                }
+                // One entry per line, *without* the long-format columns. Like GNU `ls`, `-l`
+                // takes precedence if both are given.
+                Arg::Short('1') => separator = LIST_ENTRY_SEPARATOR,
                 Arg::Short('a') | Arg::Long("all") => {
                     filter_dotfiles = false;
                     filter_implied = false;
@@ -91,6 +102,7 @@ impl<'a> TryFrom<&'a [String]> for LsSettings<'a> {
         Ok(Self {
             path,
             separator,
+            long,
             filter_hidden: filter_dotfiles,
             filter_implied,
         })
@@ -131,12 +143,21 @@ unsafe extern "C" fn start(stack_top: *const usize) -> ! {
 fn main(args: &[String], _env_vars: &[EnvVar]) -> ExitStatus {
     let ls_settings = try_exit!(LsSettings::try_from(args));
     let dent_names = try_exit!(dent_names(ls_settings.path));
-    let out_str = fmt_str(
-        dent_names,
-        ls_settings.separator,
-        ls_settings.filter_hidden,
-        ls_settings.filter_implied,
-    );
+    let out_str = if ls_settings.long {
+        try_exit!(fmt_long(
+            dent_names,
+            ls_settings.path,
+            ls_settings.filter_hidden,
+            ls_settings.filter_implied,
+        ))
+    } else {
+        fmt_str(
+            dent_names,
+            ls_settings.separator,
+            ls_settings.filter_hidden,
+            ls_settings.filter_implied,
+        )
+    };
 
     println!("{out_str}");
 
@@ -160,17 +181,59 @@ fn dent_names(path: &str) -> Result<Vec<String>, Errno> {
 
 /// Sorts the given list of names, filters hidden files, and joins them with the given separator.
 fn fmt_str(
-    mut names: Vec<String>,
+    names: Vec<String>,
     separator: &str,
     filter_hidden: bool,
     filter_implied: bool,
 ) -> String {
+    filter_sort(names, filter_hidden, filter_implied).join(separator)
+}
+
+/// Sorts the given list of names, filters them, and formats one long-format line per entry.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned while statting an entry.
+fn fmt_long(
+    names: Vec<String>,
+    dir_path: &str,
+    filter_hidden: bool,
+    filter_implied: bool,
+) -> Result<String, Errno> {
+    let names = filter_sort(names, filter_hidden, filter_implied);
+    let mut lines = Vec::with_capacity(names.len());
+    for name in &names {
+        lines.push(long_line(dir_path, name)?);
+    }
+    Ok(lines.join(LIST_ENTRY_SEPARATOR))
+}
+
+/// Formats one long-format entry: file type character, octal mode, size in bytes, and name.
+fn long_line(dir_path: &str, name: &str) -> Result<String, Errno> {
+    let stats = fs::FileStats::try_from_path(format!("{dir_path}/{name}"))?;
+    let type_char = match stats.file_type {
+        Some(FileType::Directory) => 'd',
+        Some(FileType::SymbolicLink) => 'l',
+        Some(FileType::CharacterDevice) => 'c',
+        Some(FileType::BlockDevice) => 'b',
+        Some(FileType::Fifo) => 'p',
+        Some(FileType::Socket) => 's',
+        Some(FileType::RegularFile) => '-',
+        None => '?',
+    };
+    let mode = stats.mode.map_or(0, |mode| mode.bits());
+    let size = stats.size.unwrap_or_default();
+    Ok(format!("{type_char}{mode:04o} {size:>8} {name}"))
+}
+
+/// Sorts the given list of names and filters hidden/implied entries.
+fn filter_sort(mut names: Vec<String>, filter_hidden: bool, filter_implied: bool) -> Vec<String> {
     names.sort_unstable();
     names.retain(|n| {
         !(filter_hidden && n.starts_with(HIDDEN_PREFIX))
             && !(filter_implied && (n == THIS_DIR || n == SUPER_DIR))
     });
-    names.join(separator)
+    names
 }
 
 #[panic_handler]
@@ -248,7 +311,7 @@ mod tests {
     }
 
     macro_rules! lss_test {
-        ($test_name:ident([$($s:literal),*] => ($path:expr, $sep:expr, $fh:expr, $fi:expr))) => {
+        ($test_name:ident([$($s:literal),*] => ($path:expr, $sep:expr, $long:expr, $fh:expr, $fi:expr))) => {
             #[test_case]
             fn $test_name() {
                 let strings = ["ls".to_string(), $($s.to_string()),*];
@@ -256,6 +319,7 @@ mod tests {
                 let expected = LsSettings {
                     path: $path,
                     separator: $sep,
+                    long: $long,
                     filter_hidden: $fh,
                     filter_implied: $fi,
                 };
@@ -264,22 +328,56 @@ mod tests {
         };
     }
 
-    lss_test!(lss_empty([] => (DEFAULT_PATH, ENTRY_SEPARATOR, true, true)));
-    lss_test!(lss_dir(["/"] => ("/", ENTRY_SEPARATOR, true, true)));
-    lss_test!(lss_l(["-l"] => (DEFAULT_PATH, LIST_ENTRY_SEPARATOR, true, true)));
-    lss_test!(lss_l_before_dir(["-l", "mydir"] => ("mydir", LIST_ENTRY_SEPARATOR, true, true)));
-    lss_test!(lss_l_after_dir(["mydir", "-l"] => ("mydir", LIST_ENTRY_SEPARATOR, true, true)));
-    lss_test!(lss_extra_flags(["-bks", "mydir", "-lhk"] => ("mydir", LIST_ENTRY_SEPARATOR, true, true)));
-    lss_test!(lss_long_l_after(["mydir", "--long"] => ("mydir", LIST_ENTRY_SEPARATOR, true, true)));
-    lss_test!(lss_long_l_before(["--long", "mydir"] => ("mydir", LIST_ENTRY_SEPARATOR, true, true)));
-    lss_test!(lss_list_l_after(["mydir", "--list"] => ("mydir", LIST_ENTRY_SEPARATOR, true, true)));
-    lss_test!(lss_list_l_before(["--list", "mydir"] => ("mydir", LIST_ENTRY_SEPARATOR, true, true)));
-    lss_test!(lss_a(["-a"] => (DEFAULT_PATH, ENTRY_SEPARATOR, false, false)));
-    lss_test!(lss_aa(["-A"] => (DEFAULT_PATH, ENTRY_SEPARATOR, false, true)));
-    lss_test!(lss_implied_overwrite(["-aA"] => (DEFAULT_PATH, ENTRY_SEPARATOR, false, true)));
-    lss_test!(lss_hidden_overwrite(["-A", "mydir", "-a"] => ("mydir", ENTRY_SEPARATOR, false, false)));
-    lss_test!(lss_la(["mydir", "-la"] => ("mydir", LIST_ENTRY_SEPARATOR, false, false)));
-    lss_test!(lss_aal(["-A", "mydir", "-l"] => ("mydir", LIST_ENTRY_SEPARATOR, false, true)));
+    lss_test!(lss_empty([] => (DEFAULT_PATH, ENTRY_SEPARATOR, false, true, true)));
+    lss_test!(lss_dir(["/"] => ("/", ENTRY_SEPARATOR, false, true, true)));
+    lss_test!(lss_l(["-l"] => (DEFAULT_PATH, LIST_ENTRY_SEPARATOR, true, true, true)));
+    lss_test!(lss_l_before_dir(["-l", "mydir"] => ("mydir", LIST_ENTRY_SEPARATOR, true, true, true)));
+    lss_test!(lss_l_after_dir(["mydir", "-l"] => ("mydir", LIST_ENTRY_SEPARATOR, true, true, true)));
+    lss_test!(lss_extra_flags(["-bks", "mydir", "-lhk"] => ("mydir", LIST_ENTRY_SEPARATOR, true, true, true)));
+    lss_test!(lss_long_l_after(["mydir", "--long"] => ("mydir", LIST_ENTRY_SEPARATOR, true, true, true)));
+    lss_test!(lss_long_l_before(["--long", "mydir"] => ("mydir", LIST_ENTRY_SEPARATOR, true, true, true)));
+    lss_test!(lss_list_l_after(["mydir", "--list"] => ("mydir", LIST_ENTRY_SEPARATOR, true, true, true)));
+    lss_test!(lss_list_l_before(["--list", "mydir"] => ("mydir", LIST_ENTRY_SEPARATOR, true, true, true)));
+    lss_test!(lss_a(["-a"] => (DEFAULT_PATH, ENTRY_SEPARATOR, false, false, false)));
+    lss_test!(lss_aa(["-A"] => (DEFAULT_PATH, ENTRY_SEPARATOR, false, false, true)));
+    lss_test!(lss_implied_overwrite(["-aA"] => (DEFAULT_PATH, ENTRY_SEPARATOR, false, false, true)));
+    lss_test!(lss_hidden_overwrite(["-A", "mydir", "-a"] => ("mydir", ENTRY_SEPARATOR, false, false, false)));
+    lss_test!(lss_la(["mydir", "-la"] => ("mydir", LIST_ENTRY_SEPARATOR, true, false, false)));
+    lss_test!(lss_aal(["-A", "mydir", "-l"] => ("mydir", LIST_ENTRY_SEPARATOR, true, false, true)));
+    lss_test!(lss_one(["-1"] => (DEFAULT_PATH, LIST_ENTRY_SEPARATOR, false, true, true)));
+    lss_test!(lss_one_then_l(["-1", "-l"] => (DEFAULT_PATH, LIST_ENTRY_SEPARATOR, true, true, true)));
+    lss_test!(lss_l_then_one(["-l", "-1"] => (DEFAULT_PATH, LIST_ENTRY_SEPARATOR, true, true, true)));
+
+    #[test_case]
+    fn one_per_line_has_no_columns() {
+        let names = Vec::from(["b".to_string(), "a".to_string()]);
+        let out = fmt_str(names, LIST_ENTRY_SEPARATOR, false, false);
+        // Just the bare names, one per line — no mode/size columns.
+        assert_eq!(out, "a\nb");
+        assert!(!out.contains(' '));
+    }
+
+    #[test_case]
+    fn long_line_has_columns() {
+        const PATH: &str = "/tmp/tlenix_ls_long_line";
+        const NAME: &str = "f";
+
+        fs::mkdir(PATH, fs::FilePermissions::from(0o755)).unwrap();
+        let f = fs::OpenOptions::new()
+            .write_only()
+            .create(true)
+            .set_mode(0o644)
+            .open(PATH.to_string() + "/" + NAME)
+            .unwrap();
+        f.write(b"12345").unwrap();
+
+        let line = long_line(PATH, NAME).unwrap();
+
+        fs::rm(PATH.to_string() + "/" + NAME).unwrap();
+        fs::rmdir(PATH).unwrap();
+
+        assert_eq!(line, "-0644        5 f");
+    }
 
     fn compare_dent_result(mut dents: Vec<String>, expected: &[&'static str]) {
         let mut expected = expected
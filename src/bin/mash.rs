@@ -12,6 +12,7 @@
 #![no_main]
 #![feature(custom_test_frameworks)]
 #![cfg_attr(test, test_runner(tlenix_core::custom_test_runner))]
+#![cfg_attr(test, reexport_test_harness_main = "test_main")]
 
 extern crate alloc;
 
@@ -23,10 +24,10 @@ use core::panic::PanicInfo;
 use num_enum::TryFromPrimitive;
 
 use tlenix_core::{
-    Console, EnvVar, Errno, align_stack_pointer, eprintln,
-    fs::{self, FilePermissions},
-    print,
-    process::{self, ExitStatus},
+    Console, EnvVar, Errno, align_stack_pointer, eprintln, format,
+    fs::{self, File, FilePermissions},
+    ipc, print, println,
+    process::{self, ExitStatus, WaitOptions},
     system,
 };
 
@@ -47,9 +48,18 @@ const ENVIRONMENT_COMMENT: char = '#';
 /// Name of the `PATH` environment variable.
 const PATH_ENV_VAR_NAME: &str = "PATH";
 
+/// Name of the `OLDPWD` environment variable, which tracks the previous working directory.
+const OLDPWD_ENV_VAR_NAME: &str = "OLDPWD";
+
+/// `cd` argument meaning "go back to the previous working directory".
+const CD_PREVIOUS: &str = "-";
+
 /// Character separating the various `PATH` environment variable paths.
 const PATH_SEPARATOR: char = ':';
 
+/// Shell builtins. These only make sense as a sole command, never inside a pipeline.
+const BUILTINS: [&str; 5] = ["exit", "poweroff", "reboot", "cd", "export"];
+
 // Home directory.
 #[cfg(debug_assertions)]
 const HOME_DIR: &str = "/";
@@ -72,7 +82,10 @@ extern "C" fn _start() -> ! {
     align_stack_pointer!();
 
     #[cfg(test)]
-    process::exit(process::ExitStatus::ExitSuccess);
+    {
+        test_main();
+        process::exit(process::ExitStatus::ExitSuccess);
+    }
 
     // HACK: This stops the compiler from complaining when building the test/debug target
     #[allow(unreachable_code)]
@@ -80,81 +93,781 @@ extern "C" fn _start() -> ! {
     ();
 
     let console = Console::open().unwrap();
+    let mut history = History::default();
+    // Session-scoped variables (`PWD`/`OLDPWD`) which outlive a single loop iteration.
+    let mut session_env: Vec<EnvVar> = Vec::new();
+    // Reused across loops so the prompt doesn't reallocate every time.
+    let mut cwd_buf = String::new();
     loop {
-        print_prompt();
+        print_prompt(&mut cwd_buf);
+
+        // Get argv, with the arrow keys recalling earlier commands.
+        let line = console
+            .read_line_interactive(LINE_MAX, history.entries())
+            .unwrap();
+        let raw_line_string = String::from_utf8(line).unwrap();
 
-        // Get argv.
-        let line = console.read_line(LINE_MAX).unwrap();
-        let line_string = String::from_utf8(line).unwrap();
-        let mut argv: Vec<&str> = line_string.split_whitespace().collect();
+        // Expand any `!!`/`!N`/`!prefix` history references.
+        let Ok(line_string) = expand_history(&raw_line_string, &history) else {
+            eprintln!("Event not found.");
+            continue;
+        };
+        if line_string != raw_line_string {
+            // Echo the expanded command so the user sees what actually runs.
+            println!("{line_string}");
+        }
+        history.push(&line_string);
 
-        // Read env vars.
-        let env_vars = read_env_vars();
+        // Read env vars, overlaying the session-scoped ones.
+        let mut env_vars = read_env_vars();
+        overlay_env(&mut env_vars, &session_env);
         let envp = env_vars.iter().map(String::from).collect::<Vec<String>>();
 
         // Do nothing if nothing was typed
-        if argv.is_empty() {
+        if line_string.split_whitespace().next().is_none() {
             eprintln!("doing nothin'");
             continue;
         }
 
-        match (argv[0], argv.len()) {
-            ("exit", 1) => process::exit(process::ExitStatus::ExitSuccess),
-            ("poweroff", 1) => {
-                let errno = system::power_off().unwrap_err();
-                eprintln!("poweroff fail: {}", errno.as_str());
-            }
-            ("reboot", 1) => {
-                let errno = system::reboot().unwrap_err();
-                eprintln!("reboot fail: {}", errno.as_str());
+        // Evaluate the `&&`/`||` chain left-to-right, short-circuiting on each command's status.
+        let Ok(chain) = split_chain(&line_string) else {
+            eprintln!("Syntax error near `&&`/`||`/`;`.");
+            continue;
+        };
+        let mut success = true;
+        let mut pending: Option<Connector> = None;
+        for (command, connector) in chain {
+            let run = match pending {
+                None | Some(Connector::Seq) => true,
+                Some(Connector::And) => success,
+                Some(Connector::Or) => !success,
+            };
+            if run {
+                success = run_command(command, &env_vars, &envp, &mut session_env);
             }
-            ("cd", 1) => {
-                if let Err(e) = fs::change_dir(HOME_DIR) {
-                    eprintln!("{e}");
+            pending = connector;
+        }
+    }
+}
+
+/// Runs one (possibly piped or redirected) command, returning whether it reported success.
+/// Builtins report into the chain too: a failed `cd` counts as a failure.
+fn run_command(
+    command: &str,
+    env_vars: &[EnvVar],
+    envp: &[String],
+    session_env: &mut Vec<EnvVar>,
+) -> bool {
+    // Expand `$VAR`/`${VAR}` references in each word.
+    let argv_strings: Vec<String> = command
+        .split_whitespace()
+        .map(|word| expand(word, env_vars))
+        .collect();
+    let argv: Vec<&str> = argv_strings.iter().map(String::as_str).collect();
+
+    if argv.is_empty() {
+        eprintln!("Missing command.");
+        return false;
+    }
+
+    // Pipelines get their own path; the match below only handles sole commands.
+    let segments = split_pipeline(command);
+    if segments.len() > 1 {
+        return run_pipeline(&segments, env_vars, envp);
+    }
+
+    match (argv[0], argv.len()) {
+        ("exit", 1) => process::exit(process::ExitStatus::ExitSuccess),
+        ("poweroff", 1) => {
+            let errno = system::power_off().unwrap_err();
+            eprintln!("poweroff fail: {}", errno.as_str());
+            false
+        }
+        ("reboot", 1) => {
+            let errno = system::reboot().unwrap_err();
+            eprintln!("reboot fail: {}", errno.as_str());
+            false
+        }
+        ("cd", 1 | 2) => {
+            let Ok(target) = resolve_cd_target(argv.get(1).copied(), session_env, HOME_DIR) else {
+                eprintln!("cd: OLDPWD not set");
+                return false;
+            };
+            if let Err(e) = fs::change_dir_tracked(target.as_str(), session_env) {
+                eprintln!("{e}");
+                false
+            } else {
+                if argv.get(1).copied() == Some(CD_PREVIOUS) {
+                    // Like other shells, `cd -` announces where it ended up.
+                    println!("{target}");
                 }
+                true
+            }
+        }
+        ("export", 2) => match parse_export(argv[1]) {
+            Ok(Some(var)) => {
+                overlay_env(session_env, &[var]);
+                true
+            }
+            // A bare `export KEY` leaves the variable unset.
+            Ok(None) => true,
+            Err(_) => {
+                eprintln!("export: bad assignment `{}`", argv[1]);
+                false
             }
-            ("cd", 2) => {
-                if let Err(e) = fs::change_dir(argv[1]) {
-                    eprintln!("{e}");
+        },
+        (_, _) => {
+            let Ok(mut parsed) = parse_redirections(&argv) else {
+                eprintln!("Missing redirection target.");
+                return false;
+            };
+            let Some(&argv0) = parsed.argv.first() else {
+                eprintln!("Missing command.");
+                return false;
+            };
+            let new_argv0 = match program_path_subst(argv0, env_vars) {
+                Ok(new_argv0) => new_argv0,
+                Err(Errno::Enoent) => {
+                    eprintln!("Unrecognised command.");
+                    return false;
                 }
+                Err(errno) => {
+                    eprintln!("Program path substitute fail: {errno}");
+                    return false;
+                }
+            };
+            parsed.argv[0] = &new_argv0;
+
+            // Here-documents and here-strings become literal stdin text up front.
+            let text = match stdin_text(&parsed, env_vars) {
+                Ok(text) => text,
+                Err(errno) => {
+                    eprintln!("here-document: {errno}");
+                    return false;
+                }
+            };
+
+            if parsed.stdin.is_none() && parsed.stdout.is_none() {
+                report_exit(parsed.argv[0], process::execute_process(&parsed.argv, envp))
+            } else {
+                run_redirected(&parsed, text.as_deref(), envp)
             }
-            (_, _) => {
-                let new_argv0 = match program_path_subst(argv[0], &env_vars) {
-                    Ok(new_argv0) => new_argv0,
-                    Err(Errno::Enoent) => {
-                        eprintln!("Unrecognised command.");
-                        continue;
-                    }
-                    Err(errno) => {
-                        eprintln!("Program path substitute fail: {errno}");
-                        continue;
-                    }
-                };
-                argv[0] = &new_argv0;
-
-                match process::execute_process(&argv, &envp) {
-                    Ok(ExitStatus::ExitFailure(code)) => {
-                        if let Ok(errno) = Errno::try_from_primitive(code) {
-                            eprintln!("{}: {}", argv[0], errno);
-                        } else {
-                            eprintln!("{}: Process exited with failure code {}.", argv[0], code);
+        }
+    }
+}
+
+/// The connector between two chained commands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Connector {
+    /// `&&`: the next command only runs if this one succeeds.
+    And,
+    /// `||`: the next command only runs if this one fails.
+    Or,
+    /// `;`: the next command runs unconditionally.
+    Seq,
+}
+
+/// Splits a command line into `&&`/`||`/`;`-connected commands, pairing each command with the
+/// connector following it (`None` for the last one). Unquoted single `|`s are left alone for
+/// [`split_pipeline`].
+///
+/// # Errors
+///
+/// This function returns [`Errno::Einval`] if a connector is missing a command on either side.
+/// (A trailing `;` is fine.)
+fn split_chain(line: &str) -> Result<Vec<(&str, Option<Connector>)>, Errno> {
+    let mut chain = Vec::new();
+    let mut segment_start = 0;
+    let mut in_single = false;
+    let mut in_double = false;
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        let connector = match c {
+            b'\'' if !in_double => {
+                in_single = !in_single;
+                None
+            }
+            b'"' if !in_single => {
+                in_double = !in_double;
+                None
+            }
+            b'&' | b'|' if !in_single && !in_double && bytes.get(i + 1) == Some(&c) => {
+                Some(if c == b'&' { Connector::And } else { Connector::Or })
+            }
+            b';' if !in_single && !in_double => Some(Connector::Seq),
+            _ => None,
+        };
+        let Some(connector) = connector else {
+            i += 1;
+            continue;
+        };
+        let command = &line[segment_start..i];
+        if command.trim().is_empty() {
+            return Err(Errno::Einval);
+        }
+        chain.push((command, Some(connector)));
+        i += if connector == Connector::Seq { 1 } else { 2 };
+        segment_start = i;
+    }
+    let last = &line[segment_start..];
+    if last.trim().is_empty() {
+        // A trailing `;` is allowed; a dangling `&&`/`||` (or a bare `;`) isn't.
+        if matches!(chain.last(), Some((_, Some(Connector::Seq)))) {
+            return Ok(chain);
+        }
+        return Err(Errno::Einval);
+    }
+    chain.push((last, None));
+    Ok(chain)
+}
+
+/// Expands `$VAR` and `${VAR}` references in the given word against the environment.
+///
+/// Unset variables expand to the empty string, `\$` escapes a literal `$`, and references inside
+/// single quotes are left alone. A lone `$` is taken literally.
+fn expand(token: &str, env_vars: &[EnvVar]) -> String {
+    let mut expanded = String::with_capacity(token.len());
+    let mut chars = token.chars().peekable();
+    let mut in_single = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_single = !in_single;
+                expanded.push(c);
+            }
+            '\\' if !in_single && chars.peek() == Some(&'$') => {
+                expanded.push(chars.next().unwrap_or('$'));
+            }
+            '$' if !in_single => {
+                let name = if chars.peek() == Some(&'{') {
+                    // `${VAR}`: everything up to the closing brace.
+                    chars.next();
+                    let mut name = String::new();
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            break;
                         }
+                        name.push(c);
                     }
-                    Ok(ExitStatus::Terminated(signo)) => {
-                        eprintln!("{}: Process terminated {}", argv[0], signo);
-                    }
-                    Err(e) => {
-                        eprintln!("{}: {}", argv[0], e);
-                    }
-                    #[allow(unused_variables)]
-                    other => {
-                        #[cfg(debug_assertions)]
-                        eprintln!("{}: {:?}", argv[0], other);
+                    name
+                } else {
+                    // `$VAR`: the longest run of name characters.
+                    let mut name = String::new();
+                    while let Some(&c) = chars.peek() {
+                        if !c.is_ascii_alphanumeric() && c != '_' {
+                            break;
+                        }
+                        name.push(c);
+                        chars.next();
                     }
+                    name
+                };
+                if name.is_empty() {
+                    expanded.push('$');
+                } else if let Some(env_var) = env_vars.iter().find(|ev| ev.key == name) {
+                    expanded.push_str(&env_var.value);
+                }
+            }
+            _ => expanded.push(c),
+        }
+    }
+    expanded
+}
+
+/// How a command's stdout redirection target should be opened.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputMode {
+    /// `>`: truncate the target file.
+    Truncate,
+    /// `>>`: append to the target file.
+    Append,
+}
+
+/// A command's stdin redirection source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum StdinSource<'a> {
+    /// `< file`: redirect stdin from a file.
+    Path(&'a str),
+    /// `<<< text`: feed the (possibly quoted) word itself to stdin.
+    HereString(&'a str),
+    /// `<< DELIM`: feed a here-document body, read up to the delimiter line, to stdin.
+    HereDoc(&'a str),
+}
+
+/// A command's argv with any redirection targets split out.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ParsedCommand<'a> {
+    /// The command's arguments, with the redirection tokens removed.
+    argv: Vec<&'a str>,
+    /// Where to redirect stdin from (`< file`/`<< DELIM`/`<<< text`), if anywhere.
+    stdin: Option<StdinSource<'a>>,
+    /// The file to redirect stdout to (`> file`/`>> file`), if any.
+    stdout: Option<(&'a str, OutputMode)>,
+}
+
+/// Separates `<`, `<<`, `<<<`, `>`, and `>>` redirections from the rest of a command's words.
+///
+/// Repeated redirections of the same stream keep the last one, like other shells.
+///
+/// # Errors
+///
+/// This function returns [`Errno::Einval`] if a redirection operator has no target word after it.
+fn parse_redirections<'a>(words: &[&'a str]) -> Result<ParsedCommand<'a>, Errno> {
+    let mut parsed = ParsedCommand::default();
+    let mut words = words.iter();
+    while let Some(&word) = words.next() {
+        let mode = match word {
+            "<" | "<<" | "<<<" => None,
+            ">" => Some(OutputMode::Truncate),
+            ">>" => Some(OutputMode::Append),
+            _ => {
+                parsed.argv.push(word);
+                continue;
+            }
+        };
+        let &target = words.next().ok_or(Errno::Einval)?;
+        match mode {
+            None => {
+                parsed.stdin = Some(match word {
+                    "<<" => StdinSource::HereDoc(target),
+                    "<<<" => StdinSource::HereString(target),
+                    _ => StdinSource::Path(target),
+                });
+            }
+            Some(mode) => parsed.stdout = Some((target, mode)),
+        }
+    }
+    Ok(parsed)
+}
+
+/// Strips one layer of matching surrounding quotes from a here-document delimiter or here-string
+/// word, additionally reporting whether the associated text should have its variables expanded
+/// (quoting the word turns expansion off, like other shells).
+fn strip_quotes(word: &str) -> (&str, bool) {
+    let quoted = word.len() >= 2
+        && ((word.starts_with('\'') && word.ends_with('\''))
+            || (word.starts_with('"') && word.ends_with('"')));
+    if quoted {
+        (&word[1..word.len() - 1], false)
+    } else {
+        (word, true)
+    }
+}
+
+/// Collects the body of a here-document from the given lines: everything up to (but not
+/// including) the line matching the delimiter. The delimiter may be quoted; the quotes only
+/// affect matching here — the body always comes back verbatim, and any variable expansion is the
+/// caller's job.
+///
+/// # Errors
+///
+/// This function returns [`Errno::Einval`] if the lines run out before the delimiter appears.
+fn collect_heredoc<'a>(
+    lines: &mut impl Iterator<Item = &'a str>,
+    delim: &str,
+) -> Result<String, Errno> {
+    let (delim, _) = strip_quotes(delim);
+    let mut body = String::new();
+    for line in lines {
+        if line.trim_end() == delim {
+            return Ok(body);
+        }
+        body.push_str(line);
+        body.push('\n');
+    }
+    Err(Errno::Einval)
+}
+
+/// The most lines a here-document body may span before it counts as unterminated.
+const HEREDOC_MAX_LINES: usize = 1024;
+
+/// Reads a here-document body from the console, prompting with `> ` for each line until the
+/// delimiter line arrives.
+///
+/// # Errors
+///
+/// This function returns [`Errno::Einval`] if no delimiter line arrives within
+/// [`HEREDOC_MAX_LINES`] lines, and propagates any I/O errors while reading.
+fn read_heredoc_console(delim: &str) -> Result<String, Errno> {
+    let console = Console::open()?;
+    let end = strip_quotes(delim).0;
+
+    let mut raw = String::new();
+    for _ in 0..HEREDOC_MAX_LINES {
+        print!("> ");
+        let line = String::from_utf8(console.read_line(LINE_MAX)?).map_err(|_| Errno::Eilseq)?;
+        let terminated = line.trim_end() == end;
+        raw.push_str(&line);
+        raw.push('\n');
+        if terminated {
+            break;
+        }
+    }
+    collect_heredoc(&mut raw.lines(), delim)
+}
+
+/// Resolves a parsed stdin redirection into the literal text to feed the command, or `None` if
+/// stdin comes from a file (or isn't redirected at all). Variables in the text are expanded
+/// unless the here-document delimiter (or here-string word) was quoted.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s from [`read_heredoc_console`].
+fn stdin_text(parsed: &ParsedCommand<'_>, env_vars: &[EnvVar]) -> Result<Option<String>, Errno> {
+    let (body, interpolate) = match parsed.stdin {
+        Some(StdinSource::HereDoc(delim)) => {
+            (read_heredoc_console(delim)?, strip_quotes(delim).1)
+        }
+        Some(StdinSource::HereString(word)) => {
+            let (text, interpolate) = strip_quotes(word);
+            let mut body = String::from(text);
+            body.push('\n');
+            (body, interpolate)
+        }
+        Some(StdinSource::Path(_)) | None => return Ok(None),
+    };
+
+    if !interpolate {
+        return Ok(Some(body));
+    }
+    let mut expanded = String::new();
+    for line in body.lines() {
+        expanded.push_str(&expand(line, env_vars));
+        expanded.push('\n');
+    }
+    Ok(Some(expanded))
+}
+
+/// Runs a single command with its standard streams redirected as parsed, waiting for it to
+/// finish and returning whether it succeeded. `parsed.argv[0]` must already be resolved against
+/// `PATH`; any here-document/here-string body arrives pre-collected as `text`.
+fn run_redirected(parsed: &ParsedCommand<'_>, text: Option<&str>, envp: &[String]) -> bool {
+    let stdin_file = if let Some(text) = text {
+        // Feed the text through a pipe; dropping the write end delivers EOF after it. Bodies
+        // bigger than the pipe buffer aren't supported.
+        match ipc::pipe() {
+            Ok((read_end, write_end)) => {
+                if let Err(errno) = write_end.write(text.as_bytes()) {
+                    eprintln!("here-document: {errno}");
+                    return false;
+                }
+                Some(read_end)
+            }
+            Err(errno) => {
+                eprintln!("pipe fail: {errno}");
+                return false;
+            }
+        }
+    } else {
+        match parsed.stdin {
+            Some(StdinSource::Path(path)) => match fs::OpenOptions::new().open(path) {
+                Ok(file) => Some(file),
+                Err(errno) => {
+                    eprintln!("{path}: {errno}");
+                    return false;
+                }
+            },
+            _ => None,
+        }
+    };
+    let stdout_file = match parsed.stdout {
+        Some((path, mode)) => {
+            let mut options = fs::OpenOptions::new();
+            options.write_only().create(true);
+            match mode {
+                OutputMode::Truncate => options.truncate(true),
+                OutputMode::Append => options.append(true),
+            };
+            match options.open(path) {
+                Ok(file) => Some(file),
+                Err(errno) => {
+                    eprintln!("{path}: {errno}");
+                    return false;
                 }
             }
         }
+        None => None,
+    };
+
+    match process::spawn_process_redirected(
+        &parsed.argv,
+        envp,
+        stdin_file.as_ref(),
+        stdout_file.as_ref(),
+    ) {
+        // OK to lose sign; spawned PIDs are always positive.
+        #[allow(clippy::cast_sign_loss)]
+        Ok(pid) => report_exit(
+            parsed.argv[0],
+            process::wait_state(pid as usize, WaitOptions::WEXITED),
+        ),
+        Err(errno) => {
+            eprintln!("{}: {errno}", parsed.argv[0]);
+            false
+        }
+    }
+}
+
+/// Splits a command line into pipeline segments on every unquoted `|`.
+///
+/// Lines without a pipe come back as a single segment. Quotes aren't stripped; they only stop a
+/// `|` inside them from acting as a pipe.
+fn split_pipeline(line: &str) -> Vec<&str> {
+    let mut segments = Vec::new();
+    let mut segment_start = 0;
+    let mut in_single = false;
+    let mut in_double = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '\'' if !in_double => in_single = !in_single,
+            '"' if !in_single => in_double = !in_double,
+            '|' if !in_single && !in_double => {
+                segments.push(&line[segment_start..i]);
+                segment_start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    segments.push(&line[segment_start..]);
+    segments
+}
+
+/// Runs a pipeline: each segment's stdout is wired into the next segment's stdin via
+/// [`ipc::pipe`], then all of the children are waited on in order. Returns whether the pipeline
+/// succeeded — like other shells, its status is that of its last stage.
+fn run_pipeline(segments: &[&str], env_vars: &[EnvVar], envp: &[String]) -> bool {
+    // Resolve every stage up front so a typo in a later stage doesn't leave earlier ones running.
+    let mut stage_argvs: Vec<Vec<String>> = Vec::with_capacity(segments.len());
+    for segment in segments {
+        let mut argv: Vec<String> = segment
+            .split_whitespace()
+            .map(|word| expand(word, env_vars))
+            .collect();
+        let Some(argv0) = argv.first() else {
+            eprintln!("Empty pipeline command.");
+            return false;
+        };
+        if BUILTINS.contains(&argv0.as_str()) {
+            eprintln!("{argv0}: can only be used as a sole command");
+            return false;
+        }
+        match program_path_subst(argv0, env_vars) {
+            Ok(new_argv0) => argv[0] = new_argv0,
+            Err(Errno::Enoent) => {
+                eprintln!("{argv0}: Unrecognised command.");
+                return false;
+            }
+            Err(errno) => {
+                eprintln!("Program path substitute fail: {errno}");
+                return false;
+            }
+        }
+        stage_argvs.push(argv);
+    }
+
+    let mut child_pids: Vec<(&str, i32)> = Vec::with_capacity(stage_argvs.len());
+    let mut spawn_failed = false;
+    let mut prev_read: Option<File> = None;
+    for (i, argv) in stage_argvs.iter().enumerate() {
+        // Every stage but the last writes into a fresh pipe.
+        let (next_read, write_end) = if i == stage_argvs.len() - 1 {
+            (None, None)
+        } else {
+            match ipc::pipe() {
+                Ok((read_end, write_end)) => (Some(read_end), Some(write_end)),
+                Err(errno) => {
+                    eprintln!("pipe fail: {errno}");
+                    spawn_failed = true;
+                    break;
+                }
+            }
+        };
+
+        match process::spawn_process_redirected(argv, envp, prev_read.as_ref(), write_end.as_ref())
+        {
+            Ok(pid) => child_pids.push((&argv[0], pid)),
+            Err(errno) => {
+                eprintln!("{}: {errno}", argv[0]);
+                spawn_failed = true;
+                break;
+            }
+        }
+
+        // Drop the parent's copies of the pipe ends promptly; a stage only sees EOF once every
+        // descriptor for its stdin's write side is closed.
+        prev_read = next_read;
+    }
+    drop(prev_read);
+
+    // Wait on every stage, reporting failures the same way sole commands are reported.
+    let stage_count = child_pids.len();
+    let mut last_success = false;
+    for (i, (name, pid)) in child_pids.into_iter().enumerate() {
+        // OK to lose sign; spawned PIDs are always positive.
+        #[allow(clippy::cast_sign_loss)]
+        let stage_success = report_exit(name, process::wait_state(pid as usize, WaitOptions::WEXITED));
+        if i == stage_count - 1 {
+            last_success = stage_success;
+        }
+    }
+    !spawn_failed && last_success
+}
+
+/// Prints a diagnostic for a command which failed to run or exited unsuccessfully, returning
+/// whether the command succeeded.
+fn report_exit(name: &str, result: Result<ExitStatus, Errno>) -> bool {
+    match result {
+        Ok(ExitStatus::ExitSuccess) => true,
+        Ok(ExitStatus::ExitFailure(code)) => {
+            if let Ok(errno) = Errno::try_from_primitive(code) {
+                eprintln!("{name}: {errno}");
+            } else {
+                eprintln!("{name}: Process exited with failure code {code}.");
+            }
+            false
+        }
+        Ok(ExitStatus::Terminated(signo)) => {
+            eprintln!("{name}: Process terminated {signo}");
+            false
+        }
+        Err(e) => {
+            eprintln!("{name}: {e}");
+            false
+        }
+        #[allow(unused_variables)]
+        other => {
+            #[cfg(debug_assertions)]
+            eprintln!("{name}: {other:?}");
+            false
+        }
+    }
+}
+
+/// Resolves the target directory of a `cd` command.
+///
+/// No argument (or `~`) means the home directory, [`CD_PREVIOUS`] means the previous directory
+/// taken from the `OLDPWD` environment variable, a leading `~/` is replaced with the home
+/// directory, and anything else is taken as a path.
+///
+/// # Errors
+///
+/// This function returns [`Errno::Einval`] if `-` is used while no `OLDPWD` is set.
+fn resolve_cd_target(arg: Option<&str>, env: &[EnvVar], home: &str) -> Result<String, Errno> {
+    match arg {
+        None | Some("~") => Ok(home.to_string()),
+        Some(CD_PREVIOUS) => env
+            .iter()
+            .find(|ev| ev.key == OLDPWD_ENV_VAR_NAME)
+            .map(|ev| ev.value.clone())
+            .ok_or(Errno::Einval),
+        Some(path) => Ok(path
+            .strip_prefix("~/")
+            .map_or_else(|| path.to_string(), |rest| format!("{home}/{rest}"))),
+    }
+}
+
+/// Parses an `export` argument. `KEY=value` yields a variable to set; a bare `KEY` yields
+/// [`None`], leaving the variable unset.
+///
+/// # Errors
+///
+/// This function returns [`Errno::Einval`] if the key is empty (e.g. `export =value`).
+fn parse_export(arg: &str) -> Result<Option<EnvVar>, Errno> {
+    if !arg.contains('=') {
+        return Ok(None);
+    }
+    EnvVar::try_from(arg.to_string()).map(Some)
+}
+
+/// Overlays the session-scoped variables (e.g. `PWD`/`OLDPWD`) onto the environment read from
+/// disk.
+fn overlay_env(env_vars: &mut Vec<EnvVar>, session_env: &[EnvVar]) {
+    for session_var in session_env {
+        if let Some(existing) = env_vars.iter_mut().find(|ev| ev.key == session_var.key) {
+            existing.value.clone_from(&session_var.value);
+        } else {
+            env_vars.push(session_var.clone());
+        }
+    }
+}
+
+/// The shell's in-memory command history, oldest first.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct History {
+    entries: Vec<String>,
+}
+impl History {
+    /// Records a command line. Blank lines and consecutive duplicates aren't recorded.
+    fn push(&mut self, line: &str) {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || self.entries.last().is_some_and(|last| last == trimmed) {
+            return;
+        }
+        self.entries.push(trimmed.to_string());
+    }
+
+    /// Gets all entries, oldest first.
+    fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Gets the most recent entry.
+    fn last(&self) -> Option<&str> {
+        self.entries.last().map(String::as_str)
+    }
+
+    /// Gets the `n`th entry, numbered from 1 (the number `!N` refers to).
+    fn nth(&self, n: usize) -> Option<&str> {
+        n.checked_sub(1)
+            .and_then(|i| self.entries.get(i))
+            .map(String::as_str)
+    }
+
+    /// Gets the most recent entry starting with the given prefix.
+    fn last_with_prefix(&self, prefix: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|entry| entry.starts_with(prefix))
+            .map(String::as_str)
+    }
+}
+
+/// Expands any `!!` (last command), `!N` (command number `N`), and `!prefix` (most recent command
+/// starting with `prefix`) history references in the given line.
+///
+/// Lines without history references are returned unchanged. A lone `!` is taken literally.
+///
+/// # Errors
+///
+/// This function returns [`Errno::Einval`] if a history reference has no match.
+fn expand_history(line: &str, history: &History) -> Result<String, Errno> {
+    if !line
+        .split_whitespace()
+        .any(|word| word.len() > 1 && word.starts_with('!'))
+    {
+        return Ok(line.to_string());
+    }
+
+    let mut expanded_words = Vec::new();
+    for word in line.split_whitespace() {
+        let Some(reference) = word.strip_prefix('!').filter(|r| !r.is_empty()) else {
+            expanded_words.push(word);
+            continue;
+        };
+
+        let replacement = if reference == "!" {
+            history.last()
+        } else if let Ok(n) = reference.parse::<usize>() {
+            history.nth(n)
+        } else {
+            history.last_with_prefix(reference)
+        };
+        expanded_words.push(replacement.ok_or(Errno::Einval)?);
     }
+    Ok(expanded_words.join(" "))
 }
 
 /// Read and parse the environment files from the disk.
@@ -199,15 +912,15 @@ fn env_var_read_fail(reason: &'static str, e: Errno) -> Vec<EnvVar> {
     Vec::new()
 }
 
-/// Print the MASH shell prompt.
-fn print_prompt() {
-    let cwd_backup = String::from(CWD_NAME_BACKUP);
-    let cwd = fs::get_cwd().unwrap_or(cwd_backup);
-    let basename =
-        &cwd.rsplit_once('/').map_or(
-            cwd.as_str(),
-            |(_, last)| if last.is_empty() { "/" } else { last },
-        );
+/// Print the MASH shell prompt. The given buffer is reused for the cwd lookup.
+fn print_prompt(cwd_buf: &mut String) {
+    if fs::cwd_into(cwd_buf).is_err() {
+        cwd_buf.push_str(CWD_NAME_BACKUP);
+    }
+    let basename = &cwd_buf.rsplit_once('/').map_or(
+        cwd_buf.as_str(),
+        |(_, last)| if last.is_empty() { "/" } else { last },
+    );
 
     print!("{PROMPT_START} {basename} {PROMPT_FINISH} ");
 }
@@ -283,3 +996,385 @@ fn panic(info: &PanicInfo<'_>) -> ! {
     tlenix_core::eprintln!("{} {}", MASH_PANIC_TITLE, info);
     process::exit(process::ExitStatus::ExitFailure(1))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn populated_history() -> History {
+        let mut history = History::default();
+        history.push("ls /");
+        history.push("cat /etc/environment");
+        history.push("cd /tmp");
+        history
+    }
+
+    #[test_case]
+    fn expand_bang_bang() {
+        let history = populated_history();
+        assert_eq!(expand_history("!!", &history).unwrap(), "cd /tmp");
+    }
+
+    #[test_case]
+    fn expand_bang_n() {
+        let history = populated_history();
+        assert_eq!(expand_history("!1", &history).unwrap(), "ls /");
+        assert_eq!(
+            expand_history("!2", &history).unwrap(),
+            "cat /etc/environment"
+        );
+    }
+
+    #[test_case]
+    fn expand_bang_prefix() {
+        let history = populated_history();
+        assert_eq!(
+            expand_history("!cat", &history).unwrap(),
+            "cat /etc/environment"
+        );
+    }
+
+    #[test_case]
+    fn expand_mid_line() {
+        let history = populated_history();
+        assert_eq!(expand_history("echo !3", &history).unwrap(), "echo cd /tmp");
+    }
+
+    #[test_case]
+    fn expand_no_refs_unchanged() {
+        let history = populated_history();
+        assert_eq!(expand_history("ls -la", &history).unwrap(), "ls -la");
+    }
+
+    #[test_case]
+    fn expand_no_match() {
+        let history = populated_history();
+        assert_eq!(expand_history("!nope", &history), Err(Errno::Einval));
+        assert_eq!(expand_history("!99", &history), Err(Errno::Einval));
+        assert_eq!(expand_history("!!", &History::default()), Err(Errno::Einval));
+    }
+
+    fn cd_env() -> Vec<EnvVar> {
+        alloc::vec![EnvVar {
+            key: OLDPWD_ENV_VAR_NAME.to_string(),
+            value: "/somewhere/else".to_string(),
+        }]
+    }
+
+    #[test_case]
+    fn cd_target_no_arg_is_home() {
+        assert_eq!(
+            resolve_cd_target(None, &cd_env(), "/root").unwrap(),
+            "/root"
+        );
+    }
+
+    #[test_case]
+    fn cd_target_dash_is_oldpwd() {
+        assert_eq!(
+            resolve_cd_target(Some("-"), &cd_env(), "/root").unwrap(),
+            "/somewhere/else"
+        );
+    }
+
+    #[test_case]
+    fn cd_target_dash_without_oldpwd() {
+        assert_eq!(
+            resolve_cd_target(Some("-"), &[], "/root"),
+            Err(Errno::Einval)
+        );
+    }
+
+    #[test_case]
+    fn cd_target_path_taken_verbatim() {
+        assert_eq!(
+            resolve_cd_target(Some("/tmp"), &cd_env(), "/root").unwrap(),
+            "/tmp"
+        );
+    }
+
+    #[test_case]
+    fn cd_target_tilde_expansion() {
+        assert_eq!(
+            resolve_cd_target(Some("~"), &cd_env(), "/root").unwrap(),
+            "/root"
+        );
+        assert_eq!(
+            resolve_cd_target(Some("~/notes"), &cd_env(), "/root").unwrap(),
+            "/root/notes"
+        );
+    }
+
+    fn expand_env() -> Vec<EnvVar> {
+        alloc::vec![
+            EnvVar {
+                key: "HOME".to_string(),
+                value: "/root".to_string(),
+            },
+            EnvVar {
+                key: "PATH".to_string(),
+                value: "/bin:/usr/bin".to_string(),
+            },
+        ]
+    }
+
+    #[test_case]
+    fn expand_simple_var() {
+        assert_eq!(expand("$HOME", &expand_env()), "/root");
+    }
+
+    #[test_case]
+    fn expand_braced_var() {
+        assert_eq!(expand("${PATH}", &expand_env()), "/bin:/usr/bin");
+        assert_eq!(expand("a${HOME}b", &expand_env()), "a/rootb");
+    }
+
+    #[test_case]
+    fn expand_unset_var_is_empty() {
+        assert_eq!(expand("$NOPE", &expand_env()), "");
+    }
+
+    #[test_case]
+    fn expand_escaped_dollar() {
+        assert_eq!(expand("\\$HOME", &expand_env()), "$HOME");
+    }
+
+    #[test_case]
+    fn expand_single_quotes_literal() {
+        assert_eq!(expand("'$HOME'", &expand_env()), "'$HOME'");
+    }
+
+    #[test_case]
+    fn parse_export_key_value() {
+        assert_eq!(
+            parse_export("A=b").unwrap(),
+            Some(EnvVar {
+                key: "A".to_string(),
+                value: "b".to_string(),
+            })
+        );
+    }
+
+    #[test_case]
+    fn parse_export_bare_key_leaves_unset() {
+        assert_eq!(parse_export("A").unwrap(), None);
+    }
+
+    #[test_case]
+    fn parse_export_empty_key_rejected() {
+        assert_eq!(parse_export("=b"), Err(Errno::Einval));
+    }
+
+    #[test_case]
+    fn export_overrides_existing_key() {
+        let mut session_env = alloc::vec![EnvVar {
+            key: "A".to_string(),
+            value: "old".to_string(),
+        }];
+        let var = parse_export("A=new").unwrap().unwrap();
+        overlay_env(&mut session_env, &[var]);
+        assert_eq!(session_env.len(), 1);
+        assert_eq!(session_env[0].value, "new");
+    }
+
+    #[test_case]
+    fn parse_redirections_stdout_truncate() {
+        let parsed = parse_redirections(&["echo", "hi", ">", "out"]).unwrap();
+        assert_eq!(parsed.argv, ["echo", "hi"]);
+        assert_eq!(parsed.stdin, None);
+        assert_eq!(parsed.stdout, Some(("out", OutputMode::Truncate)));
+    }
+
+    #[test_case]
+    fn parse_redirections_stdin() {
+        let parsed = parse_redirections(&["cat", "<", "in"]).unwrap();
+        assert_eq!(parsed.argv, ["cat"]);
+        assert_eq!(parsed.stdin, Some(StdinSource::Path("in")));
+        assert_eq!(parsed.stdout, None);
+    }
+
+    #[test_case]
+    fn parse_redirections_here_string() {
+        let parsed = parse_redirections(&["cat", "<<<", "\"hi\""]).unwrap();
+        assert_eq!(parsed.argv, ["cat"]);
+        assert_eq!(parsed.stdin, Some(StdinSource::HereString("\"hi\"")));
+    }
+
+    #[test_case]
+    fn parse_redirections_heredoc() {
+        let parsed = parse_redirections(&["cat", "<<", "EOF"]).unwrap();
+        assert_eq!(parsed.argv, ["cat"]);
+        assert_eq!(parsed.stdin, Some(StdinSource::HereDoc("EOF")));
+
+        // A later stdin redirection replaces an earlier one, like other shells.
+        let parsed = parse_redirections(&["cat", "<<", "EOF", "<", "in"]).unwrap();
+        assert_eq!(parsed.stdin, Some(StdinSource::Path("in")));
+    }
+
+    #[test_case]
+    fn parse_redirections_stdout_append() {
+        let parsed = parse_redirections(&["ls", ">>", "log"]).unwrap();
+        assert_eq!(parsed.argv, ["ls"]);
+        assert_eq!(parsed.stdout, Some(("log", OutputMode::Append)));
+    }
+
+    #[test_case]
+    fn parse_redirections_none() {
+        let parsed = parse_redirections(&["ls", "-la"]).unwrap();
+        assert_eq!(parsed.argv, ["ls", "-la"]);
+        assert_eq!(parsed.stdin, None);
+        assert_eq!(parsed.stdout, None);
+    }
+
+    #[test_case]
+    fn parse_redirections_missing_target() {
+        assert_eq!(parse_redirections(&["echo", "hi", ">"]), Err(Errno::Einval));
+        assert_eq!(parse_redirections(&["cat", "<<"]), Err(Errno::Einval));
+    }
+
+    #[test_case]
+    fn collect_heredoc_simple() {
+        let mut lines = "first\nsecond\nEOF\nafter".lines();
+        assert_eq!(collect_heredoc(&mut lines, "EOF").unwrap(), "first\nsecond\n");
+        // Lines after the delimiter are left for the caller.
+        assert_eq!(lines.next(), Some("after"));
+    }
+
+    #[test_case]
+    fn collect_heredoc_quoted_delim() {
+        // A quoted delimiter matches its unquoted form; the body still comes back verbatim, and
+        // the quoting marks it as exempt from variable expansion.
+        let mut lines = "$HOME stays\nEOF".lines();
+        assert_eq!(collect_heredoc(&mut lines, "'EOF'").unwrap(), "$HOME stays\n");
+        assert_eq!(strip_quotes("'EOF'"), ("EOF", false));
+        assert_eq!(strip_quotes("EOF"), ("EOF", true));
+    }
+
+    #[test_case]
+    fn collect_heredoc_unterminated() {
+        let mut lines = "no\ndelimiter\nhere".lines();
+        assert_eq!(collect_heredoc(&mut lines, "EOF"), Err(Errno::Einval));
+    }
+
+    #[test_case]
+    fn stdin_text_here_string_expansion() {
+        let env = [EnvVar {
+            key: "GREETING".to_string(),
+            value: "hullo".to_string(),
+        }];
+
+        let parsed = parse_redirections(&["cat", "<<<", "$GREETING"]).unwrap();
+        assert_eq!(stdin_text(&parsed, &env).unwrap().unwrap(), "hullo\n");
+
+        // A quoted here-string word stays verbatim.
+        let parsed = parse_redirections(&["cat", "<<<", "'$GREETING'"]).unwrap();
+        assert_eq!(stdin_text(&parsed, &env).unwrap().unwrap(), "$GREETING\n");
+
+        // Plain file redirections (and unredirected commands) carry no text.
+        let parsed = parse_redirections(&["cat", "<", "in"]).unwrap();
+        assert_eq!(stdin_text(&parsed, &env).unwrap(), None);
+    }
+
+    #[test_case]
+    fn split_chain_mixed_connectors() {
+        assert_eq!(
+            split_chain("make && ls || echo fail").unwrap(),
+            [
+                ("make ", Some(Connector::And)),
+                (" ls ", Some(Connector::Or)),
+                (" echo fail", None)
+            ]
+        );
+    }
+
+    #[test_case]
+    fn split_chain_single_command() {
+        assert_eq!(split_chain("ls -la").unwrap(), [("ls -la", None)]);
+    }
+
+    #[test_case]
+    fn split_chain_keeps_single_pipe() {
+        assert_eq!(
+            split_chain("ls | wc && echo ok").unwrap(),
+            [("ls | wc ", Some(Connector::And)), (" echo ok", None)]
+        );
+    }
+
+    #[test_case]
+    fn split_chain_quoted_connector_literal() {
+        assert_eq!(
+            split_chain("echo 'a && b'").unwrap(),
+            [("echo 'a && b'", None)]
+        );
+    }
+
+    #[test_case]
+    fn split_chain_dangling_connector() {
+        assert_eq!(split_chain("ls &&"), Err(Errno::Einval));
+        assert_eq!(split_chain("|| ls"), Err(Errno::Einval));
+        assert_eq!(split_chain("a && || b"), Err(Errno::Einval));
+        assert_eq!(split_chain(";"), Err(Errno::Einval));
+        assert_eq!(split_chain("a ;; b"), Err(Errno::Einval));
+    }
+
+    #[test_case]
+    fn split_chain_sequence() {
+        assert_eq!(
+            split_chain("a ; b").unwrap(),
+            [("a ", Some(Connector::Seq)), (" b", None)]
+        );
+    }
+
+    #[test_case]
+    fn split_chain_trailing_semicolon() {
+        assert_eq!(split_chain("ls;").unwrap(), [("ls", Some(Connector::Seq))]);
+    }
+
+    #[test_case]
+    fn split_chain_mixed_with_sequence() {
+        assert_eq!(
+            split_chain("a && b ; c || d").unwrap(),
+            [
+                ("a ", Some(Connector::And)),
+                (" b ", Some(Connector::Seq)),
+                (" c ", Some(Connector::Or)),
+                (" d", None)
+            ]
+        );
+    }
+
+    #[test_case]
+    fn split_chain_quoted_semicolon_literal() {
+        assert_eq!(split_chain("echo 'a ; b'").unwrap(), [("echo 'a ; b'", None)]);
+    }
+
+    #[test_case]
+    fn split_pipeline_no_pipe() {
+        assert_eq!(split_pipeline("ls -la"), ["ls -la"]);
+    }
+
+    #[test_case]
+    fn split_pipeline_splits_stages() {
+        assert_eq!(
+            split_pipeline("cat /etc/environment | grep PATH | wc -l"),
+            ["cat /etc/environment ", " grep PATH ", " wc -l"]
+        );
+    }
+
+    #[test_case]
+    fn split_pipeline_respects_quotes() {
+        assert_eq!(
+            split_pipeline("grep 'a|b' f | wc"),
+            ["grep 'a|b' f ", " wc"]
+        );
+        assert_eq!(split_pipeline("echo \"a|b\""), ["echo \"a|b\""]);
+    }
+
+    #[test_case]
+    fn history_skips_blanks_and_duplicates() {
+        let mut history = populated_history();
+        history.push("   ");
+        history.push("cd /tmp");
+        assert_eq!(history.nth(4), None);
+    }
+}
@@ -0,0 +1,218 @@
+//! Displays a file (or standard input) one screenful at a time.
+
+#![warn(
+    missing_docs,
+    missing_debug_implementations,
+    rust_2018_idioms,
+    clippy::all,
+    clippy::pedantic
+)]
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![cfg_attr(test, test_runner(tlenix_core::custom_test_runner))]
+#![cfg_attr(test, reexport_test_harness_main = "test_main")]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use core::panic::PanicInfo;
+
+use getargs::{Arg, Options};
+use tlenix_core::{
+    EnvVar, Errno, eprintln, fs, parse_argv_envp, print,
+    process::{self, ExitStatus},
+    streams,
+    term::{self, TermMode},
+    try_exit,
+};
+
+const PANIC_TITLE: &str = "more";
+
+/// If this symbol is an argument, it means "read from stdin".
+const STDIN_SYMBOL: &str = "-";
+
+/// Path to the controlling terminal, used for keypresses and the window size.
+const TTY_PATH: &str = "/dev/tty";
+
+/// Fallback page height if the terminal window size can't be determined.
+const DEFAULT_ROWS: usize = 24;
+
+/// Prompt shown at the bottom of each page.
+const MORE_PROMPT: &str = "\u{001b}[7m--More--\u{001b}[0m";
+
+core::arch::global_asm! {
+    ".global _start",
+    "_start:",
+    "mov rdi, rsp",
+    "call start"
+}
+
+/// Displays a file (or standard input) one screenful at a time.
+///
+/// # Safety
+///
+/// This program must be passed appropriate `execve`-compatible args.
+#[unsafe(no_mangle)]
+#[allow(unused_variables)]
+unsafe extern "C" fn start(stack_top: *const usize) -> ! {
+    #[cfg(test)]
+    {
+        test_main();
+        process::exit(ExitStatus::ExitSuccess);
+    }
+
+    // HACK: This stops the compiler from complaining when building the test/debug target
+    #[allow(unreachable_code)]
+    #[allow(clippy::no_effect)]
+    ();
+
+    // SAFETY: This function is being called right at the start of execution before anything else.
+    // The stack pointer is retrieved directly from the function args.
+    let (argv, envp) = match unsafe { parse_argv_envp(stack_top) } {
+        Ok(argv_envp) => argv_envp,
+        Err(errno) => process::exit(ExitStatus::ExitFailure(errno as i32)),
+    };
+
+    let exit_code = main(&argv, &envp);
+
+    process::exit(exit_code);
+}
+
+fn main(args: &[String], _env_vars: &[EnvVar]) -> ExitStatus {
+    let path = try_exit!(get_path(args));
+    let bytes = try_exit!(read_input(path.as_deref()));
+
+    let mut lines: Vec<&[u8]> = bytes.split(|&b| b == b'\n').collect();
+    // A trailing newline leaves an empty final "line"; don't display it.
+    if lines.last() == Some(&&b""[..]) {
+        lines.pop();
+    }
+
+    let pages = paginate(&lines, page_rows());
+
+    for (i, page) in pages.iter().enumerate() {
+        let mut output = Vec::new();
+        for line in *page {
+            output.extend_from_slice(line);
+            output.push(b'\n');
+        }
+        try_exit!(streams::STDOUT.lock().write(&output));
+        if i + 1 < pages.len() && !try_exit!(wait_for_keypress()) {
+            break;
+        }
+    }
+
+    ExitStatus::ExitSuccess
+}
+
+/// Gets the (optional) file path from the command-line arguments.
+fn get_path(args: &[String]) -> Result<Option<String>, Errno> {
+    let mut opts = Options::new(args.iter().map(String::as_str).skip(1));
+    let mut path = None;
+    while let Some(arg) = opts.next_arg().map_err(|_| Errno::Einval)? {
+        if let Arg::Positional(val) = arg
+            && path.is_none()
+            && val != STDIN_SYMBOL
+        {
+            path = Some(String::from(val));
+        }
+    }
+    Ok(path)
+}
+
+/// Reads the entire input: the file at the given path, or standard input if no path was given.
+fn read_input(path: Option<&str>) -> Result<Vec<u8>, Errno> {
+    match path {
+        Some(path) => fs::OpenOptions::new().open(path)?.read_to_bytes(),
+        None => streams::STDIN.lock().read_to_bytes(),
+    }
+}
+
+/// Determines the number of content lines per page, leaving one row for the prompt.
+fn page_rows() -> usize {
+    let rows = fs::OpenOptions::new()
+        .open(TTY_PATH)
+        .and_then(|tty| term::window_size(&tty))
+        .map_or(DEFAULT_ROWS, |ws| usize::from(ws.rows));
+    if rows > 1 { rows - 1 } else { 1 }
+}
+
+/// Splits the given lines into pages of (at most) `rows` lines each.
+fn paginate<'a>(lines: &'a [&'a [u8]], rows: usize) -> Vec<&'a [&'a [u8]]> {
+    lines.chunks(rows.max(1)).collect()
+}
+
+/// Displays the prompt and waits for a keypress on the controlling terminal.
+///
+/// Returns `true` if the next page should be shown, or `false` to quit.
+fn wait_for_keypress() -> Result<bool, Errno> {
+    let tty = fs::OpenOptions::new().read_write().open(TTY_PATH)?;
+    // Cbreak (rather than raw) mode so Ctrl-C still works while paging.
+    let saved = term::set_mode(&tty, TermMode::Cbreak)?;
+
+    print!("{MORE_PROMPT}");
+    let result = loop {
+        match tty.read_byte() {
+            Ok(Some(b' ')) => break Ok(true),
+            Ok(Some(b'q' | b'Q')) => break Ok(false),
+            Ok(_) => {}
+            Err(e) => break Err(e),
+        }
+    };
+    // Erase the prompt.
+    print!("\r\u{001b}[K");
+
+    // Restore the terminal before handing back any errors.
+    term::tcsetattr(&tty, &saved)?;
+    result
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo<'_>) -> ! {
+    eprintln!("{PANIC_TITLE} {info}");
+    process::exit(ExitStatus::ExitFailure(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINES: [&[u8]; 5] = [b"one", b"two", b"three", b"four", b"five"];
+
+    #[test_case]
+    fn paginate_shorter_than_screen() {
+        let pages = paginate(&LINES, 10);
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0], &LINES[..]);
+    }
+
+    #[test_case]
+    fn paginate_longer_than_screen() {
+        let pages = paginate(&LINES, 2);
+        assert_eq!(pages.len(), 3);
+        assert_eq!(pages[0], &LINES[..2]);
+        assert_eq!(pages[1], &LINES[2..4]);
+        assert_eq!(pages[2], &LINES[4..]);
+    }
+
+    #[test_case]
+    fn paginate_exact_fit() {
+        let pages = paginate(&LINES, 5);
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0], &LINES[..]);
+    }
+
+    #[test_case]
+    fn paginate_empty() {
+        let lines: [&[u8]; 0] = [];
+        assert!(paginate(&lines, 3).is_empty());
+    }
+
+    #[test_case]
+    fn paginate_zero_rows() {
+        // A nonsensical page height of 0 still makes progress.
+        let pages = paginate(&LINES, 0);
+        assert_eq!(pages.len(), LINES.len());
+    }
+}
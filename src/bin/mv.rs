@@ -231,6 +231,12 @@ fn rename_with_settings(
     destination: &str,
     settings: &MvSettings<'_>,
 ) -> Result<(), Errno> {
+    // Refuse to move a file onto itself (`mv f f`, or two names for the same file); a missing
+    // destination simply fails the identity check.
+    if fs::same_file(source, destination).unwrap_or(false) {
+        eprintln!("mv failed: '{source}' and '{destination}' are the same file");
+        return Err(Errno::Einval);
+    }
     // Check if prompt overwrite is enabled AND if a file exists at the destination.
     if settings.prompt_overwrite && FileStats::try_from_path(destination).is_ok() {
         let console = Console::open()?;
@@ -246,13 +252,60 @@ fn rename_with_settings(
             }
         }
     }
-    fs::rename(source, destination, settings.rename_flags)?;
+    match fs::rename(source, destination, settings.rename_flags) {
+        // The kernel can't rename across filesystem boundaries; fall back to copying the file
+        // (metadata included) and removing the source.
+        Err(Errno::Exdev) => copy_fallback(source, destination)?,
+        other => other?,
+    }
     if settings.verbose {
         println!("Renamed '{source}' to '{destination}'.");
     }
     Ok(())
 }
 
+/// Moves a regular file across filesystems by copying its bytes, preserving its metadata, and
+/// removing the source.
+fn copy_fallback(source: &str, destination: &str) -> Result<(), Errno> {
+    let source_stats = FileStats::try_from_path(source)?;
+    // Moving whole trees across filesystems is out of scope; report the original failure.
+    if source_stats.file_type == Some(FileType::Directory) {
+        return Err(Errno::Exdev);
+    }
+
+    let contents = fs::OpenOptions::new().open(source)?.read_to_bytes()?;
+    let dest_file = fs::OpenOptions::new()
+        .write_only()
+        .create(true)
+        .truncate(true)
+        .open(destination)?;
+    let mut written = 0;
+    while written < contents.len() {
+        written += dest_file.write(&contents[written..])?;
+    }
+
+    // A moved file keeps its metadata, so the fallback preserves it too.
+    if let Some(mode) = source_stats.mode {
+        fs::chmod(destination, mode)?;
+    }
+    if let (Some(uid), Some(gid)) = (source_stats.uid, source_stats.gid) {
+        // Only a privileged user may hand files to other owners.
+        match fs::chown(destination, uid, gid) {
+            Ok(()) | Err(Errno::Eperm) => {}
+            Err(errno) => return Err(errno),
+        }
+    }
+    fs::set_times(
+        destination,
+        source_stats.access_time.unwrap_or(fs::FileTimestamp::OMIT),
+        source_stats
+            .modification_time
+            .unwrap_or(fs::FileTimestamp::OMIT),
+    )?;
+
+    fs::rm(source)
+}
+
 #[panic_handler]
 fn panic(info: &PanicInfo<'_>) -> ! {
     eprintln!("{PANIC_TITLE} {info}");
@@ -502,6 +555,26 @@ mod tests {
         test_teardown(&dir_path);
     }
 
+    #[test_case]
+    fn same_file_refused() {
+        let dir_path = test_setup("same_file_refused");
+
+        let f_path = dir_path.clone() + "/f";
+        let f_contents = "don't truncate me";
+
+        create_file_with_contents(&f_path, f_contents);
+
+        let args = [f_path.as_str(), f_path.as_str()];
+        assert_eq!(move_files(&mk_mv_settings(&args)), Err(Errno::Einval));
+
+        // The refused move must leave the file and its contents untouched.
+        assert_exists(&f_path, FileType::RegularFile);
+        assert_contents(&f_path, f_contents);
+
+        fs::rm(&f_path).unwrap();
+        test_teardown(&dir_path);
+    }
+
     #[test_case]
     fn nonexistent_src_fails() {
         let args = ["fwliueghwgeuhjfhlfh3gg", "/tmp"];
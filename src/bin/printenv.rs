@@ -23,14 +23,17 @@ use core::panic::PanicInfo;
 
 use getargs::{Arg, Options};
 use tlenix_core::{
-    EnvVar, Errno, eprintln, parse_argv_envp, println,
+    EnvVar, Errno, eprintln, parse_argv_envp, print, println,
     process::{self, ExitStatus},
     try_exit,
 };
 
 const PANIC_TITLE: &str = "printenv";
 
-const PRINTENV_SEPARATOR: &str = "\n";
+/// The default entry separator.
+const PRINTENV_SEPARATOR: u8 = b'\n';
+/// The entry separator in `-0` mode.
+const NUL_SEPARATOR: u8 = b'\0';
 
 core::arch::global_asm! {
     ".global _start",
@@ -71,21 +74,35 @@ unsafe extern "C" fn start(stack_top: *const usize) -> ! {
 }
 
 fn main(args: &[String], env_vars: &[EnvVar]) -> ExitStatus {
-    let filter = try_exit!(get_filter(args));
+    let (filter, null_separated) = try_exit!(get_filter(args));
     let filtered_env_vars = filter_env_vars(env_vars, &filter);
-    println!("{}", format_string(&filtered_env_vars, filter.is_empty()));
+    if null_separated {
+        // NUL-separated entries get no trailing separator (or newline) either.
+        print!(
+            "{}",
+            format_string(&filtered_env_vars, filter.is_empty(), NUL_SEPARATOR)
+        );
+    } else {
+        println!(
+            "{}",
+            format_string(&filtered_env_vars, filter.is_empty(), PRINTENV_SEPARATOR)
+        );
+    }
     ExitStatus::ExitSuccess
 }
 
-fn get_filter(args: &[String]) -> Result<Vec<&str>, Errno> {
+fn get_filter(args: &[String]) -> Result<(Vec<&str>, bool), Errno> {
     let mut opts = Options::new(args.iter().map(String::as_str).skip(1));
     let mut filter = Vec::with_capacity(args.len());
+    let mut null_separated = false;
     while let Some(arg) = opts.next_arg().map_err(|_| Errno::Einval)? {
-        if let Arg::Positional(val) = arg {
-            filter.push(val);
+        match arg {
+            Arg::Short('0') | Arg::Long("null") => null_separated = true,
+            Arg::Positional(val) => filter.push(val),
+            _ => {}
         }
     }
-    Ok(filter)
+    Ok((filter, null_separated))
 }
 
 fn filter_env_vars<'a>(env_vars: &'a [EnvVar], filter: &[&str]) -> Vec<&'a EnvVar> {
@@ -99,7 +116,7 @@ fn filter_env_vars<'a>(env_vars: &'a [EnvVar], filter: &[&str]) -> Vec<&'a EnvVa
     }
 }
 
-fn format_string(env_vars: &[&EnvVar], include_keys: bool) -> String {
+fn format_string(env_vars: &[&EnvVar], include_keys: bool, separator: u8) -> String {
     env_vars
         .iter()
         .map(|ev| {
@@ -110,7 +127,7 @@ fn format_string(env_vars: &[&EnvVar], include_keys: bool) -> String {
             }
         })
         .collect::<Vec<String>>()
-        .join(PRINTENV_SEPARATOR)
+        .join(char::from(separator).encode_utf8(&mut [0; 4]))
 }
 
 #[panic_handler]
@@ -128,7 +145,7 @@ mod tests {
            #[test_case]
            fn $fn_name() {
                let input = ["printenv".to_string(), $($arg.to_string()),*];
-               let result = get_filter(&input).unwrap();
+               let result = get_filter(&input).unwrap().0;
                let expected: &[&str] = &[$($expected),*][..];
                assert_eq!(&result, expected);
            }
@@ -140,6 +157,22 @@ mod tests {
     get_filter_test!(get_filter_empty_flags ["--myflag", "-f", "-Alq"] => []);
     get_filter_test!(get_filter_flags_interspersed ["--myflag", "TEST1", "-Alq", "TEST2", "-z"] => ["TEST1", "TEST2"]);
 
+    #[test_case]
+    fn get_filter_null_flag() {
+        let input = ["printenv".to_string(), "-0".to_string(), "PATH".to_string()];
+        let (filter, null_separated) = get_filter(&input).unwrap();
+        assert_eq!(&filter, &["PATH"]);
+        assert!(null_separated);
+
+        let input = ["printenv".to_string(), "--null".to_string()];
+        let (filter, null_separated) = get_filter(&input).unwrap();
+        assert!(filter.is_empty());
+        assert!(null_separated);
+
+        let input = ["printenv".to_string()];
+        assert!(!get_filter(&input).unwrap().1);
+    }
+
     macro_rules! filter_ev_test {
         ($fn_name:ident([$(($ev_k:expr, $ev_v:expr)),*], [$($f:expr),*]) => [$(($ex_k:expr, $ex_v:expr)),*]) => {
             #[test_case]
@@ -170,7 +203,7 @@ mod tests {
             },
         ];
         let evs: Vec<&EnvVar> = evs_owned.iter().collect();
-        assert_eq!("123\nabc", &format_string(&evs, false));
+        assert_eq!("123\nabc", &format_string(&evs, false, PRINTENV_SEPARATOR));
     }
 
     #[test_case]
@@ -186,7 +219,26 @@ mod tests {
             },
         ];
         let evs: Vec<&EnvVar> = evs_owned.iter().collect();
-        assert_eq!("K1=123\nK2=abc", &format_string(&evs, true));
+        assert_eq!("K1=123\nK2=abc", &format_string(&evs, true, PRINTENV_SEPARATOR));
+    }
+
+    #[test_case]
+    fn format_string_nul_separated() {
+        let evs_owned = [
+            EnvVar {
+                key: "K1".to_string(),
+                value: "multi\nline".to_string(),
+            },
+            EnvVar {
+                key: "K2".to_string(),
+                value: "abc".to_string(),
+            },
+        ];
+        let evs: Vec<&EnvVar> = evs_owned.iter().collect();
+        let formatted = format_string(&evs, true, NUL_SEPARATOR);
+        // A single NUL between entries, and no trailing separator.
+        assert_eq!("K1=multi\nline\0K2=abc", &formatted);
+        assert!(!formatted.ends_with('\0'));
     }
 
     #[test_case]
@@ -196,14 +248,14 @@ mod tests {
             value: String::new(),
         }];
         let evs: Vec<&EnvVar> = evs_owned.iter().collect();
-        assert_eq!("", &format_string(&evs, false));
-        assert_eq!("K1=", &format_string(&evs, true));
+        assert_eq!("", &format_string(&evs, false, PRINTENV_SEPARATOR));
+        assert_eq!("K1=", &format_string(&evs, true, PRINTENV_SEPARATOR));
     }
 
     #[test_case]
     fn format_string_empty() {
         let evs: Vec<&EnvVar> = Vec::new();
-        assert_eq!("", &format_string(&evs, false));
-        assert_eq!("", &format_string(&evs, true));
+        assert_eq!("", &format_string(&evs, false, PRINTENV_SEPARATOR));
+        assert_eq!("", &format_string(&evs, true, NUL_SEPARATOR));
     }
 }
@@ -0,0 +1,153 @@
+//! Print the current working directory.
+
+#![warn(
+    missing_docs,
+    missing_debug_implementations,
+    rust_2018_idioms,
+    clippy::all,
+    clippy::pedantic
+)]
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![cfg_attr(test, test_runner(tlenix_core::custom_test_runner))]
+#![cfg_attr(test, reexport_test_harness_main = "test_main")]
+
+extern crate alloc;
+
+use alloc::string::String;
+use core::panic::PanicInfo;
+
+use getargs::{Arg, Options};
+use tlenix_core::{
+    EnvVar, Errno, eprintln, fs, parse_argv_envp, println,
+    process::{self, ExitStatus},
+    try_exit,
+};
+
+const PANIC_TITLE: &str = "pwd";
+
+core::arch::global_asm! {
+    ".global _start",
+    "_start:",
+    "mov rdi, rsp",
+    "call start"
+}
+
+/// Prints the current working directory.
+///
+/// `-L` (the default) prints the directory as reported by [`fs::get_cwd`]; `-P` additionally
+/// resolves it through [`fs::read_link`] if the path is itself a symbolic link.
+///
+/// # Safety
+///
+/// This program must be passed appropriate `execve`-compatible args.
+#[unsafe(no_mangle)]
+#[allow(unused_variables)]
+unsafe extern "C" fn start(stack_top: *const usize) -> ! {
+    #[cfg(test)]
+    {
+        test_main();
+        process::exit(ExitStatus::ExitSuccess);
+    }
+
+    // HACK: This stops the compiler from complaining when building the test/debug target
+    #[allow(unreachable_code)]
+    #[allow(clippy::no_effect)]
+    ();
+
+    // SAFETY: This function is being called right at the start of execution before anything else.
+    // The stack pointer is retrieved directly from the function args.
+    let (argv, envp) = match unsafe { parse_argv_envp(stack_top) } {
+        Ok(argv_envp) => argv_envp,
+        Err(errno) => process::exit(ExitStatus::ExitFailure(errno as i32)),
+    };
+
+    let exit_code = main(&argv, &envp);
+
+    process::exit(exit_code);
+}
+
+fn main(args: &[String], _env_vars: &[EnvVar]) -> ExitStatus {
+    let physical = try_exit!(parse_physical(args));
+    let cwd = try_exit!(current_dir(physical));
+    println!("{cwd}");
+    ExitStatus::ExitSuccess
+}
+
+/// Returns `true` if `-P` (physical) was requested, `false` for the default `-L` (logical).
+///
+/// # Errors
+///
+/// This function returns [`Errno::Einval`] if the arguments can't be parsed.
+fn parse_physical(args: &[String]) -> Result<bool, Errno> {
+    let mut physical = false;
+    let mut opts = Options::new(args.iter().map(String::as_str).skip(1));
+    while let Some(arg) = opts.next_arg().map_err(|_| Errno::Einval)? {
+        match arg {
+            Arg::Short('P') | Arg::Long("physical") => physical = true,
+            // `-L` and `-P` override each other; the last one wins.
+            Arg::Short('L') | Arg::Long("logical") => physical = false,
+            _ => {}
+        }
+    }
+    Ok(physical)
+}
+
+/// Gets the current working directory, resolving it through any symbolic link if `physical`.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by [`fs::get_cwd`] or [`fs::read_link`].
+fn current_dir(physical: bool) -> Result<String, Errno> {
+    let cwd = fs::get_cwd()?;
+    if physical {
+        match fs::read_link(cwd.as_str()) {
+            Ok(target) => return Ok(target),
+            // Not a symbolic link; the path is already physical.
+            Err(Errno::Einval) => {}
+            Err(errno) => return Err(errno),
+        }
+    }
+    Ok(cwd)
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo<'_>) -> ! {
+    eprintln!("{PANIC_TITLE} {info}");
+    process::exit(ExitStatus::ExitFailure(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    fn argv(words: &[&str]) -> alloc::vec::Vec<String> {
+        core::iter::once("pwd")
+            .chain(words.iter().copied())
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test_case]
+    fn logical_is_default() {
+        assert_eq!(parse_physical(&argv(&[])), Ok(false));
+        assert_eq!(parse_physical(&argv(&["-L"])), Ok(false));
+    }
+
+    #[test_case]
+    fn physical_flag() {
+        assert_eq!(parse_physical(&argv(&["-P"])), Ok(true));
+        assert_eq!(parse_physical(&argv(&["--physical"])), Ok(true));
+        assert_eq!(parse_physical(&argv(&["-P", "-L"])), Ok(false));
+    }
+
+    #[test_case]
+    fn current_dir_matches_get_cwd() {
+        assert_eq!(current_dir(false).unwrap(), fs::get_cwd().unwrap());
+        // The test harness runs from a real directory, so both views agree.
+        assert_eq!(current_dir(true).unwrap(), fs::get_cwd().unwrap());
+    }
+}
@@ -0,0 +1,344 @@
+//! Removes files and directories.
+
+#![warn(
+    missing_docs,
+    missing_debug_implementations,
+    rust_2018_idioms,
+    clippy::all,
+    clippy::pedantic
+)]
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![cfg_attr(test, test_runner(tlenix_core::custom_test_runner))]
+#![cfg_attr(test, reexport_test_harness_main = "test_main")]
+
+extern crate alloc;
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::panic::PanicInfo;
+
+use getargs::{Arg, Options};
+use tlenix_core::{
+    Console, EnvVar, Errno, eprintln,
+    fs::{self, DirEnt, DirEntType, FileStats, FileType, OpenOptions},
+    parse_argv_envp, print,
+    process::{self, ExitStatus},
+    try_exit,
+};
+
+const PANIC_TITLE: &str = "rm";
+
+core::arch::global_asm! {
+    ".global _start",
+    "_start:",
+    "mov rdi, rsp",
+    "call start"
+}
+
+/// All the things that govern `rm`'s behaviour.
+#[derive(Debug, Default)]
+struct RmSettings<'a> {
+    paths: Vec<&'a str>,
+    /// Recurse into directories.
+    recursive: bool,
+    /// Ignore nonexistent paths.
+    force: bool,
+    /// Prompt before removing each path argument.
+    interactive: bool,
+}
+impl<'a> RmSettings<'a> {
+    fn from_cli(args: &'a [String]) -> Result<Self, Errno> {
+        let mut result = Self::default();
+
+        let mut opts = Options::new(args.iter().map(String::as_str).skip(1));
+        while let Some(arg) = opts.next_arg().map_err(|_| Errno::Einval)? {
+            match arg {
+                Arg::Short('r' | 'R') | Arg::Long("recursive") => {
+                    result.recursive = true;
+                }
+                // `-f` and `-i` override each other; the last one wins.
+                Arg::Short('f') | Arg::Long("force") => {
+                    result.force = true;
+                    result.interactive = false;
+                }
+                Arg::Short('i') | Arg::Long("interactive") => {
+                    result.interactive = true;
+                    result.force = false;
+                }
+                Arg::Positional(value) => {
+                    result.paths.push(value);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// Removes files and directories.
+///
+/// `-r`/`-R` recurses into directories, `-f` ignores missing paths, and `-i` prompts before
+/// removing each path argument. Recursion never follows symbolic links — the link itself is
+/// unlinked.
+///
+/// # Safety
+///
+/// This program must be passed appropriate `execve`-compatible args.
+#[unsafe(no_mangle)]
+#[allow(unused_variables)]
+unsafe extern "C" fn start(stack_top: *const usize) -> ! {
+    #[cfg(test)]
+    {
+        test_main();
+        process::exit(ExitStatus::ExitSuccess);
+    }
+
+    // HACK: This stops the compiler from complaining when building the test/debug target
+    #[allow(unreachable_code)]
+    #[allow(clippy::no_effect)]
+    ();
+
+    // SAFETY: This function is being called right at the start of execution before anything else.
+    // The stack pointer is retrieved directly from the function args.
+    let (argv, envp) = match unsafe { parse_argv_envp(stack_top) } {
+        Ok(argv_envp) => argv_envp,
+        Err(errno) => process::exit(ExitStatus::ExitFailure(errno as i32)),
+    };
+
+    let exit_code = main(&argv, &envp);
+
+    process::exit(exit_code);
+}
+
+fn main(args: &[String], _env_vars: &[EnvVar]) -> ExitStatus {
+    let settings = try_exit!(RmSettings::from_cli(args));
+    if settings.paths.is_empty() {
+        eprintln!("Usage: 'rm [-rRfi] <path>...'");
+        return ExitStatus::ExitFailure(255);
+    }
+
+    for path in &settings.paths {
+        try_exit!(remove_path(path, &settings).inspect_err(|errno| {
+            eprintln!("rm failed: '{path}': {errno}");
+        }));
+    }
+
+    ExitStatus::ExitSuccess
+}
+
+/// Removes one path argument according to the given settings.
+fn remove_path(path: &str, settings: &RmSettings<'_>) -> Result<(), Errno> {
+    if settings.interactive && !prompt_removal(path)? {
+        return Ok(());
+    }
+
+    // A symlink is always unlinked itself, never followed — even with `-r`.
+    if fs::read_link(path).is_ok() {
+        return fs::rm(path);
+    }
+
+    let stats = match FileStats::try_from_path(path) {
+        Ok(stats) => stats,
+        Err(Errno::Enoent) if settings.force => return Ok(()),
+        Err(errno) => return Err(errno),
+    };
+
+    if stats.file_type == Some(FileType::Directory) {
+        if !settings.recursive {
+            return Err(Errno::Eisdir);
+        }
+        return remove_dir_recursive(path);
+    }
+    fs::rm(path)
+}
+
+/// Removes a directory and everything beneath it, without following symbolic links.
+fn remove_dir_recursive(path: &str) -> Result<(), Errno> {
+    for dent in OpenOptions::new().open(path)?.dir_ents()? {
+        if dent.name.as_str() == "." || dent.name.as_str() == ".." {
+            continue;
+        }
+        let ent_path = path.to_string() + "/" + dent.name.as_str();
+        if is_directory(&ent_path, &dent)? {
+            remove_dir_recursive(&ent_path)?;
+        } else {
+            fs::rm(&ent_path)?;
+        }
+    }
+    fs::rmdir(path)
+}
+
+/// Returns `true` if the directory entry is itself a directory (not a symlink to one).
+///
+/// Filesystems are allowed to report [`DirEntType::Unknown`], in which case the entry is checked
+/// the long way round.
+fn is_directory(ent_path: &str, dent: &DirEnt) -> Result<bool, Errno> {
+    match dent.d_type {
+        DirEntType::Dir => Ok(true),
+        DirEntType::Unknown => {
+            if fs::read_link(ent_path).is_ok() {
+                return Ok(false);
+            }
+            let stats = FileStats::try_from_path(ent_path)?;
+            Ok(stats.file_type == Some(FileType::Directory))
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Asks the user whether the given path should be removed.
+fn prompt_removal(path: &str) -> Result<bool, Errno> {
+    let console = Console::open()?;
+    print!("Remove '{path}'? [y/N] ");
+    Ok(matches!(
+        String::from_utf8(console.read_line(4096)?)
+            .map_err(|_| Errno::Einval)?
+            .to_lowercase()
+            .as_str(),
+        "yes" | "y"
+    ))
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo<'_>) -> ! {
+    eprintln!("{PANIC_TITLE} {info}");
+    process::exit(ExitStatus::ExitFailure(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RM_TEST_DIR: &str = "/tmp/tlenix_rm_test_dir";
+
+    fn test_setup(test_name: &'static str) -> String {
+        let main_dir = RM_TEST_DIR.to_string() + "/" + test_name;
+        let _ = fs::mkdir(RM_TEST_DIR, fs::FilePermissions::from(0o777));
+        let _ = fs::mkdir(&main_dir, fs::FilePermissions::from(0o777));
+        main_dir
+    }
+
+    fn test_teardown(main_dir: &str) {
+        let _ = fs::rmdir(main_dir);
+        let _ = fs::rmdir(RM_TEST_DIR);
+    }
+
+    fn create_file(path: &str) {
+        OpenOptions::new()
+            .read_write()
+            .create(true)
+            .open(path)
+            .unwrap();
+    }
+
+    fn assert_dne(path: &str) {
+        assert_eq!(FileStats::try_from_path(path), Err(Errno::Enoent));
+    }
+
+    fn settings<'a>(paths: &'a [&str], recursive: bool, force: bool) -> RmSettings<'a> {
+        RmSettings {
+            paths: paths.to_vec(),
+            recursive,
+            force,
+            interactive: false,
+        }
+    }
+
+    #[test_case]
+    fn remove_single_file() {
+        let dir_path = test_setup("remove_single_file");
+        let f_path = dir_path.clone() + "/f";
+        create_file(&f_path);
+
+        remove_path(&f_path, &settings(&[], false, false)).unwrap();
+        assert_dne(&f_path);
+
+        test_teardown(&dir_path);
+    }
+
+    #[test_case]
+    fn dir_without_recursive_fails() {
+        let dir_path = test_setup("dir_without_recursive_fails");
+
+        assert_eq!(
+            remove_path(&dir_path, &settings(&[], false, false)),
+            Err(Errno::Eisdir)
+        );
+
+        test_teardown(&dir_path);
+    }
+
+    #[test_case]
+    fn missing_path_force() {
+        let missing = "/tmp/tlenix_rm_nonexistent";
+        assert_eq!(
+            remove_path(missing, &settings(&[], false, false)),
+            Err(Errno::Enoent)
+        );
+        remove_path(missing, &settings(&[], false, true)).unwrap();
+    }
+
+    #[test_case]
+    fn recursive_removes_tree() {
+        let dir_path = test_setup("recursive_removes_tree");
+
+        let sub = dir_path.clone() + "/sub";
+        let subsub = sub.clone() + "/subsub";
+        fs::mkdir(&sub, fs::FilePermissions::from(0o777)).unwrap();
+        fs::mkdir(&subsub, fs::FilePermissions::from(0o777)).unwrap();
+        create_file(&(dir_path.clone() + "/f1"));
+        create_file(&(sub.clone() + "/f2"));
+        create_file(&(subsub.clone() + "/f3"));
+
+        remove_path(&dir_path, &settings(&[], true, false)).unwrap();
+        assert_dne(&dir_path);
+
+        test_teardown(&dir_path);
+    }
+
+    #[test_case]
+    fn recursive_unlinks_symlink_without_following() {
+        let dir_path = test_setup("recursive_unlinks_symlink");
+
+        // The symlink target lives *outside* the removed tree and must survive.
+        let target = dir_path.clone() + "/target";
+        let doomed = dir_path.clone() + "/doomed";
+        let link = doomed.clone() + "/link";
+        create_file(&target);
+        fs::mkdir(&doomed, fs::FilePermissions::from(0o777)).unwrap();
+        fs::symlink(&target, &link).unwrap();
+
+        remove_path(&doomed, &settings(&[], true, false)).unwrap();
+        assert_dne(&doomed);
+        assert!(FileStats::try_from_path(&target).is_ok());
+
+        fs::rm(&target).unwrap();
+        test_teardown(&dir_path);
+    }
+
+    #[test_case]
+    fn settings_from_cli() {
+        let args = [
+            "rm".to_string(),
+            "-rf".to_string(),
+            "a".to_string(),
+            "b".to_string(),
+        ];
+        let result = RmSettings::from_cli(&args).unwrap();
+        assert_eq!(result.paths, ["a", "b"]);
+        assert!(result.recursive);
+        assert!(result.force);
+        assert!(!result.interactive);
+
+        // The last of `-f`/`-i` wins.
+        let args = ["rm".to_string(), "-fi".to_string()];
+        let result = RmSettings::from_cli(&args).unwrap();
+        assert!(result.interactive);
+        assert!(!result.force);
+    }
+}
@@ -0,0 +1,148 @@
+//! Pauses for a given amount of time.
+
+#![warn(
+    missing_docs,
+    missing_debug_implementations,
+    rust_2018_idioms,
+    clippy::all,
+    clippy::pedantic
+)]
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![cfg_attr(test, test_runner(tlenix_core::custom_test_runner))]
+#![cfg_attr(test, reexport_test_harness_main = "test_main")]
+
+extern crate alloc;
+
+use alloc::string::String;
+use core::{panic::PanicInfo, time::Duration};
+
+use tlenix_core::{
+    EnvVar, Errno, eprintln, parse_argv_envp,
+    process::{self, ExitStatus},
+    thread, try_exit,
+};
+
+const PANIC_TITLE: &str = "sleep";
+
+core::arch::global_asm! {
+    ".global _start",
+    "_start:",
+    "mov rdi, rsp",
+    "call start"
+}
+
+/// Parses a duration argument. Plain numbers (including fractions like `1.5`) are seconds; the
+/// suffixes `ms`, `s`, `m`, and `h` select milliseconds, seconds, minutes, and hours.
+fn parse_duration(s: &str) -> Result<Duration, Errno> {
+    // `ms` must be peeled off before the single-letter suffixes, since it also ends in `s`.
+    let (number, multiplier) = if let Some(rest) = s.strip_suffix("ms") {
+        (rest, 0.001)
+    } else if let Some(rest) = s.strip_suffix('s') {
+        (rest, 1.0)
+    } else if let Some(rest) = s.strip_suffix('m') {
+        (rest, 60.0)
+    } else if let Some(rest) = s.strip_suffix('h') {
+        (rest, 3600.0)
+    } else {
+        (s, 1.0)
+    };
+
+    let value: f64 = number.parse().map_err(|_| Errno::Einval)?;
+    if !value.is_finite() || value < 0.0 {
+        return Err(Errno::Einval);
+    }
+    Ok(Duration::from_secs_f64(value * multiplier))
+}
+
+/// Sleeps for the total of the given duration arguments, then exits.
+///
+/// # Safety
+///
+/// This program must be passed appropriate `execve`-compatible args.
+#[unsafe(no_mangle)]
+#[allow(unused_variables)]
+unsafe extern "C" fn start(stack_top: *const usize) -> ! {
+    #[cfg(test)]
+    {
+        test_main();
+        process::exit(ExitStatus::ExitSuccess);
+    }
+
+    // HACK: This stops the compiler from complaining when building the test/debug target
+    #[allow(unreachable_code)]
+    #[allow(clippy::no_effect)]
+    ();
+
+    // SAFETY: This function is being called right at the start of execution before anything else.
+    // The stack pointer is retrieved directly from the function args.
+    let (argv, envp) = match unsafe { parse_argv_envp(stack_top) } {
+        Ok(argv_envp) => argv_envp,
+        Err(errno) => process::exit(ExitStatus::ExitFailure(errno as i32)),
+    };
+
+    let exit_code = main(&argv, &envp);
+
+    process::exit(exit_code);
+}
+
+fn main(args: &[String], _env_vars: &[EnvVar]) -> ExitStatus {
+    if args.len() < 2 {
+        eprintln!("Usage: 'sleep <duration>...'");
+        return ExitStatus::ExitFailure(255);
+    }
+
+    let mut total = Duration::ZERO;
+    for arg in &args[1..] {
+        total += try_exit!(parse_duration(arg).inspect_err(|_| {
+            eprintln!("sleep: bad duration `{arg}`");
+        }));
+    }
+
+    try_exit!(thread::sleep(&total));
+
+    ExitStatus::ExitSuccess
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo<'_>) -> ! {
+    eprintln!("{PANIC_TITLE} {info}");
+    process::exit(ExitStatus::ExitFailure(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn bare_seconds() {
+        assert_eq!(parse_duration("5"), Ok(Duration::from_secs(5)));
+    }
+
+    #[test_case]
+    fn fractional_seconds() {
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("0.25"), Ok(Duration::from_millis(250)));
+    }
+
+    #[test_case]
+    fn minutes_and_hours() {
+        assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(3600)));
+    }
+
+    #[test_case]
+    fn milliseconds() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+    }
+
+    #[test_case]
+    fn malformed_inputs() {
+        assert_eq!(parse_duration("abc"), Err(Errno::Einval));
+        assert_eq!(parse_duration(""), Err(Errno::Einval));
+        assert_eq!(parse_duration("s"), Err(Errno::Einval));
+        assert_eq!(parse_duration("-1"), Err(Errno::Einval));
+        assert_eq!(parse_duration("inf"), Err(Errno::Einval));
+    }
+}
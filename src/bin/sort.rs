@@ -0,0 +1,258 @@
+//! Sorts lines of text from the given files (or standard input) to standard output.
+
+#![warn(
+    missing_docs,
+    missing_debug_implementations,
+    rust_2018_idioms,
+    clippy::all,
+    clippy::pedantic
+)]
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![cfg_attr(test, test_runner(tlenix_core::custom_test_runner))]
+#![cfg_attr(test, reexport_test_harness_main = "test_main")]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use core::{cmp::Ordering, panic::PanicInfo};
+
+use getargs::{Arg, Options};
+use tlenix_core::{
+    EnvVar, Errno, eprintln, fs, parse_argv_envp,
+    process::{self, ExitStatus},
+    streams, try_exit,
+};
+
+const PANIC_TITLE: &str = "sort";
+
+/// If this symbol is an argument, it means "read from stdin".
+const STDIN_SYMBOL: &str = "-";
+
+core::arch::global_asm! {
+    ".global _start",
+    "_start:",
+    "mov rdi, rsp",
+    "call start"
+}
+
+/// The options given to `sort`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[allow(clippy::struct_excessive_bools)]
+struct SortOpts {
+    /// Reverse the result of the comparisons.
+    reverse: bool,
+    /// Compare according to the numerical value of each line.
+    numeric: bool,
+    /// Output only the first of any run of equal lines.
+    unique: bool,
+    /// Fold lowercase to uppercase characters when comparing.
+    fold_case: bool,
+}
+
+/// Sorts lines of text from the given files (or standard input) to standard output.
+///
+/// # Safety
+///
+/// This program must be passed appropriate `execve`-compatible args.
+#[unsafe(no_mangle)]
+#[allow(unused_variables)]
+unsafe extern "C" fn start(stack_top: *const usize) -> ! {
+    #[cfg(test)]
+    {
+        test_main();
+        process::exit(ExitStatus::ExitSuccess);
+    }
+
+    // HACK: This stops the compiler from complaining when building the test/debug target
+    #[allow(unreachable_code)]
+    #[allow(clippy::no_effect)]
+    ();
+
+    // SAFETY: This function is being called right at the start of execution before anything else.
+    // The stack pointer is retrieved directly from the function args.
+    let (argv, envp) = match unsafe { parse_argv_envp(stack_top) } {
+        Ok(argv_envp) => argv_envp,
+        Err(errno) => process::exit(ExitStatus::ExitFailure(errno as i32)),
+    };
+
+    let exit_code = main(&argv, &envp);
+
+    process::exit(exit_code);
+}
+
+fn main(args: &[String], _env_vars: &[EnvVar]) -> ExitStatus {
+    let (opts, files) = try_exit!(parse_args(args));
+
+    let mut bytes = Vec::new();
+    if files.is_empty() {
+        bytes = try_exit!(streams::STDIN.lock().read_to_bytes());
+    } else {
+        for file in &files {
+            if file == STDIN_SYMBOL {
+                bytes.append(&mut try_exit!(streams::STDIN.lock().read_to_bytes()));
+            } else {
+                bytes.append(&mut try_exit!(
+                    fs::OpenOptions::new().open(file).and_then(|f| f
+                        .read_to_bytes())
+                ));
+            }
+        }
+    }
+
+    let mut lines: Vec<Vec<u8>> = bytes.split(|&b| b == b'\n').map(<[u8]>::to_vec).collect();
+    // A trailing newline leaves an empty final "line"; drop it.
+    if lines.last().is_some_and(Vec::is_empty) {
+        lines.pop();
+    }
+
+    let mut output = Vec::new();
+    for line in sort_lines(lines, opts) {
+        output.extend_from_slice(&line);
+        output.push(b'\n');
+    }
+    try_exit!(streams::STDOUT.lock().write(&output));
+
+    ExitStatus::ExitSuccess
+}
+
+/// Parses the command-line arguments into the [`SortOpts`] and the list of input files.
+fn parse_args(args: &[String]) -> Result<(SortOpts, Vec<String>), Errno> {
+    let mut opts = Options::new(args.iter().map(String::as_str).skip(1));
+    let mut sort_opts = SortOpts::default();
+    let mut files = Vec::new();
+    while let Some(arg) = opts.next_arg().map_err(|_| Errno::Einval)? {
+        match arg {
+            Arg::Short('r') | Arg::Long("reverse") => sort_opts.reverse = true,
+            Arg::Short('n') | Arg::Long("numeric-sort") => sort_opts.numeric = true,
+            Arg::Short('u') | Arg::Long("unique") => sort_opts.unique = true,
+            Arg::Short('f') | Arg::Long("ignore-case") => sort_opts.fold_case = true,
+            Arg::Positional(val) => files.push(String::from(val)),
+            _ => return Err(Errno::Einval),
+        }
+    }
+    Ok((sort_opts, files))
+}
+
+/// Sorts the given lines according to the given options.
+fn sort_lines(mut lines: Vec<Vec<u8>>, opts: SortOpts) -> Vec<Vec<u8>> {
+    lines.sort_by(|a, b| compare(a, b, opts));
+    if opts.unique {
+        lines.dedup_by(|a, b| compare(a, b, opts) == Ordering::Equal);
+    }
+    if opts.reverse {
+        lines.reverse();
+    }
+    lines
+}
+
+/// Compares two lines according to the given options. Does _not_ account for the reverse option;
+/// that is applied after sorting.
+fn compare(a: &[u8], b: &[u8], opts: SortOpts) -> Ordering {
+    if opts.numeric {
+        return numeric_value(a).cmp(&numeric_value(b));
+    }
+    if opts.fold_case {
+        return a
+            .iter()
+            .map(u8::to_ascii_uppercase)
+            .cmp(b.iter().map(u8::to_ascii_uppercase));
+    }
+    a.cmp(b)
+}
+
+/// Parses the leading numerical value of a line. Lines without a leading number count as zero.
+fn numeric_value(line: &[u8]) -> i64 {
+    let mut i = 0;
+    while line.get(i).is_some_and(u8::is_ascii_whitespace) {
+        i += 1;
+    }
+    let negative = line.get(i) == Some(&b'-');
+    if negative {
+        i += 1;
+    }
+    let mut value: i64 = 0;
+    while let Some(digit) = line.get(i).filter(|b| b.is_ascii_digit()) {
+        value = value
+            .saturating_mul(10)
+            .saturating_add(i64::from(digit - b'0'));
+        i += 1;
+    }
+    if negative { -value } else { value }
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo<'_>) -> ! {
+    eprintln!("{PANIC_TITLE} {info}");
+    process::exit(ExitStatus::ExitFailure(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    fn lines(strs: &[&str]) -> Vec<Vec<u8>> {
+        strs.iter().map(|s| s.as_bytes().to_vec()).collect()
+    }
+
+    #[test_case]
+    fn sort_lexical() {
+        let result = sort_lines(lines(&["banana", "apple", "cherry"]), SortOpts::default());
+        assert_eq!(result, lines(&["apple", "banana", "cherry"]));
+    }
+
+    #[test_case]
+    fn sort_lexical_numbers() {
+        // Lexically, "10" sorts before "9".
+        let result = sort_lines(lines(&["9", "10", "1"]), SortOpts::default());
+        assert_eq!(result, lines(&["1", "10", "9"]));
+    }
+
+    #[test_case]
+    fn sort_numeric() {
+        let opts = SortOpts {
+            numeric: true,
+            ..Default::default()
+        };
+        let result = sort_lines(lines(&["10", "9", "-3", "1"]), opts);
+        assert_eq!(result, lines(&["-3", "1", "9", "10"]));
+    }
+
+    #[test_case]
+    fn sort_reverse() {
+        let opts = SortOpts {
+            reverse: true,
+            ..Default::default()
+        };
+        let result = sort_lines(lines(&["apple", "cherry", "banana"]), opts);
+        assert_eq!(result, lines(&["cherry", "banana", "apple"]));
+    }
+
+    #[test_case]
+    fn sort_unique() {
+        let opts = SortOpts {
+            unique: true,
+            ..Default::default()
+        };
+        let result = sort_lines(lines(&["b", "a", "b", "a", "c"]), opts);
+        assert_eq!(result, lines(&["a", "b", "c"]));
+    }
+
+    #[test_case]
+    fn sort_fold_case() {
+        let opts = SortOpts {
+            fold_case: true,
+            ..Default::default()
+        };
+        let result = sort_lines(lines(&["Banana", "apple", "Cherry"]), opts);
+        assert_eq!(result, lines(&["apple", "Banana", "Cherry"]));
+    }
+
+    #[test_case]
+    fn sort_empty() {
+        assert_eq!(sort_lines(vec![], SortOpts::default()), lines(&[]));
+    }
+}
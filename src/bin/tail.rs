@@ -0,0 +1,201 @@
+//! Prints the last lines of its inputs.
+
+#![warn(
+    missing_docs,
+    missing_debug_implementations,
+    rust_2018_idioms,
+    clippy::all,
+    clippy::pedantic
+)]
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![cfg_attr(test, test_runner(tlenix_core::custom_test_runner))]
+#![cfg_attr(test, reexport_test_harness_main = "test_main")]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use core::panic::PanicInfo;
+
+use getargs::{Arg, Options};
+use tlenix_core::{
+    EnvVar, Errno, eprintln, fs, parse_argv_envp,
+    process::{self, ExitStatus},
+    streams, try_exit,
+};
+
+const PANIC_TITLE: &str = "tail";
+
+/// The number of lines printed when `-n` isn't given.
+const DEFAULT_LINE_COUNT: usize = 10;
+
+core::arch::global_asm! {
+    ".global _start",
+    "_start:",
+    "mov rdi, rsp",
+    "call start"
+}
+
+/// All the things that govern `tail`'s behaviour.
+#[derive(Debug)]
+struct TailSettings<'a> {
+    files: Vec<&'a str>,
+    /// The number of lines to print from each input.
+    count: usize,
+}
+impl<'a> TailSettings<'a> {
+    fn from_cli(args: &'a [String]) -> Result<Self, Errno> {
+        let mut result = Self {
+            files: Vec::new(),
+            count: DEFAULT_LINE_COUNT,
+        };
+
+        let mut opts = Options::new(args.iter().map(String::as_str).skip(1));
+        while let Some(arg) = opts.next_arg().map_err(|_| Errno::Einval)? {
+            match arg {
+                Arg::Short('n') | Arg::Long("lines") => {
+                    result.count = opts
+                        .value()
+                        .map_err(|_| Errno::Einval)?
+                        .parse()
+                        .map_err(|_| Errno::Einval)?;
+                }
+                Arg::Positional(value) => result.files.push(value),
+                _ => {}
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// The suffix of the given bytes spanning the last `n` lines. Yields everything if there are fewer
+/// than `n` lines.
+fn tail_lines(bytes: &[u8], n: usize) -> &[u8] {
+    if n == 0 || bytes.is_empty() {
+        return &bytes[..0];
+    }
+    // A trailing newline terminates the last line rather than starting a new one, so it doesn't
+    // count as a line boundary here.
+    let end = if bytes[bytes.len() - 1] == b'\n' {
+        bytes.len() - 1
+    } else {
+        bytes.len()
+    };
+    let mut seen = 0;
+    for i in (0..end).rev() {
+        if bytes[i] == b'\n' {
+            seen += 1;
+            if seen == n {
+                return &bytes[i + 1..];
+            }
+        }
+    }
+    bytes
+}
+
+/// Prints the last lines (default 10, `-n N`) of each given file, or of standard input when no
+/// files are given.
+///
+/// # Safety
+///
+/// This program must be passed appropriate `execve`-compatible args.
+#[unsafe(no_mangle)]
+#[allow(unused_variables)]
+unsafe extern "C" fn start(stack_top: *const usize) -> ! {
+    #[cfg(test)]
+    {
+        test_main();
+        process::exit(ExitStatus::ExitSuccess);
+    }
+
+    // HACK: This stops the compiler from complaining when building the test/debug target
+    #[allow(unreachable_code)]
+    #[allow(clippy::no_effect)]
+    ();
+
+    // SAFETY: This function is being called right at the start of execution before anything else.
+    // The stack pointer is retrieved directly from the function args.
+    let (argv, envp) = match unsafe { parse_argv_envp(stack_top) } {
+        Ok(argv_envp) => argv_envp,
+        Err(errno) => process::exit(ExitStatus::ExitFailure(errno as i32)),
+    };
+
+    let exit_code = main(&argv, &envp);
+
+    process::exit(exit_code);
+}
+
+fn main(args: &[String], _env_vars: &[EnvVar]) -> ExitStatus {
+    let settings = try_exit!(TailSettings::from_cli(args));
+
+    if settings.files.is_empty() {
+        let bytes = try_exit!(streams::STDIN.lock().read_to_bytes());
+        try_exit!(
+            streams::STDOUT
+                .lock()
+                .write(tail_lines(&bytes, settings.count))
+        );
+        return ExitStatus::ExitSuccess;
+    }
+
+    for file in &settings.files {
+        let bytes = try_exit!(
+            fs::OpenOptions::new()
+                .open(*file)
+                .and_then(|f| f.read_to_bytes())
+                .inspect_err(|errno| eprintln!("tail failed: '{file}': {errno}"))
+        );
+        try_exit!(
+            streams::STDOUT
+                .lock()
+                .write(tail_lines(&bytes, settings.count))
+        );
+    }
+
+    ExitStatus::ExitSuccess
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo<'_>) -> ! {
+    eprintln!("{PANIC_TITLE} {info}");
+    process::exit(ExitStatus::ExitFailure(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test_case]
+    fn tail_lines_basic() {
+        assert_eq!(tail_lines(b"a\nb\nc\n", 2), b"b\nc\n");
+    }
+
+    #[test_case]
+    fn tail_lines_fewer_than_n() {
+        assert_eq!(tail_lines(b"a\nb\n", 10), b"a\nb\n");
+    }
+
+    #[test_case]
+    fn tail_lines_no_trailing_newline() {
+        assert_eq!(tail_lines(b"a\nb\nc", 2), b"b\nc");
+        assert_eq!(tail_lines(b"a\nb\nc", 1), b"c");
+    }
+
+    #[test_case]
+    fn tail_lines_zero() {
+        assert_eq!(tail_lines(b"a\nb\n", 0), b"");
+        assert_eq!(tail_lines(b"", 3), b"");
+    }
+
+    #[test_case]
+    fn settings_from_cli() {
+        let args = ["tail", "-n", "5", "f1"].map(ToString::to_string).to_vec();
+        let settings = TailSettings::from_cli(&args).unwrap();
+        assert_eq!(settings.count, 5);
+        assert_eq!(settings.files, ["f1"]);
+    }
+}
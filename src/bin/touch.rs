@@ -0,0 +1,220 @@
+//! Creates files and updates their timestamps.
+
+#![warn(
+    missing_docs,
+    missing_debug_implementations,
+    rust_2018_idioms,
+    clippy::all,
+    clippy::pedantic
+)]
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![cfg_attr(test, test_runner(tlenix_core::custom_test_runner))]
+#![cfg_attr(test, reexport_test_harness_main = "test_main")]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use core::panic::PanicInfo;
+
+use getargs::{Arg, Options};
+use tlenix_core::{
+    EnvVar, Errno, eprintln,
+    fs::{self, FileStats, FileTimestamp, OpenOptions},
+    parse_argv_envp,
+    process::{self, ExitStatus},
+    try_exit,
+};
+
+const PANIC_TITLE: &str = "touch";
+
+core::arch::global_asm! {
+    ".global _start",
+    "_start:",
+    "mov rdi, rsp",
+    "call start"
+}
+
+/// All the things that govern `touch`'s behaviour.
+#[derive(Debug, Default)]
+struct TouchSettings<'a> {
+    paths: Vec<&'a str>,
+    /// Don't create missing paths.
+    no_create: bool,
+    /// Only update the access time.
+    atime_only: bool,
+    /// Only update the modification time.
+    mtime_only: bool,
+}
+impl<'a> TouchSettings<'a> {
+    fn from_cli(args: &'a [String]) -> Result<Self, Errno> {
+        let mut result = Self::default();
+
+        let mut opts = Options::new(args.iter().map(String::as_str).skip(1));
+        while let Some(arg) = opts.next_arg().map_err(|_| Errno::Einval)? {
+            match arg {
+                Arg::Short('c') | Arg::Long("no-create") => result.no_create = true,
+                Arg::Short('a') => result.atime_only = true,
+                Arg::Short('m') => result.mtime_only = true,
+                Arg::Positional(value) => result.paths.push(value),
+                _ => {}
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// The timestamps to pass to [`fs::set_times`]: `-a`/`-m` restrict the update to one of the
+    /// two, and giving both (or neither) updates both.
+    fn times(&self) -> (FileTimestamp, FileTimestamp) {
+        match (self.atime_only, self.mtime_only) {
+            (true, false) => (FileTimestamp::NOW, FileTimestamp::OMIT),
+            (false, true) => (FileTimestamp::OMIT, FileTimestamp::NOW),
+            _ => (FileTimestamp::NOW, FileTimestamp::NOW),
+        }
+    }
+}
+
+/// Creates each given path if it doesn't exist, and updates its timestamps to now otherwise.
+///
+/// `-c` skips missing paths instead of creating them, and `-a`/`-m` update only the access or
+/// modification time.
+///
+/// # Safety
+///
+/// This program must be passed appropriate `execve`-compatible args.
+#[unsafe(no_mangle)]
+#[allow(unused_variables)]
+unsafe extern "C" fn start(stack_top: *const usize) -> ! {
+    #[cfg(test)]
+    {
+        test_main();
+        process::exit(ExitStatus::ExitSuccess);
+    }
+
+    // HACK: This stops the compiler from complaining when building the test/debug target
+    #[allow(unreachable_code)]
+    #[allow(clippy::no_effect)]
+    ();
+
+    // SAFETY: This function is being called right at the start of execution before anything else.
+    // The stack pointer is retrieved directly from the function args.
+    let (argv, envp) = match unsafe { parse_argv_envp(stack_top) } {
+        Ok(argv_envp) => argv_envp,
+        Err(errno) => process::exit(ExitStatus::ExitFailure(errno as i32)),
+    };
+
+    let exit_code = main(&argv, &envp);
+
+    process::exit(exit_code);
+}
+
+fn main(args: &[String], _env_vars: &[EnvVar]) -> ExitStatus {
+    let settings = try_exit!(TouchSettings::from_cli(args));
+    if settings.paths.is_empty() {
+        eprintln!("Usage: 'touch [-cam] <path>...'");
+        return ExitStatus::ExitFailure(255);
+    }
+
+    for path in &settings.paths {
+        try_exit!(touch_path(path, &settings).inspect_err(|errno| {
+            eprintln!("touch failed: '{path}': {errno}");
+        }));
+    }
+
+    ExitStatus::ExitSuccess
+}
+
+/// Touches one path according to the given settings.
+fn touch_path(path: &str, settings: &TouchSettings<'_>) -> Result<(), Errno> {
+    if FileStats::try_from_path(path).is_ok() {
+        let (atime, mtime) = settings.times();
+        return fs::set_times(path, atime, mtime);
+    }
+    if settings.no_create {
+        return Ok(());
+    }
+    // A freshly created file already has "now" for all of its timestamps.
+    OpenOptions::new().create(true).open(path)?;
+    Ok(())
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo<'_>) -> ! {
+    eprintln!("{PANIC_TITLE} {info}");
+    process::exit(ExitStatus::ExitFailure(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use core::time::Duration;
+
+    use super::*;
+
+    fn argv(words: &[&str]) -> Vec<String> {
+        core::iter::once("touch")
+            .chain(words.iter().copied())
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test_case]
+    fn settings_from_cli() {
+        let args = argv(&["-c", "-a", "f1", "f2"]);
+        let settings = TouchSettings::from_cli(&args).unwrap();
+        assert_eq!(settings.paths, ["f1", "f2"]);
+        assert!(settings.no_create);
+        assert!(settings.atime_only);
+        assert!(!settings.mtime_only);
+    }
+
+    #[test_case]
+    fn times_from_flags() {
+        let mut settings = TouchSettings::default();
+        assert_eq!(settings.times(), (FileTimestamp::NOW, FileTimestamp::NOW));
+        settings.atime_only = true;
+        assert_eq!(settings.times(), (FileTimestamp::NOW, FileTimestamp::OMIT));
+        settings.mtime_only = true;
+        assert_eq!(settings.times(), (FileTimestamp::NOW, FileTimestamp::NOW));
+        settings.atime_only = false;
+        assert_eq!(settings.times(), (FileTimestamp::OMIT, FileTimestamp::NOW));
+    }
+
+    #[test_case]
+    fn retouch_advances_mtime() {
+        const PATH: &str = "/tmp/tlenix_touch_retouch";
+
+        let _ = fs::rm(PATH);
+        touch_path(PATH, &TouchSettings::default()).unwrap();
+        let old_mtime = FileStats::try_from_path(PATH)
+            .unwrap()
+            .modification_time
+            .unwrap();
+
+        // Give the clock a moment so the new timestamp is strictly newer.
+        tlenix_core::thread::sleep(&Duration::from_millis(20)).unwrap();
+        touch_path(PATH, &TouchSettings::default()).unwrap();
+        let new_mtime = FileStats::try_from_path(PATH)
+            .unwrap()
+            .modification_time
+            .unwrap();
+
+        fs::rm(PATH).unwrap();
+        assert!(new_mtime > old_mtime);
+    }
+
+    #[test_case]
+    fn no_create_skips_missing() {
+        const PATH: &str = "/tmp/tlenix_touch_no_create";
+
+        let _ = fs::rm(PATH);
+        let settings = TouchSettings {
+            no_create: true,
+            ..TouchSettings::default()
+        };
+        touch_path(PATH, &settings).unwrap();
+        assert_eq!(FileStats::try_from_path(PATH), Err(Errno::Enoent));
+    }
+}
@@ -0,0 +1,238 @@
+//! Translates, squeezes, or deletes characters from standard input to standard output.
+
+#![warn(
+    missing_docs,
+    missing_debug_implementations,
+    rust_2018_idioms,
+    clippy::all,
+    clippy::pedantic
+)]
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![cfg_attr(test, test_runner(tlenix_core::custom_test_runner))]
+#![cfg_attr(test, reexport_test_harness_main = "test_main")]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use core::panic::PanicInfo;
+
+use getargs::{Arg, Options};
+use tlenix_core::{
+    EnvVar, Errno, eprintln, parse_argv_envp,
+    process::{self, ExitStatus},
+    streams, try_exit,
+};
+
+const PANIC_TITLE: &str = "tr";
+
+core::arch::global_asm! {
+    ".global _start",
+    "_start:",
+    "mov rdi, rsp",
+    "call start"
+}
+
+/// The options and sets given to `tr`.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+struct TrInputs {
+    /// Delete the characters in the first set instead of translating them.
+    delete: bool,
+    /// Replace each sequence of a repeated output character with a single occurrence.
+    squeeze: bool,
+    /// The positional set arguments, in order.
+    sets: Vec<String>,
+}
+
+/// Translates, squeezes, or deletes characters from standard input to standard output.
+///
+/// # Safety
+///
+/// This program must be passed appropriate `execve`-compatible args.
+#[unsafe(no_mangle)]
+#[allow(unused_variables)]
+unsafe extern "C" fn start(stack_top: *const usize) -> ! {
+    #[cfg(test)]
+    {
+        test_main();
+        process::exit(ExitStatus::ExitSuccess);
+    }
+
+    // HACK: This stops the compiler from complaining when building the test/debug target
+    #[allow(unreachable_code)]
+    #[allow(clippy::no_effect)]
+    ();
+
+    // SAFETY: This function is being called right at the start of execution before anything else.
+    // The stack pointer is retrieved directly from the function args.
+    let (argv, envp) = match unsafe { parse_argv_envp(stack_top) } {
+        Ok(argv_envp) => argv_envp,
+        Err(errno) => process::exit(ExitStatus::ExitFailure(errno as i32)),
+    };
+
+    let exit_code = main(&argv, &envp);
+
+    process::exit(exit_code);
+}
+
+fn main(args: &[String], _env_vars: &[EnvVar]) -> ExitStatus {
+    let inputs = try_exit!(parse_args(args));
+
+    let set1 = match inputs.sets.first() {
+        Some(spec) => expand_set(spec),
+        None => return ExitStatus::ExitFailure(Errno::Einval as i32),
+    };
+    let set2 = inputs.sets.get(1).map(|spec| expand_set(spec));
+
+    // Translation requires a second set; deletion forbids one, and `-s` alone squeezes a single
+    // set.
+    let valid_arity = if inputs.delete {
+        set2.is_none()
+    } else {
+        set2.is_some() || inputs.squeeze
+    };
+    if !valid_arity {
+        return ExitStatus::ExitFailure(Errno::Einval as i32);
+    }
+
+    let bytes = try_exit!(streams::STDIN.lock().read_to_bytes());
+
+    let mut output = if inputs.delete {
+        delete(&bytes, &set1)
+    } else if let Some(set2) = &set2 {
+        translate(&bytes, &set1, set2)
+    } else {
+        bytes
+    };
+    if inputs.squeeze {
+        // POSIX squeezes runs of the last operand's members: `set2` when translating, `set1`
+        // otherwise.
+        output = squeeze(&output, set2.as_deref().unwrap_or(&set1));
+    }
+    try_exit!(streams::STDOUT.lock().write(&output));
+
+    ExitStatus::ExitSuccess
+}
+
+/// Parses the command-line arguments into the [`TrInputs`].
+fn parse_args(args: &[String]) -> Result<TrInputs, Errno> {
+    let mut opts = Options::new(args.iter().map(String::as_str).skip(1));
+    let mut inputs = TrInputs::default();
+    while let Some(arg) = opts.next_arg().map_err(|_| Errno::Einval)? {
+        match arg {
+            Arg::Short('d') | Arg::Long("delete") => inputs.delete = true,
+            Arg::Short('s') | Arg::Long("squeeze-repeats") => inputs.squeeze = true,
+            Arg::Positional(val) => inputs.sets.push(String::from(val)),
+            _ => return Err(Errno::Einval),
+        }
+    }
+    Ok(inputs)
+}
+
+/// Expands a set specification into its member bytes, interpreting `a-z`-style ranges.
+///
+/// A `-` at the start or end of the specification is taken literally. Backwards ranges expand to
+/// nothing.
+fn expand_set(spec: &str) -> Vec<u8> {
+    let bytes = spec.as_bytes();
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes.get(i + 1) == Some(&b'-')
+            && let Some(&end) = bytes.get(i + 2)
+        {
+            result.extend(bytes[i]..=end);
+            i += 3;
+        } else {
+            result.push(bytes[i]);
+            i += 1;
+        }
+    }
+    result
+}
+
+/// Replaces each byte found in `set1` with the byte at the same position in `set2`. Positions past
+/// the end of `set2` map to the last byte of `set2`, matching POSIX `tr`.
+fn translate(bytes: &[u8], set1: &[u8], set2: &[u8]) -> Vec<u8> {
+    bytes
+        .iter()
+        .map(|&b| match set1.iter().position(|&s| s == b) {
+            Some(i) => *set2.get(i).or(set2.last()).unwrap_or(&b),
+            None => b,
+        })
+        .collect()
+}
+
+/// Removes every byte found in `set1`.
+fn delete(bytes: &[u8], set1: &[u8]) -> Vec<u8> {
+    bytes
+        .iter()
+        .copied()
+        .filter(|b| !set1.contains(b))
+        .collect()
+}
+
+/// Collapses each run of a repeated byte found in `set` into a single occurrence. Runs of bytes
+/// outside `set` are left intact.
+fn squeeze(bytes: &[u8], set: &[u8]) -> Vec<u8> {
+    let mut result: Vec<u8> = Vec::with_capacity(bytes.len());
+    for &b in bytes {
+        if result.last() != Some(&b) || !set.contains(&b) {
+            result.push(b);
+        }
+    }
+    result
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo<'_>) -> ! {
+    eprintln!("{PANIC_TITLE} {info}");
+    process::exit(ExitStatus::ExitFailure(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn expand_lowercase_range() {
+        let set = expand_set("a-z");
+        assert_eq!(set.len(), 26);
+        assert_eq!(set.first(), Some(&b'a'));
+        assert_eq!(set.last(), Some(&b'z'));
+    }
+
+    #[test_case]
+    fn expand_mixed_spec() {
+        assert_eq!(expand_set("x0-3y"), b"x0123y");
+    }
+
+    #[test_case]
+    fn expand_literal_dashes() {
+        assert_eq!(expand_set("-ab-"), b"-ab-");
+    }
+
+    #[test_case]
+    fn translate_basic() {
+        assert_eq!(translate(b"hello", b"el", b"ip"), b"hippo");
+    }
+
+    #[test_case]
+    fn translate_short_set2_repeats_last() {
+        assert_eq!(translate(b"abc", b"abc", b"x"), b"xxx");
+    }
+
+    #[test_case]
+    fn delete_set() {
+        assert_eq!(delete(b"banana", b"an"), b"b");
+    }
+
+    #[test_case]
+    fn squeeze_repeats() {
+        assert_eq!(squeeze(b"aaabbbc", b"abc"), b"abc");
+        // Runs of bytes outside the set stay intact.
+        assert_eq!(squeeze(b"xxaayy", b"a"), b"xxayy");
+        assert_eq!(squeeze(b"aaabbbc", b"b"), b"aaabc");
+    }
+}
@@ -0,0 +1,195 @@
+//! Prints system information.
+
+#![warn(
+    missing_docs,
+    missing_debug_implementations,
+    rust_2018_idioms,
+    clippy::all,
+    clippy::pedantic
+)]
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![cfg_attr(test, test_runner(tlenix_core::custom_test_runner))]
+#![cfg_attr(test, reexport_test_harness_main = "test_main")]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use core::panic::PanicInfo;
+
+use getargs::{Arg, Options};
+use tlenix_core::{
+    EnvVar, Errno, eprintln, parse_argv_envp, println,
+    process::{self, ExitStatus},
+    system::{self, UtsName},
+    try_exit,
+};
+
+const PANIC_TITLE: &str = "uname";
+
+core::arch::global_asm! {
+    ".global _start",
+    "_start:",
+    "mov rdi, rsp",
+    "call start"
+}
+
+/// Which `utsname` fields get printed, in the order `uname` prints them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct UnameSettings {
+    /// Print the operating system name (`-s`).
+    sysname: bool,
+    /// Print the operating system release (`-r`).
+    release: bool,
+    /// Print the hardware identifier (`-m`).
+    machine: bool,
+}
+impl UnameSettings {
+    /// Parses [`UnameSettings`] from the given command-line arguments. Without any flags, only the
+    /// operating system name is printed.
+    fn from_cli(args: &[String]) -> Result<Self, Errno> {
+        let mut opts = Options::new(args.iter().map(String::as_str).skip(1));
+        let mut result = Self {
+            sysname: false,
+            release: false,
+            machine: false,
+        };
+        let mut any_flag = false;
+        while let Some(arg) = opts.next_arg().map_err(|_| Errno::Einval)? {
+            match arg {
+                Arg::Short('a') | Arg::Long("all") => {
+                    result = Self {
+                        sysname: true,
+                        release: true,
+                        machine: true,
+                    };
+                }
+                Arg::Short('s') | Arg::Long("kernel-name") => result.sysname = true,
+                Arg::Short('r') | Arg::Long("kernel-release") => result.release = true,
+                Arg::Short('m') | Arg::Long("machine") => result.machine = true,
+                _ => return Err(Errno::Einval),
+            }
+            any_flag = true;
+        }
+        if !any_flag {
+            result.sysname = true;
+        }
+        Ok(result)
+    }
+}
+
+/// Prints system information.
+///
+/// # Safety
+///
+/// This program must be passed appropriate `execve`-compatible args.
+#[unsafe(no_mangle)]
+#[allow(unused_variables)]
+unsafe extern "C" fn start(stack_top: *const usize) -> ! {
+    #[cfg(test)]
+    {
+        test_main();
+        process::exit(ExitStatus::ExitSuccess);
+    }
+
+    // HACK: This stops the compiler from complaining when building the test/debug target
+    #[allow(unreachable_code)]
+    #[allow(clippy::no_effect)]
+    ();
+
+    // SAFETY: This function is being called right at the start of execution before anything else.
+    // The stack pointer is retrieved directly from the function args.
+    let (argv, envp) = match unsafe { parse_argv_envp(stack_top) } {
+        Ok(argv_envp) => argv_envp,
+        Err(errno) => process::exit(ExitStatus::ExitFailure(errno as i32)),
+    };
+
+    let exit_code = main(&argv, &envp);
+
+    process::exit(exit_code);
+}
+
+fn main(args: &[String], _env_vars: &[EnvVar]) -> ExitStatus {
+    let settings = try_exit!(UnameSettings::from_cli(args));
+    let uts = try_exit!(system::uname());
+    println!("{}", format_fields(&uts, settings).join(" "));
+    ExitStatus::ExitSuccess
+}
+
+/// Collects the selected `utsname` fields in the order `uname` prints them.
+fn format_fields(uts: &UtsName, settings: UnameSettings) -> Vec<&str> {
+    let mut fields = Vec::new();
+    if settings.sysname {
+        fields.push(uts.sysname.as_str());
+    }
+    if settings.release {
+        fields.push(uts.release.as_str());
+    }
+    if settings.machine {
+        fields.push(uts.machine.as_str());
+    }
+    fields
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo<'_>) -> ! {
+    eprintln!("{PANIC_TITLE} {info}");
+    process::exit(ExitStatus::ExitFailure(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    fn argv(words: &[&str]) -> Vec<String> {
+        let mut args = alloc::vec!["uname".to_string()];
+        args.extend(words.iter().map(ToString::to_string));
+        args
+    }
+
+    fn uts() -> UtsName {
+        UtsName {
+            sysname: "Linux".to_string(),
+            nodename: "tlenix".to_string(),
+            release: "6.8.0".to_string(),
+            version: "#1 SMP".to_string(),
+            machine: "x86_64".to_string(),
+            domainname: "(none)".to_string(),
+        }
+    }
+
+    #[test_case]
+    fn settings_default_sysname_only() {
+        let settings = UnameSettings::from_cli(&argv(&[])).unwrap();
+        assert_eq!(format_fields(&uts(), settings), ["Linux"]);
+    }
+
+    #[test_case]
+    fn settings_all() {
+        let settings = UnameSettings::from_cli(&argv(&["-a"])).unwrap();
+        assert_eq!(format_fields(&uts(), settings), ["Linux", "6.8.0", "x86_64"]);
+    }
+
+    #[test_case]
+    fn settings_selected_fields() {
+        let settings = UnameSettings::from_cli(&argv(&["-m", "-s"])).unwrap();
+        // Fields print in `uname` order regardless of flag order.
+        assert_eq!(format_fields(&uts(), settings), ["Linux", "x86_64"]);
+
+        let settings = UnameSettings::from_cli(&argv(&["-r"])).unwrap();
+        assert_eq!(format_fields(&uts(), settings), ["6.8.0"]);
+    }
+
+    #[test_case]
+    fn settings_long_flags() {
+        let settings = UnameSettings::from_cli(&argv(&["--kernel-name", "--machine"])).unwrap();
+        assert_eq!(format_fields(&uts(), settings), ["Linux", "x86_64"]);
+    }
+
+    #[test_case]
+    fn settings_unknown_flag_rejected() {
+        assert_eq!(UnameSettings::from_cli(&argv(&["-z"])), Err(Errno::Einval));
+    }
+}
@@ -0,0 +1,213 @@
+//! Filters adjacent matching lines from the given file (or standard input) to standard output.
+
+#![warn(
+    missing_docs,
+    missing_debug_implementations,
+    rust_2018_idioms,
+    clippy::all,
+    clippy::pedantic
+)]
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![cfg_attr(test, test_runner(tlenix_core::custom_test_runner))]
+#![cfg_attr(test, reexport_test_harness_main = "test_main")]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use core::panic::PanicInfo;
+
+use getargs::{Arg, Options};
+use tlenix_core::{
+    EnvVar, Errno, eprintln, format, fs, parse_argv_envp,
+    process::{self, ExitStatus},
+    streams, try_exit,
+};
+
+const PANIC_TITLE: &str = "uniq";
+
+/// If this symbol is an argument, it means "read from stdin".
+const STDIN_SYMBOL: &str = "-";
+
+core::arch::global_asm! {
+    ".global _start",
+    "_start:",
+    "mov rdi, rsp",
+    "call start"
+}
+
+/// The options given to `uniq`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+struct UniqOpts {
+    /// Prefix lines by the number of occurrences.
+    count: bool,
+    /// Only print duplicated lines, one for each group.
+    duplicates_only: bool,
+    /// Only print lines that are not repeated.
+    unique_only: bool,
+}
+
+/// Filters adjacent matching lines from the given file (or standard input) to standard output.
+///
+/// # Safety
+///
+/// This program must be passed appropriate `execve`-compatible args.
+#[unsafe(no_mangle)]
+#[allow(unused_variables)]
+unsafe extern "C" fn start(stack_top: *const usize) -> ! {
+    #[cfg(test)]
+    {
+        test_main();
+        process::exit(ExitStatus::ExitSuccess);
+    }
+
+    // HACK: This stops the compiler from complaining when building the test/debug target
+    #[allow(unreachable_code)]
+    #[allow(clippy::no_effect)]
+    ();
+
+    // SAFETY: This function is being called right at the start of execution before anything else.
+    // The stack pointer is retrieved directly from the function args.
+    let (argv, envp) = match unsafe { parse_argv_envp(stack_top) } {
+        Ok(argv_envp) => argv_envp,
+        Err(errno) => process::exit(ExitStatus::ExitFailure(errno as i32)),
+    };
+
+    let exit_code = main(&argv, &envp);
+
+    process::exit(exit_code);
+}
+
+fn main(args: &[String], _env_vars: &[EnvVar]) -> ExitStatus {
+    let (opts, path) = try_exit!(parse_args(args));
+
+    let bytes = match path {
+        Some(path) => try_exit!(
+            fs::OpenOptions::new()
+                .open(&path)
+                .and_then(|f| f.read_to_bytes())
+        ),
+        None => try_exit!(streams::STDIN.lock().read_to_bytes()),
+    };
+
+    let mut lines: Vec<&[u8]> = bytes.split(|&b| b == b'\n').collect();
+    // A trailing newline leaves an empty final "line"; drop it.
+    if lines.last() == Some(&&b""[..]) {
+        lines.pop();
+    }
+
+    let mut output = Vec::new();
+    for (count, line) in uniq(&lines, opts) {
+        if opts.count {
+            output.extend_from_slice(format!("{count:>7} ").as_bytes());
+        }
+        output.extend_from_slice(line);
+        output.push(b'\n');
+    }
+    try_exit!(streams::STDOUT.lock().write(&output));
+
+    ExitStatus::ExitSuccess
+}
+
+/// Parses the command-line arguments into the [`UniqOpts`] and the (optional) input file.
+fn parse_args(args: &[String]) -> Result<(UniqOpts, Option<String>), Errno> {
+    let mut opts = Options::new(args.iter().map(String::as_str).skip(1));
+    let mut uniq_opts = UniqOpts::default();
+    let mut path = None;
+    while let Some(arg) = opts.next_arg().map_err(|_| Errno::Einval)? {
+        match arg {
+            Arg::Short('c') | Arg::Long("count") => uniq_opts.count = true,
+            Arg::Short('d') | Arg::Long("repeated") => uniq_opts.duplicates_only = true,
+            Arg::Short('u') | Arg::Long("unique") => uniq_opts.unique_only = true,
+            Arg::Positional(val) => {
+                if path.is_none() && val != STDIN_SYMBOL {
+                    path = Some(String::from(val));
+                }
+            }
+            _ => return Err(Errno::Einval),
+        }
+    }
+    Ok((uniq_opts, path))
+}
+
+/// Collapses runs of adjacent duplicate lines, returning each group's occurrence count alongside
+/// its line, filtered according to the given options.
+fn uniq<'a>(lines: &[&'a [u8]], opts: UniqOpts) -> Vec<(usize, &'a [u8])> {
+    let mut result: Vec<(usize, &[u8])> = Vec::new();
+    for &line in lines {
+        match result.last_mut() {
+            Some((count, last)) if *last == line => *count += 1,
+            _ => result.push((1, line)),
+        }
+    }
+    if opts.duplicates_only {
+        result.retain(|&(count, _)| count > 1);
+    }
+    if opts.unique_only {
+        result.retain(|&(count, _)| count == 1);
+    }
+    result
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo<'_>) -> ! {
+    eprintln!("{PANIC_TITLE} {info}");
+    process::exit(ExitStatus::ExitFailure(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINES: [&[u8]; 6] = [b"a", b"a", b"b", b"c", b"c", b"c"];
+
+    #[test_case]
+    fn uniq_collapses_runs() {
+        let result = uniq(&LINES, UniqOpts::default());
+        assert_eq!(result, [(2, &b"a"[..]), (1, &b"b"[..]), (3, &b"c"[..])]);
+    }
+
+    #[test_case]
+    fn uniq_keeps_nonadjacent_duplicates() {
+        let lines: [&[u8]; 3] = [b"a", b"b", b"a"];
+        let result = uniq(&lines, UniqOpts::default());
+        assert_eq!(result, [(1, &b"a"[..]), (1, &b"b"[..]), (1, &b"a"[..])]);
+    }
+
+    #[test_case]
+    fn uniq_duplicates_only() {
+        let opts = UniqOpts {
+            duplicates_only: true,
+            ..Default::default()
+        };
+        let result = uniq(&LINES, opts);
+        assert_eq!(result, [(2, &b"a"[..]), (3, &b"c"[..])]);
+    }
+
+    #[test_case]
+    fn uniq_unique_only() {
+        let opts = UniqOpts {
+            unique_only: true,
+            ..Default::default()
+        };
+        let result = uniq(&LINES, opts);
+        assert_eq!(result, [(1, &b"b"[..])]);
+    }
+
+    #[test_case]
+    fn uniq_counts_preserved_for_count_option() {
+        let opts = UniqOpts {
+            count: true,
+            ..Default::default()
+        };
+        let result = uniq(&LINES, opts);
+        assert_eq!(result[0].0, 2);
+        assert_eq!(result[2].0, 3);
+    }
+
+    #[test_case]
+    fn uniq_empty() {
+        assert!(uniq(&[], UniqOpts::default()).is_empty());
+    }
+}
@@ -0,0 +1,279 @@
+//! Counts the lines, words, and bytes of its inputs.
+
+#![warn(
+    missing_docs,
+    missing_debug_implementations,
+    rust_2018_idioms,
+    clippy::all,
+    clippy::pedantic
+)]
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![cfg_attr(test, test_runner(tlenix_core::custom_test_runner))]
+#![cfg_attr(test, reexport_test_harness_main = "test_main")]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use core::panic::PanicInfo;
+
+use getargs::{Arg, Options};
+use tlenix_core::{
+    EnvVar, Errno, eprintln, format, fs, parse_argv_envp, println,
+    process::{self, ExitStatus},
+    streams, try_exit,
+};
+
+const PANIC_TITLE: &str = "wc";
+
+core::arch::global_asm! {
+    ".global _start",
+    "_start:",
+    "mov rdi, rsp",
+    "call start"
+}
+
+/// All the things that govern `wc`'s behaviour.
+#[derive(Debug, Default)]
+#[allow(clippy::struct_excessive_bools)]
+struct WcSettings<'a> {
+    files: Vec<&'a str>,
+    /// Print the line count.
+    lines: bool,
+    /// Print the word count.
+    words: bool,
+    /// Print the byte count.
+    bytes: bool,
+    /// Print the character count.
+    chars: bool,
+}
+impl<'a> WcSettings<'a> {
+    fn from_cli(args: &'a [String]) -> Result<Self, Errno> {
+        let mut result = Self::default();
+
+        let mut opts = Options::new(args.iter().map(String::as_str).skip(1));
+        while let Some(arg) = opts.next_arg().map_err(|_| Errno::Einval)? {
+            match arg {
+                Arg::Short('l') | Arg::Long("lines") => result.lines = true,
+                Arg::Short('w') | Arg::Long("words") => result.words = true,
+                Arg::Short('c') | Arg::Long("bytes") => result.bytes = true,
+                Arg::Short('m') | Arg::Long("chars") => result.chars = true,
+                Arg::Positional(value) => result.files.push(value),
+                _ => {}
+            }
+        }
+
+        // With no count flags at all, print the classic lines/words/bytes triple.
+        if !(result.lines || result.words || result.bytes || result.chars) {
+            result.lines = true;
+            result.words = true;
+            result.bytes = true;
+        }
+
+        Ok(result)
+    }
+}
+
+/// The counts of a single input.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Counts {
+    lines: usize,
+    words: usize,
+    chars: usize,
+    bytes: usize,
+}
+impl Counts {
+    /// Adds the given [`Counts`] to this one, for the final total line.
+    fn add(&mut self, other: Counts) {
+        self.lines += other.lines;
+        self.words += other.words;
+        self.chars += other.chars;
+        self.bytes += other.bytes;
+    }
+}
+
+/// Counts the lines, words, characters, and bytes of the given input.
+///
+/// Lines are counted by their terminating newlines, words are runs of non-whitespace bytes, and
+/// characters are UTF-8 code points (counted by skipping continuation bytes).
+fn count(bytes: &[u8]) -> Counts {
+    let mut result = Counts {
+        bytes: bytes.len(),
+        ..Counts::default()
+    };
+
+    let mut in_word = false;
+    for &byte in bytes {
+        if byte == b'\n' {
+            result.lines += 1;
+        }
+        if byte.is_ascii_whitespace() {
+            in_word = false;
+        } else if !in_word {
+            in_word = true;
+            result.words += 1;
+        }
+        // UTF-8 continuation bytes look like 0b10xx_xxxx; everything else starts a character.
+        if (byte & 0xC0) != 0x80 {
+            result.chars += 1;
+        }
+    }
+
+    result
+}
+
+/// Counts the lines, words, and bytes of each given file, or of standard input when no files are
+/// given.
+///
+/// `-l`, `-w`, `-c`, and `-m` restrict the output to the line, word, byte, and character counts
+/// respectively.
+///
+/// # Safety
+///
+/// This program must be passed appropriate `execve`-compatible args.
+#[unsafe(no_mangle)]
+#[allow(unused_variables)]
+unsafe extern "C" fn start(stack_top: *const usize) -> ! {
+    #[cfg(test)]
+    {
+        test_main();
+        process::exit(ExitStatus::ExitSuccess);
+    }
+
+    // HACK: This stops the compiler from complaining when building the test/debug target
+    #[allow(unreachable_code)]
+    #[allow(clippy::no_effect)]
+    ();
+
+    // SAFETY: This function is being called right at the start of execution before anything else.
+    // The stack pointer is retrieved directly from the function args.
+    let (argv, envp) = match unsafe { parse_argv_envp(stack_top) } {
+        Ok(argv_envp) => argv_envp,
+        Err(errno) => process::exit(ExitStatus::ExitFailure(errno as i32)),
+    };
+
+    let exit_code = main(&argv, &envp);
+
+    process::exit(exit_code);
+}
+
+fn main(args: &[String], _env_vars: &[EnvVar]) -> ExitStatus {
+    let settings = try_exit!(WcSettings::from_cli(args));
+
+    if settings.files.is_empty() {
+        let bytes = try_exit!(streams::STDIN.lock().read_to_bytes());
+        println!("{}", fmt_counts(count(&bytes), &settings, None));
+        return ExitStatus::ExitSuccess;
+    }
+
+    let mut total = Counts::default();
+    for file in &settings.files {
+        let bytes = try_exit!(
+            fs::OpenOptions::new()
+                .open(*file)
+                .and_then(|f| f.read_to_bytes())
+                .inspect_err(|errno| eprintln!("wc failed: '{file}': {errno}"))
+        );
+        let counts = count(&bytes);
+        total.add(counts);
+        println!("{}", fmt_counts(counts, &settings, Some(file)));
+    }
+    if settings.files.len() > 1 {
+        println!("{}", fmt_counts(total, &settings, Some("total")));
+    }
+
+    ExitStatus::ExitSuccess
+}
+
+/// Formats the selected counts as a single output line, with the name (if any) at the end.
+fn fmt_counts(counts: Counts, settings: &WcSettings<'_>, name: Option<&str>) -> String {
+    let mut fields: Vec<String> = Vec::new();
+    if settings.lines {
+        fields.push(format!("{:>7}", counts.lines));
+    }
+    if settings.words {
+        fields.push(format!("{:>7}", counts.words));
+    }
+    if settings.chars {
+        fields.push(format!("{:>7}", counts.chars));
+    }
+    if settings.bytes {
+        fields.push(format!("{:>7}", counts.bytes));
+    }
+    if let Some(name) = name {
+        fields.push(name.into());
+    }
+    fields.join(" ")
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo<'_>) -> ! {
+    eprintln!("{PANIC_TITLE} {info}");
+    process::exit(ExitStatus::ExitFailure(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test_case]
+    fn count_empty() {
+        assert_eq!(count(b""), Counts::default());
+    }
+
+    #[test_case]
+    fn count_lines_words_bytes() {
+        let counts = count(b"one two\nthree four five\n");
+        assert_eq!(counts.lines, 2);
+        assert_eq!(counts.words, 5);
+        assert_eq!(counts.bytes, 24);
+    }
+
+    #[test_case]
+    fn count_no_trailing_newline() {
+        let counts = count(b"no newline here");
+        assert_eq!(counts.lines, 0);
+        assert_eq!(counts.words, 3);
+        assert_eq!(counts.bytes, 15);
+    }
+
+    #[test_case]
+    fn count_multibyte_chars() {
+        // "马克斯" is three characters but nine bytes.
+        let counts = count("马克斯\n".as_bytes());
+        assert_eq!(counts.chars, 4);
+        assert_eq!(counts.bytes, 10);
+        assert_eq!(counts.lines, 1);
+        assert_eq!(counts.words, 1);
+    }
+
+    #[test_case]
+    fn count_totals_across_inputs() {
+        let mut total = Counts::default();
+        total.add(count(b"a b\n"));
+        total.add(count(b"c\nd\n"));
+        assert_eq!(total.lines, 3);
+        assert_eq!(total.words, 4);
+        assert_eq!(total.bytes, 8);
+    }
+
+    #[test_case]
+    fn settings_default_triple() {
+        let args = ["wc".to_string(), "f1".to_string()];
+        let settings = WcSettings::from_cli(&args).unwrap();
+        assert!(settings.lines && settings.words && settings.bytes);
+        assert!(!settings.chars);
+        assert_eq!(settings.files, ["f1"]);
+    }
+
+    #[test_case]
+    fn fmt_counts_selected_fields() {
+        let args = ["wc".to_string(), "-l".to_string(), "-m".to_string()];
+        let settings = WcSettings::from_cli(&args).unwrap();
+        let counts = count(b"hi there\n");
+        assert_eq!(fmt_counts(counts, &settings, Some("f")), "      1       9 f");
+    }
+}
@@ -4,9 +4,13 @@
 use alloc::vec::Vec;
 use core::time::Duration;
 
+use alloc::string::String;
+
 use crate::{
-    Errno,
+    Errno, SyscallNum,
     fs::{File, FileType, OpenOptions},
+    syscall_result,
+    term::{self, Key, KeyReader, TermMode},
     thread,
 };
 
@@ -17,6 +21,21 @@ const CONSOLE_PATH: &str = "/dev/console";
 /// Path to the Linux system console device.
 const CONSOLE_PATH: &str = "/dev/tty";
 
+/// `poll` event bit for "data available to read" (`POLLIN`).
+const POLLIN: i16 = 0x1;
+
+/// A single entry polled by the `poll` syscall. Directly corresponds to the `pollfd` struct used
+/// by the [`poll`](https://www.man7.org/linux/man-pages/man2/poll.2.html) Linux syscall.
+#[repr(C)]
+pub(crate) struct PollFdRaw {
+    /// The file descriptor being polled.
+    fd: i32,
+    /// The events the caller is interested in.
+    events: i16,
+    /// The events which actually occurred, filled in by the kernel.
+    revents: i16,
+}
+
 /// Byte representing a backspace.
 const BACKSPACE_BYTE: u8 = 8;
 /// Byte representing a newline.
@@ -30,7 +49,8 @@ const BACKSLASH_BYTE: u8 = b'\\';
 #[derive(Debug)]
 pub struct Console(File);
 impl Console {
-    /// Opens the system console in non-blocking mode with read and write permissions.
+    /// Opens the system console in non-blocking mode with read and write permissions. The console
+    /// won't become the controlling terminal of the calling process.
     ///
     /// # Errors
     ///
@@ -43,6 +63,7 @@ impl Console {
         let file = OpenOptions::new()
             .read_write()
             .non_blocking(true)
+            .no_controlling_terminal(true)
             .open(CONSOLE_PATH)?;
 
         // Reject if not a character device
@@ -74,6 +95,41 @@ impl Console {
         }
     }
 
+    /// Reads a single byte from the [system console](https://en.wikipedia.org/wiki/Linux_console),
+    /// waiting at most the given timeout for one to arrive.
+    ///
+    /// Returns `Ok(None)` if the timeout elapses without any input. Timeouts are measured in
+    /// milliseconds; anything longer than [`i32::MAX`] milliseconds is clamped. Internally uses
+    /// the [`poll`](https://www.man7.org/linux/man-pages/man2/poll.2.html) Linux syscall to wait
+    /// without spinning.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying `poll` syscall and
+    /// [`File::read_byte`] function.
+    pub fn read_byte_timeout(&self, timeout: Duration) -> Result<Option<u8>, Errno> {
+        // OK to allow this; the kernel only hands back small, non-negative descriptors.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+        let mut poll_fd = PollFdRaw {
+            fd: usize::from(self.0.file_descriptor()) as i32,
+            events: POLLIN,
+            revents: 0,
+        };
+        let timeout_ms = i32::try_from(timeout.as_millis()).unwrap_or(i32::MAX);
+
+        // SAFETY: The `PollFdRaw` type matches the layout expected by `poll`, and the mutable raw
+        // pointer to `poll_fd` is dropped right after the syscall.
+        let ready = unsafe {
+            syscall_result!(SyscallNum::Poll, &raw mut poll_fd, 1_usize, timeout_ms)?
+        };
+
+        if ready == 0 {
+            // Timed out without any input.
+            return Ok(None);
+        }
+        self.0.read_byte()
+    }
+
     /// Writes a single byte to the [system console](https://en.wikipedia.org/wiki/Linux_console),
     /// returning the number of bytes written.
     ///
@@ -118,4 +174,152 @@ impl Console {
         }
         Ok(result)
     }
+
+    /// Reads a line from the console (up to a maximum size) with in-place line editing: the
+    /// up/down arrow keys cycle through the given history entries (oldest first), redrawing the
+    /// current line.
+    ///
+    /// The console is temporarily taken out of canonical mode so individual keypresses (and their
+    /// escape sequences) can be decoded; the previous terminal attributes are restored before
+    /// returning. Ctrl-C abandons the line, returning it empty.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying [`term::set_mode`],
+    /// [`term::tcsetattr`], and [`KeyReader::next_key`] calls, as well as any I/O errors while
+    /// echoing.
+    pub fn read_line_interactive(&self, max: usize, history: &[String]) -> Result<Vec<u8>, Errno> {
+        // Decode keys ourselves rather than relying on the terminal's line discipline.
+        let saved = term::set_mode(&self.0, TermMode::Cbreak)?;
+        let result = self.edit_line(max, history);
+        // Restore the previous terminal attributes even if reading failed.
+        let restored = term::tcsetattr(&self.0, &saved);
+        let line = result?;
+        restored?;
+        Ok(line)
+    }
+
+    /// The editing loop behind [`Self::read_line_interactive`]. Expects the console to already be
+    /// out of canonical mode.
+    fn edit_line(&self, max: usize, history: &[String]) -> Result<Vec<u8>, Errno> {
+        let keys = KeyReader::new(&self.0);
+        let mut line: Vec<u8> = Vec::new();
+        // The fresh line, stashed away while browsing history.
+        let mut stash: Vec<u8> = Vec::new();
+        // `None` while editing the fresh line; `Some(i)` while history entry `i` is displayed.
+        let mut history_index: Option<usize> = None;
+
+        loop {
+            match keys.next_key()? {
+                Key::Enter | Key::Eof => {
+                    self.write_byte(NEWLINE_BYTE)?;
+                    return Ok(line);
+                }
+                Key::CtrlC => {
+                    // Abandon the line, like other shells.
+                    self.write_byte(NEWLINE_BYTE)?;
+                    return Ok(Vec::new());
+                }
+                Key::Backspace => {
+                    if line.pop().is_some() {
+                        self.0.write(&[BACKSPACE_BYTE, b' ', BACKSPACE_BYTE])?;
+                    }
+                }
+                Key::Up => {
+                    // The oldest entry is the end of the line (pun intended); stay there.
+                    let Some(next_index) = history_index
+                        .map_or_else(|| history.len().checked_sub(1), |i| i.checked_sub(1))
+                    else {
+                        continue;
+                    };
+                    if history_index.is_none() {
+                        stash.clone_from(&line);
+                    }
+                    self.replace_line(&mut line, history[next_index].as_bytes())?;
+                    history_index = Some(next_index);
+                }
+                Key::Down => match history_index {
+                    None => {}
+                    Some(i) if i + 1 < history.len() => {
+                        self.replace_line(&mut line, history[i + 1].as_bytes())?;
+                        history_index = Some(i + 1);
+                    }
+                    Some(_) => {
+                        // Below the newest entry lies the stashed fresh line.
+                        let stashed = core::mem::take(&mut stash);
+                        self.replace_line(&mut line, &stashed)?;
+                        history_index = None;
+                    }
+                },
+                Key::Char(c) if line.len() + c.len_utf8() <= max => {
+                    let mut buffer = [0_u8; 4];
+                    let encoded = c.encode_utf8(&mut buffer);
+                    line.extend_from_slice(encoded.as_bytes());
+                    self.0.write(encoded.as_bytes())?;
+                }
+                // Everything else (including characters past `max`) is ignored.
+                _ => {}
+            }
+        }
+    }
+
+    /// Replaces both the in-memory line and its on-screen echo with `new`.
+    fn replace_line(&self, line: &mut Vec<u8>, new: &[u8]) -> Result<(), Errno> {
+        self.0.write(&redraw_bytes(line, new))?;
+        line.clear();
+        line.extend_from_slice(new);
+        Ok(())
+    }
+}
+
+/// Computes the bytes to emit so the displayed line `current` becomes `new`: one
+/// backspace-space-backspace triple per displayed byte, then `new` itself.
+///
+/// Erasing the whole current line first keeps the redraw correct when `current` is longer than
+/// `new`. Each byte is assumed to occupy one terminal column.
+fn redraw_bytes(current: &[u8], new: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(current.len() * 3 + new.len());
+    for _ in current {
+        bytes.extend_from_slice(&[BACKSPACE_BYTE, b' ', BACKSPACE_BYTE]);
+    }
+    bytes.extend_from_slice(new);
+    bytes
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn read_byte_timeout_pipe() {
+        // A pipe read end stands in for the console device.
+        let (read_end, write_end) = crate::ipc::pipe().unwrap();
+        let console = Console(read_end);
+
+        // No input available: the timeout elapses and reports `None`.
+        assert_eq!(
+            console.read_byte_timeout(Duration::from_millis(10)).unwrap(),
+            None
+        );
+
+        // Available input is returned without waiting out the timeout.
+        write_end.write(b"x").unwrap();
+        assert_eq!(
+            console.read_byte_timeout(Duration::from_secs(5)).unwrap(),
+            Some(b'x')
+        );
+    }
+
+    #[test_case]
+    fn redraw_erases_longer_line() {
+        // Every displayed byte is rubbed out before the (shorter) new line is written.
+        assert_eq!(redraw_bytes(b"abc", b"z"), b"\x08 \x08\x08 \x08\x08 \x08z");
+    }
+
+    #[test_case]
+    fn redraw_from_empty_line() {
+        assert_eq!(redraw_bytes(b"", b"ls"), b"ls");
+        assert_eq!(redraw_bytes(b"", b""), b"");
+    }
 }
@@ -1,24 +1,34 @@
 //! Module for filesystem operations.
 
+mod dir;
 mod dirs;
 mod file;
 mod mount;
 mod open_flags;
 mod open_options;
 mod permissions;
+mod statfs;
 mod types;
+mod xattr;
 
 // RE-EXPORTS
-pub use dirs::{change_dir, chroot, get_cwd, mkdir, rmdir};
-pub use file::{File, rename, rm};
+pub use dir::{Dir, open_dir};
+pub use dirs::{change_dir, change_dir_tracked, chroot, cwd_into, get_cwd, mkdir, mkdir_p, rmdir};
+pub use file::{
+    File, access, chmod, chown, lchown, link, mkfifo, read_link, rename, rm, same_file, set_times,
+    symlink,
+};
 pub use mount::{FilesystemType, MountFlags, UmountFlags, mount, pivot_root, umount};
 pub use open_flags::OpenFlags;
 pub use open_options::OpenOptions;
-pub use permissions::FilePermissions;
+pub use permissions::{AccessMode, FilePermissions};
+pub use statfs::{FsStats, fstatfs, statfs};
 pub use types::{
-    DirEnt, FileAttributes, FileDescriptor, FileStats, FileStatsMask, FileType, LseekWhence,
-    RenameFlags,
+    DirEnt, DirEntType, FileAttributes, FileDescriptor, FileStats, FileStatsMask, FileTimestamp,
+    FileType, LeaseKind, LseekWhence, RenameFlags, SeekFrom, SyncRangeFlags, device_major,
+    device_minor,
 };
+pub use xattr::{XattrFlags, get_xattr, list_xattr, remove_xattr, set_xattr};
 pub(crate) use types::{FileStatsRaw, statx_get_all};
 
 #[cfg(test)]
@@ -0,0 +1,96 @@
+//! This module is responsible for the [`Dir`] type, a [`File`] guaranteed to be a directory.
+
+use alloc::vec::Vec;
+
+use crate::{
+    Errno, NixString, SyscallNum,
+    fs::{DirEnt, File, OpenFlags, OpenOptions},
+    syscall_result,
+};
+
+/// An object providing access to an open directory on the filesystem.
+///
+/// Wraps a [`File`] opened with the `O_DIRECTORY` flag, so a [`Dir`] is guaranteed to refer to a
+/// directory and only offers directory-flavoured operations.
+#[derive(Debug, PartialEq, Hash)]
+pub struct Dir(File);
+impl Dir {
+    /// Opens the directory at the given path.
+    ///
+    /// Internally, the path is opened with the `O_DIRECTORY` flag, so attempting to open anything
+    /// that isn't a directory fails.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by [`OpenOptions::open`]. Notably, it
+    /// returns [`Errno::Enotdir`] if the path doesn't denote a directory.
+    pub fn open<NS: Into<NixString>>(path: NS) -> Result<Self, Errno> {
+        Ok(Self(OpenOptions::new().directory(true).open(path)?))
+    }
+
+    /// Gets all the entries of this [`Dir`], including the `.` and `..` entries.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by [`File::dir_ents`].
+    pub fn entries(&self) -> Result<Vec<DirEnt>, Errno> {
+        self.0.dir_ents()
+    }
+
+    /// Gets an iterator over the entries of this [`Dir`], including the `.` and `..` entries.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by [`File::dir_ents`].
+    pub fn entries_iter(&self) -> Result<impl Iterator<Item = DirEnt>, Errno> {
+        Ok(self.0.dir_ents()?.into_iter())
+    }
+
+    /// Returns `true` if this [`Dir`] contains no entries besides `.` and `..`.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by [`File::is_dir_empty`].
+    pub fn is_empty(&self) -> Result<bool, Errno> {
+        self.0.is_dir_empty()
+    }
+
+    /// Opens the named entry of this [`Dir`] in read-only mode, resolving the name relative to
+    /// this directory instead of the current working directory.
+    ///
+    /// Wrapper around the [`openat`](https://man7.org/linux/man-pages/man2/openat.2.html) Linux
+    /// syscall.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying call to `openat`.
+    pub fn open_at<NS: Into<NixString>>(&self, name: NS) -> Result<File, Errno> {
+        let name_str: NixString = name.into();
+
+        // SAFETY: The arguments are valid. The pointer to the name is dropped right away.
+        let file_descriptor = unsafe {
+            syscall_result!(
+                SyscallNum::Openat,
+                self.0.file_descriptor(),
+                name_str.as_ptr(),
+                OpenFlags::O_RDONLY.bits(),
+                0
+            )?
+        };
+        Ok(File::__new(file_descriptor.into(), &OpenOptions::new()))
+    }
+}
+
+/// Opens the directory at the given path, which must denote a directory.
+///
+/// Convenience wrapper around [`Dir::open`]: the path is opened with the `O_DIRECTORY` flag, so
+/// anything that isn't a directory — regular files included — is rejected with
+/// [`Errno::Enotdir`] rather than opened.
+///
+/// # Errors
+///
+/// This function returns [`Errno::Enotdir`] if the path doesn't denote a directory, and propagates
+/// any other [`Errno`]s returned by [`OpenOptions::open`].
+pub fn open_dir<NS: Into<NixString>>(path: NS) -> Result<Dir, Errno> {
+    Dir::open(path)
+}
@@ -2,9 +2,15 @@
 
 use alloc::{string::String, vec::Vec};
 
-use crate::{Errno, NULL_BYTE, NixString, SyscallNum, fs::FilePermissions, syscall_result};
+use crate::{
+    Errno, NULL_BYTE, NixString, SyscallNum,
+    fs::{FilePermissions, FileStats, FileType},
+    syscall_result,
+};
 
-const INITIAL_CWD_BUF_SIZE: usize = 1 << 8;
+/// The maximum length of a file path on Linux. Used to seed path buffers so typical paths fit
+/// without retries.
+const PATH_MAX: usize = 4096;
 
 /// Changes the current directory of the process to the given `path`.
 ///
@@ -23,6 +29,42 @@ pub fn change_dir<NS: Into<NixString>>(path: NS) -> Result<(), Errno> {
     Ok(())
 }
 
+/// Changes the current directory of the process to the given `path`, keeping the given
+/// environment's `PWD` and `OLDPWD` variables in step.
+///
+/// After the change, `PWD` holds the canonicalized new working directory and `OLDPWD` holds the
+/// previous one, inserting either variable if it's missing. This is what lets a shell support
+/// `cd -`.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by [`change_dir`] and [`get_cwd`]. The
+/// environment is left untouched on error.
+pub fn change_dir_tracked<NS: Into<NixString>>(
+    path: NS,
+    env: &mut Vec<crate::EnvVar>,
+) -> Result<(), Errno> {
+    let old_cwd = get_cwd()?;
+    change_dir(path)?;
+    let new_cwd = get_cwd()?;
+
+    set_env_var(env, "PWD", new_cwd);
+    set_env_var(env, "OLDPWD", old_cwd);
+    Ok(())
+}
+
+/// Updates the value of the named variable in the given environment, inserting it if missing.
+fn set_env_var(env: &mut Vec<crate::EnvVar>, key: &str, value: String) {
+    if let Some(env_var) = env.iter_mut().find(|ev| ev.key == key) {
+        env_var.value = value;
+    } else {
+        env.push(crate::EnvVar {
+            key: key.into(),
+            value,
+        });
+    }
+}
+
 /// Gets the current working directory of the process.
 ///
 /// Wrapper around the [`getcwd`](https://man7.org/linux/man-pages/man2/getcwd.2.html) Linux
@@ -34,7 +76,31 @@ pub fn change_dir<NS: Into<NixString>>(path: NS) -> Result<(), Errno> {
 ///
 /// Additionally, it returns [`Errno::Eilseq`] if the path is not valid UTF-8.
 pub fn get_cwd() -> Result<String, Errno> {
-    let mut buffer: Vec<u8> = Vec::with_capacity(INITIAL_CWD_BUF_SIZE);
+    let mut cwd = String::new();
+    cwd_into(&mut cwd)?;
+    Ok(cwd)
+}
+
+/// Gets the current working directory of the process, writing it into the given [`String`].
+///
+/// Unlike [`get_cwd`], this function reuses the given buffer's allocation when it's big enough,
+/// making it suited for callers which need the cwd repeatedly (e.g. a shell prompt printed every
+/// loop).
+///
+/// Wrapper around the [`getcwd`](https://man7.org/linux/man-pages/man2/getcwd.2.html) Linux
+/// syscall.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying call to `getcwd`.
+///
+/// Additionally, it returns [`Errno::Eilseq`] if the path is not valid UTF-8. The buffer is left
+/// empty in any error case.
+pub fn cwd_into(buf: &mut String) -> Result<(), Errno> {
+    // Reuse the string's allocation, seeded to PATH_MAX so typical paths fit on the first try.
+    let mut buffer: Vec<u8> = core::mem::take(buf).into_bytes();
+    buffer.clear();
+    buffer.reserve(PATH_MAX.saturating_sub(buffer.capacity()));
 
     // Keep trying to fit the cwd string into the buffer, reallocating if it's too small.
     loop {
@@ -62,7 +128,8 @@ pub fn get_cwd() -> Result<String, Errno> {
         .unwrap_or(buffer.len());
     buffer.truncate(len);
 
-    String::from_utf8(buffer).map_err(|_| Errno::Eilseq)
+    *buf = String::from_utf8(buffer).map_err(|_| Errno::Eilseq)?;
+    Ok(())
 }
 
 /// Attempts to create a new directory with the given path.
@@ -85,6 +152,44 @@ pub fn mkdir<NS: Into<NixString>>(path: NS, mode: FilePermissions) -> Result<(),
     Ok(())
 }
 
+/// Creates the directory at the given `path`, along with any missing parent directories.
+///
+/// Each created component gets the given [`FilePermissions`] mode. Components which already exist
+/// as directories are left untouched, so the function is idempotent, matching `mkdir -p`.
+///
+/// Internally calls [`mkdir`] once per missing component.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s (other than [`Errno::Eexist`]) returned by the
+/// underlying calls to `mkdir`. Notably, [`Errno::Enotdir`] is returned if a component exists as
+/// a regular file.
+pub fn mkdir_p<NS: Into<NixString>>(path: NS, mode: FilePermissions) -> Result<(), Errno> {
+    let ns_path: NixString = path.into();
+    let path_str = ns_path.as_str();
+
+    let mut prefix = String::with_capacity(path_str.len());
+    for component in path_str.split('/') {
+        prefix.push_str(component);
+        // An empty component arises from a leading, trailing, or doubled slash; nothing to make.
+        if !component.is_empty() {
+            match mkdir(prefix.as_str(), mode) {
+                Ok(()) => {}
+                // Fine if the component already exists — as long as it's a directory.
+                Err(Errno::Eexist) => {
+                    let stats = FileStats::try_from_path(prefix.as_str())?;
+                    if stats.file_type != Some(FileType::Directory) {
+                        return Err(Errno::Enotdir);
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        prefix.push('/');
+    }
+    Ok(())
+}
+
 /// Attempts to delete the directory at the given path. This directory must be empty.
 /// Internally uses the [`rmdir`](https://man7.org/linux/man-pages/man2/rmdir.2.html) Linux
 /// syscall.
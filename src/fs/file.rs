@@ -9,17 +9,37 @@ use core::mem::size_of;
 use crate::{
     Errno, NULL_BYTE, NixString, PAGE_SIZE, SyscallNum,
     fs::{
-        AT_FDCWD, DirEnt, FileDescriptor, FileStats, LseekWhence, OpenOptions, RenameFlags,
+        AT_FDCWD, AccessMode, DirEnt, FileDescriptor, FilePermissions, FileStats, FileTimestamp,
+        LeaseKind, LseekWhence, OpenFlags, OpenOptions, RenameFlags, SeekFrom, SyncRangeFlags,
         statx_get_all, types::DirEntRawHeader,
     },
     syscall, syscall_result,
 };
 
-use super::types::DirEntType;
+use super::types::{DirEntType, FileType};
 
 /// Buffer for reading directory entries. Uses page size for better performance.
 const DIR_ENT_BUF_SIZE: usize = PAGE_SIZE;
 
+/// Constant for `*at` syscalls. If this flag is set, symbolic links are not followed.
+const AT_SYMLINK_NOFOLLOW: i32 = 0x100;
+
+/// `fcntl` command to get the file descriptor flags.
+const F_GETFD: usize = 1;
+/// `fcntl` command to set the file descriptor flags.
+const F_SETFD: usize = 2;
+/// `fcntl` command to get the file status flags.
+const F_GETFL: usize = 3;
+/// `fcntl` command to set the file status flags.
+const F_SETFL: usize = 4;
+/// `fcntl` command to set or remove a file lease.
+const F_SETLEASE: usize = 1024;
+/// `fcntl` command to query the file lease currently held.
+const F_GETLEASE: usize = 1025;
+
+/// The close-on-exec file descriptor flag used with [`F_GETFD`]/[`F_SETFD`].
+const FD_CLOEXEC: usize = 1;
+
 /// An object providing access to an open file on the filesystem.
 #[derive(Debug, PartialEq, Hash)]
 pub struct File {
@@ -50,6 +70,13 @@ impl File {
         }
     }
 
+    /// Gets the [`FileDescriptor`] of this [`File`].
+    #[doc(hidden)]
+    #[must_use]
+    pub(crate) const fn file_descriptor(&self) -> FileDescriptor {
+        self.file_descriptor
+    }
+
     /// Gets information about this [`File`] in the form of a [`FileStats`].
     ///
     /// Internally uses the [`statx`](https://man7.org/linux/man-pages/man2/statx.2.html) Linux
@@ -93,6 +120,58 @@ impl File {
         }
     }
 
+    /// Reads exactly enough bytes from the file to fill the given buffer.
+    ///
+    /// [`Self::read`] may return fewer bytes than requested; this function keeps reading until the
+    /// buffer is full, mirroring how [`Self::write`] already loops to completion.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`Errno::Eio`] if EOF is reached before the buffer has been filled.
+    /// In that case, the number of bytes read into the buffer is unspecified.
+    ///
+    /// Any [`Errno`]s returned from the underlying calls to [`Self::read`] are propagated.
+    pub fn read_exact(&self, buffer: &mut [u8]) -> Result<(), Errno> {
+        let mut total_bytes_read = 0;
+
+        while total_bytes_read < buffer.len() {
+            match self.read(&mut buffer[total_bytes_read..])? {
+                // EOF before the buffer was filled.
+                0 => return Err(Errno::Eio),
+                bytes_read => total_bytes_read += bytes_read,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads bytes from the file at the given byte offset into the given buffer, leaving the file
+    /// cursor untouched. Returns the number of bytes read on success.
+    ///
+    /// Uses the [`pread64`](https://www.man7.org/linux/man-pages/man2/pread.2.html) Linux syscall
+    /// internally.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an [`Errno`] if the underlying `pread64` syscall fails. Notably,
+    /// [`Errno::Espipe`] is returned if this [`File`] is unseekable; i.e., a pipe or FIFO.
+    pub fn read_at(&self, buffer: &mut [u8], offset: u64) -> Result<usize, Errno> {
+        let buf_ptr = buffer.as_mut_ptr();
+
+        // SAFETY: The arguments are correct and the length is guaranteed to match the given
+        // buffer. The mutable raw pointer to the array is not accessed after mutating the array
+        // and goes out of scope right after reading.
+        unsafe {
+            syscall_result!(
+                SyscallNum::Pread64,
+                self.file_descriptor,
+                buf_ptr,
+                buffer.len(),
+                offset
+            )
+        }
+    }
+
     /// Reads the entire contents of this file into a [`Vec<u8>`].
     ///
     /// Convenience function. Uses [`Self::read`] internally.
@@ -105,21 +184,52 @@ impl File {
     /// This function will propagate any [`Errno`]s from the internal call to [`Self::read`].
     pub fn read_to_bytes(&self) -> Result<Vec<u8>, Errno> {
         let mut buffer = Vec::new();
+        self.read_to_end_into(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    /// Reads the entire contents of this file, appending them to the given [`Vec<u8>`] and
+    /// returning the number of bytes appended.
+    ///
+    /// Convenience function. Uses [`Self::read`] internally. Unlike [`Self::read_to_bytes`], this
+    /// reuses the buffer's existing capacity, so callers concatenating many files into one buffer
+    /// can avoid repeated allocations.
+    ///
+    /// This function tries to keep the file cursor at the same spot it was before this function
+    /// was called.
+    ///
+    /// # Errors
+    ///
+    /// This function will propagate any [`Errno`]s from the internal call to [`Self::read`]. On
+    /// error, the buffer is truncated back to its original length.
+    pub fn read_to_end_into(&self, buf: &mut Vec<u8>) -> Result<usize, Errno> {
+        let orig_len = buf.len();
         // Chunks are page size for better performance
         let mut chunk = [0_u8; PAGE_SIZE];
 
         let orig_cursor = self.cursor()?;
 
+        // Reserve the file's on-disk size up front to avoid repeated reallocations. `st_size` is
+        // only meaningful for regular files — FIFOs and device nodes report a garbage value — so
+        // everything else just grows chunk by chunk.
+        if let Ok(stats) = self.stats()
+            && stats.file_type == Some(FileType::RegularFile)
+            && let Some(size) = stats.size
+        {
+            buf.reserve(usize::try_from(size).unwrap_or(0));
+        }
+
         loop {
             match self.read(&mut chunk) {
                 // EOF
                 Ok(0) => break,
                 // Got more bytes!
                 Ok(num_bytes_read) => {
-                    buffer.extend_from_slice(&chunk[..num_bytes_read]);
+                    buf.extend_from_slice(&chunk[..num_bytes_read]);
                 }
                 // Error
                 Err(errno) => {
+                    buf.truncate(orig_len);
                     // We have to allow it to be unused, this is simply a last-ditch effort to
                     // restore the cursor after already failing.
                     #[allow(clippy::cast_possible_wrap, unused_must_use)]
@@ -137,7 +247,7 @@ impl File {
             self.set_cursor(orig_cursor as i64)?;
         }
 
-        Ok(buffer)
+        Ok(buf.len() - orig_len)
     }
 
     /// Reads the entire contents of this file into a [`String`].
@@ -216,6 +326,68 @@ impl File {
         Ok(total_bytes_written)
     }
 
+    /// Writes bytes from the provided buffer into the file at the given byte offset, leaving the
+    /// file cursor untouched. Returns the number of bytes written.
+    ///
+    /// Uses the [`pwrite64`](https://www.man7.org/linux/man-pages/man2/pwrite.2.html) Linux
+    /// syscall internally.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an [`Errno`] if the underlying `pwrite64` syscall fails. Notably,
+    /// [`Errno::Espipe`] is returned if this [`File`] is unseekable; i.e., a pipe or FIFO.
+    ///
+    /// [`Errno::Einval`] is returned if this [`File`] was opened in append mode: POSIX has
+    /// `pwrite` silently ignore the offset on `O_APPEND` files (writes always go to the end),
+    /// which would defeat the point of calling this function.
+    pub fn write_at(&self, buffer: &[u8], offset: u64) -> Result<usize, Errno> {
+        if self.open_options.flags_contains(OpenFlags::O_APPEND) {
+            return Err(Errno::Einval);
+        }
+
+        // SAFETY: The arguments are correct. The raw pointer to the buffer is dropped before the
+        // buffer goes out of scope. The buffer length is guaranteed to be correct.
+        unsafe {
+            syscall_result!(
+                SyscallNum::Pwrite64,
+                self.file_descriptor,
+                buffer.as_ptr(),
+                buffer.len(),
+                offset
+            )
+        }
+    }
+
+    /// Truncates or extends this [`File`] to precisely `len` bytes.
+    ///
+    /// Shrinking drops the trailing bytes; extending zero-fills the new region. The file cursor
+    /// is left untouched, even if it now sits past the end of the file.
+    ///
+    /// Relies on the [`ftruncate`](https://www.man7.org/linux/man-pages/man2/ftruncate.2.html)
+    /// Linux syscall internally.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`Errno::Ebadf`] if the file wasn't opened with write access,
+    /// matching [`Self::write`].
+    ///
+    /// Any other errors encountered during the `ftruncate` syscall are propagated as [`Errno`]s.
+    pub fn set_len(&self, len: u64) -> Result<(), Errno> {
+        // The kernel reports a read-only descriptor as EINVAL here; report EBADF like `write`
+        // does instead.
+        if !self.open_options.flags_contains(OpenFlags::O_WRONLY)
+            && !self.open_options.flags_contains(OpenFlags::O_RDWR)
+        {
+            return Err(Errno::Ebadf);
+        }
+
+        // SAFETY: No pointers are involved; the length is passed by value.
+        unsafe {
+            syscall_result!(SyscallNum::Ftruncate, self.file_descriptor, len)?;
+        }
+        Ok(())
+    }
+
     /// Writes a single byte to the file. Returns the number of bytes written.
     ///
     /// Internally relies on the [`write`](https://www.man7.org/linux/man-pages/man2/write.2.html)
@@ -434,6 +606,246 @@ impl File {
         self.lseek_wrapper(offset, LseekWhence::SeekEnd)
     }
 
+    /// Moves the cursor to the given [`SeekFrom`] position in one call. Returns the new cursor
+    /// location.
+    ///
+    /// Equivalent to the individual cursor methods ([`Self::set_cursor`], [`Self::cursor_offset`],
+    /// and [`Self::cursor_to_end_offset`]), but matching the ergonomics of the
+    /// [standard library's `Seek`](https://doc.rust-lang.org/std/io/trait.Seek.html).
+    ///
+    /// Returns [`None`] if cursor operations do not apply to this [`File`]; i.e., the file is a
+    /// terminal, socket, pipe, or FIFO.
+    ///
+    /// Uses the [`lseek`](https://www.man7.org/linux/man-pages/man2/lseek.2.html) Linux syscall
+    /// internally.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`Errno::Einval`] if a [`SeekFrom::Start`] offset doesn't fit the
+    /// kernel's signed offset type.
+    ///
+    /// This function propagates any errors encountered during the underlying `lseek` operation.
+    pub fn seek(&self, pos: SeekFrom) -> Result<Option<usize>, Errno> {
+        let (offset, whence) = match pos {
+            SeekFrom::Start(offset) => (
+                i64::try_from(offset).map_err(|_| Errno::Einval)?,
+                LseekWhence::SeekSet,
+            ),
+            SeekFrom::Current(offset) => (offset, LseekWhence::SeekCur),
+            SeekFrom::End(offset) => (offset, LseekWhence::SeekEnd),
+        };
+        self.lseek_wrapper(offset, whence)
+    }
+
+    /// Moves the cursor to the start of the next hole at or after the given offset, returning the
+    /// new cursor location. The implicit zero-length hole at the end of the file counts, so on a
+    /// file without holes this lands at the end.
+    ///
+    /// Returns [`None`] if cursor operations do not apply to this [`File`]; i.e., the file is a
+    /// terminal, socket, pipe, or FIFO.
+    ///
+    /// Uses the [`lseek`](https://www.man7.org/linux/man-pages/man2/lseek.2.html) Linux syscall
+    /// with `SEEK_HOLE` internally.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`Errno::Einval`] if `offset` doesn't fit the kernel's signed offset
+    /// type, and [`Errno::Enxio`] if `offset` is beyond the end of the file.
+    ///
+    /// This function propagates any other errors encountered during the underlying `lseek`
+    /// operation.
+    pub fn seek_hole(&self, offset: u64) -> Result<Option<usize>, Errno> {
+        let offset = i64::try_from(offset).map_err(|_| Errno::Einval)?;
+        self.lseek_wrapper(offset, LseekWhence::SeekHole)
+    }
+
+    /// Moves the cursor to the start of the next run of data at or after the given offset,
+    /// returning the new cursor location.
+    ///
+    /// Returns [`None`] if cursor operations do not apply to this [`File`]; i.e., the file is a
+    /// terminal, socket, pipe, or FIFO.
+    ///
+    /// Uses the [`lseek`](https://www.man7.org/linux/man-pages/man2/lseek.2.html) Linux syscall
+    /// with `SEEK_DATA` internally.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`Errno::Einval`] if `offset` doesn't fit the kernel's signed offset
+    /// type, and [`Errno::Enxio`] if no data follows `offset` (including when `offset` is beyond
+    /// the end of the file).
+    ///
+    /// This function propagates any other errors encountered during the underlying `lseek`
+    /// operation.
+    pub fn seek_data(&self, offset: u64) -> Result<Option<usize>, Errno> {
+        let offset = i64::try_from(offset).map_err(|_| Errno::Einval)?;
+        self.lseek_wrapper(offset, LseekWhence::SeekData)
+    }
+
+    /// Flushes this [`File`]'s data and metadata to disk.
+    ///
+    /// Blocks until the device reports that the transfer is complete. Succeeds on read-only
+    /// files; there's simply nothing dirty to write back.
+    ///
+    /// Uses the [`fsync`](https://man7.org/linux/man-pages/man2/fsync.2.html) Linux syscall.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying call to `fsync`.
+    /// Notably, [`Errno::Ebadf`] is returned for `O_PATH` file descriptors.
+    pub fn sync(&self) -> Result<(), Errno> {
+        // SAFETY: No pointers are involved.
+        unsafe {
+            syscall_result!(SyscallNum::Fsync, self.file_descriptor)?;
+        }
+        Ok(())
+    }
+
+    /// Flushes this [`File`]'s data to disk, skipping metadata (e.g. timestamps) that isn't
+    /// needed to read the data back.
+    ///
+    /// Uses the [`fdatasync`](https://man7.org/linux/man-pages/man2/fdatasync.2.html) Linux
+    /// syscall.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying call to `fdatasync`.
+    /// Notably, [`Errno::Ebadf`] is returned for `O_PATH` file descriptors.
+    pub fn sync_data(&self) -> Result<(), Errno> {
+        // SAFETY: No pointers are involved.
+        unsafe {
+            syscall_result!(SyscallNum::Fdatasync, self.file_descriptor)?;
+        }
+        Ok(())
+    }
+
+    /// Flushes the given byte range of this [`File`] to disk, without the full-file cost of an
+    /// `fsync`. A `len` of `0` means "everything from `offset` to the end of the file".
+    ///
+    /// The [`SyncRangeFlags`] control whether the call starts writeback, waits for it, or both;
+    /// see [`sync_file_range`](https://man7.org/linux/man-pages/man2/sync_file_range.2.html) for
+    /// the details (and its considerable caveats around durability).
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying call to
+    /// `sync_file_range`. Notably, [`Errno::Einval`] is returned if `offset` or `len` doesn't fit
+    /// in an `off_t`, and [`Errno::Espipe`] if this [`File`] doesn't support the operation (e.g. a
+    /// pipe).
+    pub fn sync_range(&self, offset: u64, len: u64, flags: SyncRangeFlags) -> Result<(), Errno> {
+        let offset = i64::try_from(offset).map_err(|_| Errno::Einval)?;
+        let len = i64::try_from(len).map_err(|_| Errno::Einval)?;
+        // SAFETY: No pointers are involved. The `offset` and `len` arguments match the C `off_t`
+        // type.
+        unsafe {
+            syscall_result!(
+                SyscallNum::SyncFileRange,
+                self.file_descriptor,
+                offset,
+                len,
+                flags.bits()
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Sets (or, with [`LeaseKind::Unlock`], removes) a lease on this [`File`].
+    ///
+    /// When another process performs an operation conflicting with the lease (a "lease break"),
+    /// the kernel notifies the holder by delivering `SIGIO`, then blocks the conflicting opener
+    /// until the lease is downgraded or removed.
+    ///
+    /// Uses the [`fcntl`](https://man7.org/linux/man-pages/man2/fcntl.2.html) Linux syscall with
+    /// the `F_SETLEASE` command.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying call to `fcntl`.
+    /// Notably, taking a lease requires the caller to own the file or hold `CAP_LEASE`.
+    pub fn set_lease(&self, kind: LeaseKind) -> Result<(), Errno> {
+        // SAFETY: No pointers are involved. The `kind` argument is restricted to the allowed
+        // values by the `LeaseKind` enum.
+        unsafe {
+            syscall_result!(SyscallNum::Fcntl, self.file_descriptor, F_SETLEASE, kind)?;
+        }
+        Ok(())
+    }
+
+    /// Queries the type of lease currently held on this [`File`]. Returns [`LeaseKind::Unlock`]
+    /// if no lease is held.
+    ///
+    /// Uses the [`fcntl`](https://man7.org/linux/man-pages/man2/fcntl.2.html) Linux syscall with
+    /// the `F_GETLEASE` command.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying call to `fcntl`.
+    pub fn get_lease(&self) -> Result<LeaseKind, Errno> {
+        // SAFETY: Statically-chosen arguments; no pointers are involved.
+        let raw = unsafe { syscall_result!(SyscallNum::Fcntl, self.file_descriptor, F_GETLEASE)? };
+        raw.try_into().map_err(|_| Errno::Einval)
+    }
+
+    /// Gets the current [`OpenFlags`] of this [`File`], including the access mode it was opened
+    /// with and any status flags (e.g. [`OpenFlags::O_APPEND`]) set since.
+    ///
+    /// Uses the [`fcntl`](https://man7.org/linux/man-pages/man2/fcntl.2.html) Linux syscall with
+    /// the `F_GETFL` command.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying call to `fcntl`.
+    pub fn get_flags(&self) -> Result<OpenFlags, Errno> {
+        // SAFETY: Statically-chosen arguments; no pointers are involved.
+        let raw = unsafe { syscall_result!(SyscallNum::Fcntl, self.file_descriptor, F_GETFL)? };
+        Ok(OpenFlags::from_bits_truncate(raw))
+    }
+
+    /// Sets the status flags of this [`File`], e.g. to flip [`OpenFlags::O_NONBLOCK`] on a pipe
+    /// after opening it.
+    ///
+    /// Only the status flags ([`OpenFlags::O_APPEND`], [`OpenFlags::O_NONBLOCK`],
+    /// [`OpenFlags::O_ASYNC`], [`OpenFlags::O_DIRECT`], and [`OpenFlags::O_NOATIME`]) can be
+    /// changed; the kernel silently ignores the access mode and any creation flags.
+    ///
+    /// Uses the [`fcntl`](https://man7.org/linux/man-pages/man2/fcntl.2.html) Linux syscall with
+    /// the `F_SETFL` command.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying call to `fcntl`.
+    pub fn set_flags(&self, flags: OpenFlags) -> Result<(), Errno> {
+        // SAFETY: No pointers are involved. The kernel ignores any bits that aren't settable
+        // status flags.
+        unsafe {
+            syscall_result!(SyscallNum::Fcntl, self.file_descriptor, F_SETFL, flags.bits())?;
+        }
+        Ok(())
+    }
+
+    /// Sets or clears the close-on-exec flag of this [`File`]'s descriptor, controlling whether it
+    /// survives an `execve`.
+    ///
+    /// Uses the [`fcntl`](https://man7.org/linux/man-pages/man2/fcntl.2.html) Linux syscall with
+    /// the `F_GETFD` and `F_SETFD` commands.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying calls to `fcntl`.
+    pub fn set_cloexec(&self, cloexec: bool) -> Result<(), Errno> {
+        // SAFETY: Statically-chosen arguments; no pointers are involved.
+        let raw = unsafe { syscall_result!(SyscallNum::Fcntl, self.file_descriptor, F_GETFD)? };
+        let new = if cloexec {
+            raw | FD_CLOEXEC
+        } else {
+            raw & !FD_CLOEXEC
+        };
+        // SAFETY: No pointers are involved; `FD_CLOEXEC` is the only defined descriptor flag.
+        unsafe {
+            syscall_result!(SyscallNum::Fcntl, self.file_descriptor, F_SETFD, new)?;
+        }
+        Ok(())
+    }
+
     /// Wrapper around the `lseek` syscall to reduce code duplication.
     ///
     /// Returns [`None`] if cursor operations do not apply to this [`File`]; i.e., the file is a
@@ -458,6 +870,26 @@ impl Drop for File {
     }
 }
 
+/// Returns `true` if the two paths refer to the same underlying file.
+///
+/// Compares the containing device IDs and inode numbers reported by
+/// [`statx`](https://man7.org/linux/man-pages/man2/statx.2.html), so two different names for the
+/// same file — e.g. hard links, or a path and itself — compare equal. Useful as a guard against
+/// things like `cp a a` clobbering its own source.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned while statting either path. Notably,
+/// [`Errno::Enoent`] is returned if either path doesn't exist.
+pub fn same_file<NA: Into<NixString>, NB: Into<NixString>>(a: NA, b: NB) -> Result<bool, Errno> {
+    let stats_a = FileStats::try_from_path(a)?;
+    let stats_b = FileStats::try_from_path(b)?;
+
+    Ok(stats_a.inode.is_some()
+        && (stats_a.fs_device_major, stats_a.fs_device_minor, stats_a.inode)
+            == (stats_b.fs_device_major, stats_b.fs_device_minor, stats_b.inode))
+}
+
 /// Deletes the file at the given path from the filesystem.
 ///
 /// If other processes still have access to the file, it will remain in existence until the last
@@ -480,6 +912,304 @@ pub fn rm<NS: Into<NixString>>(path: NS) -> Result<(), Errno> {
     Ok(())
 }
 
+/// File type bits marking a FIFO in `mknod`'s mode argument.
+const S_IFIFO: usize = 0o1_0000;
+
+/// Creates a FIFO (named pipe) at the given path with the given [`FilePermissions`].
+///
+/// Opening the FIFO for reading blocks until a writer opens it (and vice versa); bytes written to
+/// it are read back in order, like an [`ipc::pipe`](crate::ipc::pipe) with a name on the
+/// filesystem.
+///
+/// Internally uses the [`mknod`](https://www.man7.org/linux/man-pages/man2/mknod.2.html) Linux
+/// syscall.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying `mknod` syscall. Notably,
+/// [`Errno::Eexist`] is returned if the path already exists.
+pub fn mkfifo<NS: Into<NixString>>(path: NS, mode: FilePermissions) -> Result<(), Errno> {
+    let ns_path: NixString = path.into();
+
+    // SAFETY: The path is guaranteed to be null-terminated, valid UTF-8 because of its NixString
+    // type. The device argument is ignored for FIFOs.
+    unsafe {
+        syscall_result!(
+            SyscallNum::Mknod,
+            ns_path.as_ptr(),
+            S_IFIFO | mode.bits(),
+            0usize
+        )?;
+    }
+    Ok(())
+}
+
+/// Checks whether the calling process can access the file at the given path in the given
+/// [`AccessMode`]s, using the process's *real* (not effective) user and group IDs.
+///
+/// [`AccessMode::F_OK`] checks for bare existence; the other modes each check one kind of
+/// permission, and can be OR-ed together to check several at once.
+///
+/// Internally uses the [`faccessat`](https://www.man7.org/linux/man-pages/man2/faccessat.2.html)
+/// Linux syscall with the directory file descriptor set to the current working directory.
+///
+/// # Errors
+///
+/// This function returns [`Errno::Enoent`] if the path doesn't exist, and [`Errno::Eacces`] if it
+/// exists but at least one requested permission is denied.
+pub fn access<NS: Into<NixString>>(path: NS, mode: AccessMode) -> Result<(), Errno> {
+    let ns_path: NixString = path.into();
+
+    // SAFETY: The path is guaranteed to be null-terminated, valid UTF-8 because of its NixString
+    // type. The mode is restricted by the AccessMode type.
+    unsafe {
+        syscall_result!(
+            SyscallNum::Faccessat,
+            AT_FDCWD,
+            ns_path.as_ptr(),
+            mode.bits()
+        )?;
+    }
+    Ok(())
+}
+
+/// Changes the mode of the existing file at the given path to the given [`FilePermissions`].
+///
+/// Unlike [`OpenOptions::set_mode`](crate::fs::OpenOptions::set_mode), which only applies to newly
+/// created files, this works on any existing path.
+///
+/// Internally uses the [`fchmodat`](https://www.man7.org/linux/man-pages/man2/chmod.2.html) Linux
+/// syscall with the directory file descriptor set to the current working directory and no flags.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying `fchmodat` syscall. Notably,
+/// [`Errno::Enoent`] is returned if the path doesn't exist.
+pub fn chmod<NS: Into<NixString>>(path: NS, mode: FilePermissions) -> Result<(), Errno> {
+    let ns_path: NixString = path.into();
+
+    // SAFETY: The path is guaranteed to be null-terminated, valid UTF-8 because of its NixString
+    // type. The mode is restricted by the FilePermissions type.
+    unsafe {
+        syscall_result!(
+            SyscallNum::Fchmodat,
+            AT_FDCWD,
+            ns_path.as_ptr(),
+            mode.bits(),
+            0usize
+        )?;
+    }
+    Ok(())
+}
+
+/// Sets the access and modification timestamps of the file at the given path.
+///
+/// Use [`FileTimestamp::NOW`] to set a timestamp to the current time, and [`FileTimestamp::OMIT`]
+/// to leave it unchanged. If the path is a symbolic link, it is followed.
+///
+/// Internally uses the [`utimensat`](https://www.man7.org/linux/man-pages/man2/utimensat.2.html)
+/// Linux syscall with the directory file descriptor set to the current working directory and no
+/// flags.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying `utimensat` syscall.
+/// Notably, [`Errno::Enoent`] is returned if the path doesn't exist.
+pub fn set_times<NS: Into<NixString>>(
+    path: NS,
+    atime: FileTimestamp,
+    mtime: FileTimestamp,
+) -> Result<(), Errno> {
+    /// A `timespec`-shaped timestamp as expected by `utimensat`.
+    #[repr(C)]
+    struct TimespecRaw {
+        /// Seconds.
+        sec: i64,
+        /// Nanoseconds, or one of the special `UTIME_NOW`/`UTIME_OMIT` values.
+        nsec: i64,
+    }
+
+    let path_ns: NixString = path.into();
+    let times = [
+        TimespecRaw {
+            sec: atime.sec,
+            nsec: i64::from(atime.nsec),
+        },
+        TimespecRaw {
+            sec: mtime.sec,
+            nsec: i64::from(mtime.nsec),
+        },
+    ];
+
+    // SAFETY: The NixString type guarantees null-terminated UTF-8, and the times array matches
+    // the `timespec[2]` layout `utimensat` expects.
+    unsafe {
+        syscall_result!(
+            SyscallNum::Utimensat,
+            AT_FDCWD,
+            path_ns.as_ptr(),
+            times.as_ptr() as usize,
+            0_usize
+        )?;
+    }
+    Ok(())
+}
+
+/// Changes the owner and group of the file at the given path.
+///
+/// Passing [`u32::MAX`] for `uid` or `gid` leaves that field unchanged, matching the kernel's `-1`
+/// convention. If the path is a symbolic link, it is followed; use [`lchown`] to change the
+/// ownership of the link itself.
+///
+/// Internally uses the [`fchownat`](https://www.man7.org/linux/man-pages/man2/chown.2.html) Linux
+/// syscall with the directory file descriptor set to the current working directory.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying `fchownat` syscall. Notably,
+/// only privileged processes may change a file's owner, so unprivileged callers get
+/// [`Errno::Eperm`].
+pub fn chown<NS: Into<NixString>>(path: NS, uid: u32, gid: u32) -> Result<(), Errno> {
+    chown_flags(path, uid, gid, 0)
+}
+
+/// Changes the owner and group of the file at the given path, without following symbolic links.
+///
+/// Identical to [`chown`], except that if the path is a symbolic link, the ownership of the link
+/// itself is changed rather than that of its target.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying `fchownat` syscall. See
+/// [`chown`].
+pub fn lchown<NS: Into<NixString>>(path: NS, uid: u32, gid: u32) -> Result<(), Errno> {
+    chown_flags(path, uid, gid, AT_SYMLINK_NOFOLLOW)
+}
+
+/// Shared implementation of [`chown`] and [`lchown`]; wraps the `fchownat` Linux syscall with the
+/// given flags.
+fn chown_flags<NS: Into<NixString>>(path: NS, uid: u32, gid: u32, flags: i32) -> Result<(), Errno> {
+    let ns_path: NixString = path.into();
+
+    // SAFETY: The path is guaranteed to be null-terminated, valid UTF-8 because of its NixString
+    // type. The ids are passed by value; `u32::MAX` is the kernel's "leave unchanged" sentinel.
+    unsafe {
+        syscall_result!(
+            SyscallNum::Fchownat,
+            AT_FDCWD,
+            ns_path.as_ptr(),
+            uid,
+            gid,
+            flags
+        )?;
+    }
+    Ok(())
+}
+
+/// Creates a new hard link at `new` referring to the same file as `existing`.
+///
+/// Both names refer to the same underlying file afterwards; removing one (e.g. with [`rm`])
+/// leaves the file reachable through the other.
+///
+/// Internally uses the [`linkat`](https://www.man7.org/linux/man-pages/man2/link.2.html) Linux
+/// syscall with both directory file descriptors set to the current working directory and no
+/// flags.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying `linkat` syscall. Notably,
+/// [`Errno::Eexist`] is returned if `new` already exists.
+pub fn link<NA: Into<NixString>, NB: Into<NixString>>(existing: NA, new: NB) -> Result<(), Errno> {
+    let existing_ns: NixString = existing.into();
+    let new_ns: NixString = new.into();
+
+    // SAFETY: Both paths are guaranteed to be null-terminated, valid UTF-8 because of their
+    // NixString type.
+    unsafe {
+        syscall_result!(
+            SyscallNum::Linkat,
+            AT_FDCWD,
+            existing_ns.as_ptr(),
+            AT_FDCWD,
+            new_ns.as_ptr(),
+            0usize
+        )?;
+    }
+    Ok(())
+}
+
+/// Creates a symbolic link at `linkpath` pointing to `target`.
+///
+/// The target doesn't need to exist; dangling symbolic links are allowed.
+///
+/// Internally uses the [`symlink`](https://www.man7.org/linux/man-pages/man2/symlink.2.html)
+/// Linux syscall.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying `symlink` syscall. Notably,
+/// [`Errno::Eexist`] is returned if `linkpath` already exists.
+pub fn symlink<NA: Into<NixString>, NB: Into<NixString>>(
+    target: NA,
+    linkpath: NB,
+) -> Result<(), Errno> {
+    let target_ns: NixString = target.into();
+    let linkpath_ns: NixString = linkpath.into();
+
+    // SAFETY: Both arguments are guaranteed to be null-terminated, valid UTF-8 because of their
+    // NixString type.
+    unsafe {
+        syscall_result!(SyscallNum::Symlink, target_ns.as_ptr(), linkpath_ns.as_ptr())?;
+    }
+    Ok(())
+}
+
+/// Reads the target of the symbolic link at the given path.
+///
+/// Internally uses the [`readlinkat`](https://www.man7.org/linux/man-pages/man2/readlink.2.html)
+/// Linux syscall. Since `readlinkat` doesn't null-terminate its result, a full buffer is taken to
+/// mean truncation and the buffer is grown until the whole target fits.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying `readlinkat` syscall.
+/// Notably, [`Errno::Einval`] is returned if the path is not a symbolic link.
+///
+/// Additionally, it returns [`Errno::Eilseq`] if the link target is not valid UTF-8.
+pub fn read_link<NS: Into<NixString>>(path: NS) -> Result<String, Errno> {
+    /// Starting size of the link target buffer.
+    const INITIAL_BUF_SIZE: usize = 1 << 8;
+
+    let path_ns: NixString = path.into();
+    let mut buffer: Vec<u8> = Vec::with_capacity(INITIAL_BUF_SIZE);
+
+    // Keep trying to fit the link target into the buffer, reallocating if it may be truncated.
+    loop {
+        // Ensure the buffer size matches its capacity
+        buffer.resize(buffer.capacity(), 0);
+        // SAFETY: The NixString type guarantees null-terminated UTF-8. The buffer length is
+        // guaranteed to match the buffer itself, and `readlinkat` writes at most that many bytes.
+        let len = unsafe {
+            syscall_result!(
+                SyscallNum::Readlinkat,
+                AT_FDCWD,
+                path_ns.as_ptr(),
+                buffer.as_mut_ptr(),
+                buffer.len()
+            )?
+        };
+        if len < buffer.len() {
+            // The whole target fit.
+            buffer.truncate(len);
+            break;
+        }
+        // A full buffer means the result may have been truncated. Double the size and try again.
+        buffer.reserve(buffer.capacity());
+    }
+
+    String::from_utf8(buffer).map_err(|_| Errno::Eilseq)
+}
+
 /// Renames a file or directory, optionally moving its location if needed.
 ///
 /// If a file is being renamed and another file exists at that location, the existing file is
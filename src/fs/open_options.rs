@@ -202,6 +202,11 @@ impl OpenOptions {
         /// won't be updated.
         no_update_last_access => O_NOATIME;
 
+        /// If this flag is set, when [`Self::open`] is called and the file is a terminal device,
+        /// the terminal won't become the controlling terminal of the calling process, even if the
+        /// process doesn't already have one.
+        no_controlling_terminal => O_NOCTTY;
+
         /// If this flag is set, when [`Self::open`] is called and the file is a symbolic link,
         /// then the operation will fail with [`Errno::Eloop`].
         no_follow => O_NOFOLLOW;
@@ -309,6 +314,13 @@ impl OpenOptions {
                     // Can't create a tempfile in read-only mode
                     self.open_flags.insert(OpenFlags::O_RDWR);
                 }
+                // O_TMPFILE takes a directory path but opens a new file, so requiring the opened
+                // file to be a directory would always fail
+                self.open_flags.remove(OpenFlags::O_DIRECTORY);
+            }
+            (OpenFlags::O_DIRECTORY, true) => {
+                // O_TMPFILE and O_DIRECTORY are contradictory; see above
+                self.open_flags.remove(OpenFlags::O_TMPFILE);
             }
             _ => {}
         }
@@ -462,6 +474,32 @@ mod tests {
         assert_eq!(oo.open_flags, OpenFlags::O_RDONLY);
     }
 
+    #[test_case]
+    fn no_tmp_and_directory() {
+        let mut oo = OpenOptions::new();
+
+        // Whichever of the contradictory pair was set last wins.
+        oo.directory(true).create_temp(true);
+        assert!(!oo.flags_contains(OpenFlags::O_DIRECTORY));
+        assert!(oo.flags_contains(OpenFlags::O_TMPFILE));
+
+        oo.directory(true);
+        assert!(oo.flags_contains(OpenFlags::O_DIRECTORY));
+        assert!(!oo.flags_contains(OpenFlags::O_TMPFILE));
+    }
+
+    #[test_case]
+    fn no_ctty() {
+        let mut oo = OpenOptions::new();
+        assert!(!oo.flags_contains(OpenFlags::O_NOCTTY));
+
+        oo.no_controlling_terminal(true);
+        assert!(oo.flags_contains(OpenFlags::O_NOCTTY));
+
+        oo.no_controlling_terminal(false);
+        assert!(!oo.flags_contains(OpenFlags::O_NOCTTY));
+    }
+
     #[test_case]
     fn set_mode_fp() {
         let mut oo = OpenOptions::new();
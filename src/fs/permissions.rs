@@ -1,7 +1,24 @@
-//! The [`FilePermissions`] bitflags.
+//! The [`FilePermissions`] and [`AccessMode`] bitflags.
 
 use core::default::Default;
 
+bitflags::bitflags! {
+    /// The accessibility checks performable by [`access`](crate::fs::access). See
+    /// [`faccessat(2)`](https://www.man7.org/linux/man-pages/man2/faccessat.2.html) for more
+    /// details.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+    pub struct AccessMode: u32 {
+        /// Does the file exist? (Implied by every other check.)
+        const F_OK = 0;
+        /// Can the file be read?
+        const R_OK = 0o4;
+        /// Can the file be written?
+        const W_OK = 0o2;
+        /// Can the file be executed/searched?
+        const X_OK = 0o1;
+    }
+}
+
 bitflags::bitflags! {
     /// The attributes of a given file. See
     /// [here](https://www.man7.org/linux/man-pages/man3/mode_t.3type.html) for more details.
@@ -0,0 +1,116 @@
+//! This module is responsible for filesystem-level statistics: the [`FsStats`] type and the
+//! [`statfs`]/[`fstatfs`] wrappers producing it.
+
+use crate::{Errno, NixString, SyscallNum, syscall_result};
+
+use super::file::File;
+
+/// Statistics about a mounted filesystem, as reported by [`statfs`] or [`fstatfs`].
+///
+/// Block counts are in units of [`FsStats::block_size`] bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FsStats {
+    /// The filesystem's optimal transfer block size, in bytes.
+    pub block_size: u64,
+    /// The total number of data blocks in the filesystem.
+    pub total_blocks: u64,
+    /// The number of free blocks in the filesystem.
+    pub free_blocks: u64,
+    /// The number of free blocks available to unprivileged users.
+    pub available_blocks: u64,
+    /// The total number of inodes in the filesystem.
+    pub total_inodes: u64,
+    /// The number of free inodes in the filesystem.
+    pub free_inodes: u64,
+    /// The filesystem's type magic number (e.g. `0xEF53` for ext2/3/4).
+    pub fs_type: u64,
+}
+
+/// A `statfs`-shaped buffer as filled in by the `statfs`/`fstatfs` syscalls.
+#[repr(C)]
+#[derive(Default)]
+#[allow(dead_code)]
+struct FsStatsRaw {
+    /// The filesystem's type magic number.
+    fs_type: i64,
+    /// The optimal transfer block size.
+    block_size: i64,
+    /// The total number of data blocks.
+    total_blocks: u64,
+    /// The number of free blocks.
+    free_blocks: u64,
+    /// The number of free blocks available to unprivileged users.
+    available_blocks: u64,
+    /// The total number of inodes.
+    total_inodes: u64,
+    /// The number of free inodes.
+    free_inodes: u64,
+    /// The filesystem ID.
+    fs_id: [i32; 2],
+    /// The maximum length of filenames.
+    name_len: i64,
+    /// The fragment size.
+    fragment_size: i64,
+    /// The mount flags.
+    flags: i64,
+    /// Padding reserved by the kernel.
+    spare: [i64; 4],
+}
+
+impl From<FsStatsRaw> for FsStats {
+    fn from(raw: FsStatsRaw) -> Self {
+        // OK to lose sign here; the kernel never reports negative sizes or magic numbers.
+        #[allow(clippy::cast_sign_loss)]
+        Self {
+            block_size: raw.block_size as u64,
+            total_blocks: raw.total_blocks,
+            free_blocks: raw.free_blocks,
+            available_blocks: raw.available_blocks,
+            total_inodes: raw.total_inodes,
+            free_inodes: raw.free_inodes,
+            fs_type: raw.fs_type as u64,
+        }
+    }
+}
+
+/// Gets the [`FsStats`] of the filesystem containing the given path.
+///
+/// Wrapper around the [`statfs`](https://man7.org/linux/man-pages/man2/statfs.2.html) Linux
+/// syscall.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying `statfs` syscall. Notably,
+/// it returns [`Errno::Enoent`] if the path doesn't exist.
+pub fn statfs<NS: Into<NixString>>(path: NS) -> Result<FsStats, Errno> {
+    let ns_path = path.into();
+    let mut raw = FsStatsRaw::default();
+
+    // SAFETY: The `FsStatsRaw` type matches the layout expected by `statfs`, and both raw
+    // pointers are dropped right after the syscall.
+    unsafe {
+        syscall_result!(SyscallNum::Statfs, ns_path.as_ptr(), &raw mut raw as usize)?;
+    }
+
+    Ok(raw.into())
+}
+
+/// Gets the [`FsStats`] of the filesystem containing the given open [`File`].
+///
+/// Wrapper around the [`fstatfs`](https://man7.org/linux/man-pages/man2/statfs.2.html) Linux
+/// syscall.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying `fstatfs` syscall.
+pub fn fstatfs(file: &File) -> Result<FsStats, Errno> {
+    let mut raw = FsStatsRaw::default();
+
+    // SAFETY: The `FsStatsRaw` type matches the layout expected by `fstatfs`, and the mutable raw
+    // pointer to `raw` is dropped right after the syscall.
+    unsafe {
+        syscall_result!(SyscallNum::Fstatfs, file.file_descriptor(), &raw mut raw as usize)?;
+    }
+
+    Ok(raw.into())
+}
@@ -1,6 +1,9 @@
 #![allow(clippy::unwrap_used)]
 
-use alloc::string::ToString;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
 
 use crate::{Errno, assert_err, format, fs::types::DirEntType};
 
@@ -69,6 +72,456 @@ fn read_past_end() {
     assert_eq!(buffer, expected_2);
 }
 
+#[test_case]
+fn chmod_changes_mode() {
+    const PATH: &str = "/tmp/tlenix_chmod_test";
+
+    let _ = rm(PATH);
+    OpenOptions::new()
+        .create(true)
+        .set_mode(0o600)
+        .open(PATH)
+        .unwrap();
+    assert_eq!(
+        FileStats::try_from_path(PATH).unwrap().mode,
+        Some(FilePermissions::from(0o600))
+    );
+
+    chmod(PATH, FilePermissions::from(0o644)).unwrap();
+    assert_eq!(
+        FileStats::try_from_path(PATH).unwrap().mode,
+        Some(FilePermissions::from(0o644))
+    );
+
+    rm(PATH).unwrap();
+}
+
+#[test_case]
+fn chmod_missing_path() {
+    assert_err!(
+        chmod("/tmp/tlenix_chmod_missing", FilePermissions::default()),
+        Errno::Enoent
+    );
+}
+
+#[test_case]
+fn write_at_append_mode_rejected() {
+    const PATH: &str = "/tmp/tlenix_write_at_append";
+
+    let _ = rm(PATH);
+    let file = OpenOptions::new()
+        .read_write()
+        .create(true)
+        .append(true)
+        .open(PATH)
+        .unwrap();
+    file.write(b"base").unwrap();
+
+    // `pwrite` would silently ignore the offset on an `O_APPEND` file, so it's rejected.
+    assert_err!(file.write_at(b"x", 0), Errno::Einval);
+
+    // The file is untouched by the rejected write.
+    let mut buffer = [0_u8; 4];
+    file.read_at(&mut buffer, 0).unwrap();
+    assert_eq!(&buffer, b"base");
+
+    rm(PATH).unwrap();
+}
+
+#[test_case]
+fn seek_all_variants() {
+    let file = OpenOptions::new().open(TEST_PATH).unwrap();
+    let len = TEST_PATH_CONTENTS.len();
+
+    assert_eq!(file.seek(SeekFrom::Start(7)).unwrap(), Some(7));
+    assert_eq!(file.seek(SeekFrom::Current(-2)).unwrap(), Some(5));
+    assert_eq!(file.seek(SeekFrom::Current(3)).unwrap(), Some(8));
+    assert_eq!(file.seek(SeekFrom::End(-4)).unwrap(), Some(len - 4));
+    assert_eq!(file.seek(SeekFrom::End(0)).unwrap(), Some(len));
+
+    // Reads continue from the sought-to position.
+    file.seek(SeekFrom::Start(0)).unwrap();
+    let mut buffer = [0_u8; 6];
+    file.read_exact(&mut buffer).unwrap();
+    assert_eq!(&buffer, b"Hello!");
+
+    // A start offset too big for the kernel's signed offset type is rejected up front.
+    assert_err!(file.seek(SeekFrom::Start(u64::MAX)), Errno::Einval);
+}
+
+#[test_case]
+fn sync_range_flushes_written_region() {
+    const PATH: &str = "/tmp/tlenix_sync_range_test";
+
+    let _ = rm(PATH);
+    let file = OpenOptions::new()
+        .read_write()
+        .create(true)
+        .open(PATH)
+        .unwrap();
+
+    // Write 8 KiB, then flush just the first 4 KiB.
+    let contents = [0x5A_u8; 8192];
+    file.write(&contents).unwrap();
+    file.sync_range(0, 4096, SyncRangeFlags::WRITE | SyncRangeFlags::WAIT_AFTER)
+        .unwrap();
+
+    rm(PATH).unwrap();
+}
+
+#[test_case]
+fn sync_after_write() {
+    const PATH: &str = "/tmp/tlenix_sync_test";
+
+    let _ = rm(PATH);
+    let file = OpenOptions::new()
+        .read_write()
+        .create(true)
+        .open(PATH)
+        .unwrap();
+
+    file.write(b"durable").unwrap();
+    file.sync().unwrap();
+    file.sync_data().unwrap();
+
+    // A read-only descriptor has nothing dirty, but syncing it still succeeds.
+    let read_only = OpenOptions::new().open(PATH).unwrap();
+    read_only.sync().unwrap();
+
+    rm(PATH).unwrap();
+}
+
+#[test_case]
+fn fcntl_flags_round_trip() {
+    const PATH: &str = "/tmp/tlenix_fcntl_flags_test";
+
+    let _ = rm(PATH);
+    let file = OpenOptions::new()
+        .read_write()
+        .create(true)
+        .open(PATH)
+        .unwrap();
+
+    let flags = file.get_flags().unwrap();
+    assert!(flags.contains(OpenFlags::O_RDWR));
+    assert!(!flags.contains(OpenFlags::O_APPEND));
+
+    // Toggle `O_APPEND` on the already-open descriptor, then read the flags back.
+    file.set_flags(flags | OpenFlags::O_APPEND).unwrap();
+    assert!(file.get_flags().unwrap().contains(OpenFlags::O_APPEND));
+    file.set_flags(flags).unwrap();
+    assert!(!file.get_flags().unwrap().contains(OpenFlags::O_APPEND));
+
+    // Close-on-exec lives in the descriptor flags, so it doesn't show up in `get_flags`.
+    file.set_cloexec(true).unwrap();
+    file.set_cloexec(false).unwrap();
+
+    rm(PATH).unwrap();
+}
+
+#[test_case]
+fn xattr_round_trip() {
+    const PATH: &str = "/tmp/tlenix_xattr_test";
+    const NAME: &str = "user.test";
+    const VALUE: &[u8] = b"xattr value";
+
+    let _ = rm(PATH);
+    let file = OpenOptions::new()
+        .read_write()
+        .create(true)
+        .open(PATH)
+        .unwrap();
+
+    // Not every filesystem supports `user.*` attributes; skip gracefully if this one doesn't.
+    match set_xattr(PATH, NAME, VALUE, XattrFlags::default()) {
+        Ok(()) => {}
+        Err(Errno::Eopnotsupp) => {
+            rm(PATH).unwrap();
+            return;
+        }
+        Err(errno) => panic!("setxattr fail: {errno}"),
+    }
+
+    assert!(list_xattr(PATH).unwrap().iter().any(|name| name == NAME));
+    assert_eq!(get_xattr(PATH, NAME).unwrap(), VALUE);
+
+    // The descriptor-based variants see the same attribute.
+    assert_eq!(file.get_xattr(NAME).unwrap(), VALUE);
+    assert!(file.list_xattr().unwrap().iter().any(|name| name == NAME));
+
+    // A pure create of an existing attribute must fail; a pure replace must succeed.
+    assert_err!(
+        set_xattr(PATH, NAME, VALUE, XattrFlags::CREATE),
+        Errno::Eexist
+    );
+    file.set_xattr(NAME, b"replaced", XattrFlags::REPLACE)
+        .unwrap();
+    assert_eq!(get_xattr(PATH, NAME).unwrap(), b"replaced");
+
+    remove_xattr(PATH, NAME).unwrap();
+    assert_err!(get_xattr(PATH, NAME), Errno::Enodata);
+
+    rm(PATH).unwrap();
+}
+
+#[test_case]
+fn read_to_end_into_appends() {
+    let file_a = OpenOptions::new().open(TEST_PATH).unwrap();
+    let file_b = OpenOptions::new().open(LARGE_PATH).unwrap();
+
+    // Concatenate both files into one buffer, like `cat` does.
+    let mut buffer = Vec::new();
+    let count_a = file_a.read_to_end_into(&mut buffer).unwrap();
+    let count_b = file_b.read_to_end_into(&mut buffer).unwrap();
+
+    assert_eq!(count_a, TEST_PATH_CONTENTS.len());
+    assert_eq!(count_b, LARGE_CONTENTS_BYTES.len());
+    assert_eq!(&buffer[..count_a], TEST_PATH_CONTENTS.as_bytes());
+    assert_eq!(&buffer[count_a..], LARGE_CONTENTS_BYTES);
+}
+
+#[test_case]
+fn exchange_non_empty_dirs() {
+    const DIR_A: &str = "/tmp/tlenix_exchange_dir_a";
+    const DIR_B: &str = "/tmp/tlenix_exchange_dir_b";
+    const FILE_A: &str = "/tmp/tlenix_exchange_dir_a/file_a";
+    const FILE_B: &str = "/tmp/tlenix_exchange_dir_b/file_b";
+
+    // Clean up any leftovers from previous runs.
+    let _ = rm(FILE_A);
+    let _ = rm(FILE_B);
+    let _ = rm("/tmp/tlenix_exchange_dir_a/file_b");
+    let _ = rm("/tmp/tlenix_exchange_dir_b/file_a");
+    let _ = rmdir(DIR_A);
+    let _ = rmdir(DIR_B);
+
+    mkdir(DIR_A, FilePermissions::from(0o755)).unwrap();
+    mkdir(DIR_B, FilePermissions::from(0o755)).unwrap();
+    OpenOptions::new().create(true).open(FILE_A).unwrap();
+    OpenOptions::new().create(true).open(FILE_B).unwrap();
+
+    // Atomically swap the two non-empty directories.
+    rename(DIR_A, DIR_B, RenameFlags::EXCHANGE).unwrap();
+
+    // Each directory now holds the other's contents.
+    assert!(FileStats::try_from_path("/tmp/tlenix_exchange_dir_a/file_b").is_ok());
+    assert!(FileStats::try_from_path("/tmp/tlenix_exchange_dir_b/file_a").is_ok());
+    assert_err!(FileStats::try_from_path(FILE_A), Errno::Enoent);
+    assert_err!(FileStats::try_from_path(FILE_B), Errno::Enoent);
+
+    rm("/tmp/tlenix_exchange_dir_a/file_b").unwrap();
+    rm("/tmp/tlenix_exchange_dir_b/file_a").unwrap();
+    rmdir(DIR_A).unwrap();
+    rmdir(DIR_B).unwrap();
+}
+
+#[test_case]
+fn dup_shares_offset() {
+    const PATH: &str = "/tmp/tlenix_dup_test";
+
+    let _ = rm(PATH);
+    let file = OpenOptions::new()
+        .read_write()
+        .create(true)
+        .open(PATH)
+        .unwrap();
+
+    let duped = File::define(file.file_descriptor().dup().unwrap());
+
+    // The two descriptors share the file offset, so alternating writes append.
+    file.write(b"abc").unwrap();
+    duped.write(b"def").unwrap();
+    assert_eq!(file.cursor().unwrap(), Some(6));
+
+    // The duped descriptor is independently closeable; the original stays usable.
+    drop(duped);
+    file.set_cursor(0).unwrap();
+    assert_eq!(file.read_to_bytes().unwrap(), b"abcdef");
+
+    drop(file);
+    rm(PATH).unwrap();
+}
+
+#[test_case]
+fn dup2_redirects_descriptor() {
+    const PATH_A: &str = "/tmp/tlenix_dup2_test_a";
+    const PATH_B: &str = "/tmp/tlenix_dup2_test_b";
+
+    let _ = rm(PATH_A);
+    let _ = rm(PATH_B);
+    let a = OpenOptions::new()
+        .read_write()
+        .create(true)
+        .open(PATH_A)
+        .unwrap();
+    let b = OpenOptions::new()
+        .read_write()
+        .create(true)
+        .open(PATH_B)
+        .unwrap();
+
+    // Redirect `b`'s descriptor to `a`'s open file description, as shell redirection would.
+    a.file_descriptor().dup2(&b.file_descriptor()).unwrap();
+    b.write(b"redirected").unwrap();
+    drop(b);
+
+    a.set_cursor(0).unwrap();
+    assert_eq!(a.read_to_bytes().unwrap(), b"redirected");
+    assert_eq!(
+        OpenOptions::new().open(PATH_B).unwrap().read_to_bytes(),
+        Ok(Vec::new())
+    );
+
+    drop(a);
+    rm(PATH_A).unwrap();
+    rm(PATH_B).unwrap();
+}
+
+#[test_case]
+fn mkdir_p_creates_missing_parents() {
+    const ROOT: &str = "/tmp/tlenix_mkdir_p_test";
+    const NESTED: &str = "/tmp/tlenix_mkdir_p_test/a/b/c";
+
+    // Clean up any leftovers from previous runs.
+    let _ = rmdir(NESTED);
+    let _ = rmdir("/tmp/tlenix_mkdir_p_test/a/b");
+    let _ = rmdir("/tmp/tlenix_mkdir_p_test/a");
+    let _ = rmdir(ROOT);
+
+    mkdir_p(NESTED, FilePermissions::from(0o755)).unwrap();
+    assert_eq!(
+        FileStats::try_from_path(NESTED).unwrap().file_type,
+        Some(FileType::Directory)
+    );
+
+    // Idempotent: every component already exists.
+    mkdir_p(NESTED, FilePermissions::from(0o755)).unwrap();
+
+    rmdir(NESTED).unwrap();
+    rmdir("/tmp/tlenix_mkdir_p_test/a/b").unwrap();
+    rmdir("/tmp/tlenix_mkdir_p_test/a").unwrap();
+    rmdir(ROOT).unwrap();
+}
+
+#[test_case]
+fn mkdir_p_component_is_file() {
+    const ROOT: &str = "/tmp/tlenix_mkdir_p_file_test";
+    const FILE: &str = "/tmp/tlenix_mkdir_p_file_test/f";
+    const NESTED: &str = "/tmp/tlenix_mkdir_p_file_test/f/d";
+
+    let _ = rm(FILE);
+    let _ = rmdir(ROOT);
+
+    mkdir(ROOT, FilePermissions::from(0o755)).unwrap();
+    OpenOptions::new().create(true).open(FILE).unwrap();
+
+    assert_err!(
+        mkdir_p(NESTED, FilePermissions::from(0o755)),
+        Errno::Enotdir
+    );
+
+    rm(FILE).unwrap();
+    rmdir(ROOT).unwrap();
+}
+
+#[test_case]
+fn chown_noop_and_permissions() {
+    const PATH: &str = "/tmp/tlenix_chown_test";
+
+    let _ = rm(PATH);
+    OpenOptions::new().create(true).open(PATH).unwrap();
+
+    // `u32::MAX` for both ids leaves ownership untouched, so this succeeds unprivileged.
+    chown(PATH, u32::MAX, u32::MAX).unwrap();
+    lchown(PATH, u32::MAX, u32::MAX).unwrap();
+
+    if crate::process::effective_uid() == 0 {
+        // Privileged; actually hand the file to another owner.
+        chown(PATH, 12345, u32::MAX).unwrap();
+        assert_eq!(FileStats::try_from_path(PATH).unwrap().uid, Some(12345));
+    } else {
+        // Only privileged processes may change a file's owner.
+        assert_err!(chown(PATH, 0, u32::MAX), Errno::Eperm);
+    }
+
+    rm(PATH).unwrap();
+}
+
+#[test_case]
+fn same_file_identity() {
+    const PATH: &str = "/tmp/tlenix_same_file_identity";
+    const HARDLINK: &str = "/tmp/tlenix_same_file_hardlink";
+    const OTHER: &str = "/tmp/tlenix_same_file_other";
+
+    // Clean up any leftovers from previous runs.
+    let _ = rm(PATH);
+    let _ = rm(HARDLINK);
+    let _ = rm(OTHER);
+
+    OpenOptions::new().create(true).open(PATH).unwrap();
+    OpenOptions::new().create(true).open(OTHER).unwrap();
+    link(PATH, HARDLINK).unwrap();
+
+    // A path is the same file as itself and as its hard link, but not as a distinct file.
+    assert!(same_file(PATH, PATH).unwrap());
+    assert!(same_file(PATH, HARDLINK).unwrap());
+    assert!(!same_file(PATH, OTHER).unwrap());
+
+    rm(PATH).unwrap();
+    rm(HARDLINK).unwrap();
+    rm(OTHER).unwrap();
+}
+
+#[test_case]
+fn read_exact_fills_buffer() {
+    let mut buffer = [0; TEST_PATH_CONTENTS.len()];
+    let file = OpenOptions::new().open(TEST_PATH).unwrap();
+    file.read_exact(&mut buffer).unwrap();
+    assert_eq!(TEST_PATH_CONTENTS.as_bytes(), buffer);
+}
+
+#[test_case]
+fn read_exact_past_end() {
+    let mut buffer = [0; TEST_PATH_CONTENTS.len() + 1];
+    let file = OpenOptions::new().open(TEST_PATH).unwrap();
+    assert_err!(file.read_exact(&mut buffer), Errno::Eio);
+}
+
+#[test_case]
+fn read_at_keeps_cursor() {
+    const OFFSET: u64 = 5;
+    let expected = &TEST_PATH_CONTENTS.as_bytes()[5..15];
+
+    let mut buffer = [0; 10];
+    let file = OpenOptions::new().open(TEST_PATH).unwrap();
+    let bytes_read = file.read_at(&mut buffer, OFFSET).unwrap();
+
+    assert_eq!(bytes_read, buffer.len());
+    assert_eq!(buffer, expected);
+    assert_eq!(file.cursor().unwrap(), Some(0));
+}
+
+#[test_case]
+fn write_at_keeps_cursor() {
+    const PATH: &str = "/tmp/tlenix_write_at_test";
+    const ORIG_CONTENTS: &[u8] = b"0123456789";
+    const OVERWRITE: &[u8] = b"XXX";
+
+    let _ = rm(PATH);
+    let file = OpenOptions::new().read_write().create(true).open(PATH).unwrap();
+    file.write(ORIG_CONTENTS).unwrap();
+    file.set_cursor(0).unwrap();
+
+    let bytes_written = file.write_at(OVERWRITE, 4).unwrap();
+    assert_eq!(bytes_written, OVERWRITE.len());
+    assert_eq!(file.cursor().unwrap(), Some(0));
+
+    assert_eq!(file.read_to_bytes().unwrap(), b"0123XXX789");
+
+    drop(file);
+    rm(PATH).unwrap();
+}
+
 #[test_case]
 fn read_wo() {
     let mut buffer = [0; 1];
@@ -250,6 +703,58 @@ fn follow_symlink() {
     assert_eq!(buffer, TEST_PATH_CONTENTS.as_bytes());
 }
 
+#[test_case]
+fn create_symlink() {
+    const LINK_PATH: &str = "/tmp/tlenix_create_symlink_test";
+
+    // Clean up any leftovers from previous runs.
+    let _ = rm(LINK_PATH);
+
+    // The symlink target must be resolvable from the link's own directory.
+    let target = format!("{}/{TEST_PATH}", get_cwd().unwrap());
+    symlink(target.as_str(), LINK_PATH).unwrap();
+
+    // A second link at the same path must be refused.
+    assert_err!(symlink(target.as_str(), LINK_PATH), Errno::Eexist);
+
+    // Reading through the link gives the target's contents.
+    let contents = OpenOptions::new()
+        .open(LINK_PATH)
+        .unwrap()
+        .read_to_string()
+        .unwrap();
+    assert_eq!(contents, TEST_PATH_CONTENTS);
+
+    rm(LINK_PATH).unwrap();
+}
+
+#[test_case]
+fn read_link_target() {
+    assert_eq!(read_link(SYMLINK_PATH).unwrap(), "./test.txt");
+}
+
+#[test_case]
+fn read_link_long_target() {
+    // A target longer than the initial buffer forces the doubling path.
+    const LINK_PATH: &str = "/tmp/tlenix_read_link_long_test";
+    let mut target = String::from("/tmp");
+    while target.len() <= (1 << 8) {
+        target.push_str("/long_component");
+    }
+
+    let _ = rm(LINK_PATH);
+    symlink(target.as_str(), LINK_PATH).unwrap();
+
+    let read_target = read_link(LINK_PATH);
+    rm(LINK_PATH).unwrap();
+    assert_eq!(read_target.unwrap(), target);
+}
+
+#[test_case]
+fn read_link_not_symlink() {
+    assert_err!(read_link(TEST_PATH), Errno::Einval);
+}
+
 #[test_case]
 fn tempfile() {
     const EXPECTED: [u8; 17] = *b"Howdeedoodeethere";
@@ -364,6 +869,71 @@ fn cd_root() {
     assert_eq!(&cwd, new_path);
 }
 
+#[test_case]
+fn cwd_into_deep_path_one_allocation() {
+    const BASE: &str = "/tmp/tlenix_cwd_into_test";
+
+    let old_path = get_cwd().unwrap();
+
+    // Build a reasonably deep directory under /tmp.
+    let mut deep_path = String::from(BASE);
+    for _ in 0..10 {
+        deep_path.push_str("/subdirectory");
+    }
+    let mut partial = String::new();
+    for component in deep_path.split('/').skip(1) {
+        partial.push('/');
+        partial.push_str(component);
+        let _ = mkdir(partial.as_str(), FilePermissions::from(0o755));
+    }
+
+    change_dir(deep_path.as_str()).unwrap();
+    let mut buf = String::new();
+    cwd_into(&mut buf).unwrap();
+
+    // Clean up after yourself!
+    change_dir(old_path.as_str()).unwrap();
+    while deep_path.len() >= BASE.len() {
+        rmdir(deep_path.as_str()).unwrap();
+        let Some((parent, _)) = deep_path.rsplit_once('/') else {
+            break;
+        };
+        deep_path.truncate(parent.len());
+    }
+
+    assert_eq!(buf, {
+        let mut expected = String::from(BASE);
+        for _ in 0..10 {
+            expected.push_str("/subdirectory");
+        }
+        expected
+    });
+    // The PATH_MAX seed means the whole path fit in the buffer's single up-front allocation.
+    assert_eq!(buf.capacity(), 4096);
+}
+
+#[test_case]
+fn cd_tracked_updates_pwd_env() {
+    let old_path = get_cwd().unwrap();
+    let mut env: Vec<crate::EnvVar> = Vec::new();
+
+    change_dir_tracked("/tmp", &mut env).unwrap();
+
+    let pwd = env.iter().find(|ev| ev.key == "PWD").unwrap().value.clone();
+    let old_pwd = env
+        .iter()
+        .find(|ev| ev.key == "OLDPWD")
+        .unwrap()
+        .value
+        .clone();
+
+    // Clean up after yourself!
+    change_dir(old_path.as_str()).unwrap();
+
+    assert_eq!(pwd, "/tmp");
+    assert_eq!(old_pwd, old_path);
+}
+
 #[test_case]
 fn cd_dir_dne() {
     assert_err!(
@@ -449,6 +1019,73 @@ fn rm_wait_multiple_fds() {
     assert_err!(OpenOptions::new().open(PATH), Errno::Enoent);
 }
 
+#[test_case]
+fn set_len_shrink_and_extend() {
+    const PATH: &str = "/tmp/tlenix_set_len_test";
+    const CONTENTS: &[u8] = b"0123456789";
+
+    let _ = rm(PATH);
+    let file = OpenOptions::new()
+        .read_write()
+        .create(true)
+        .open(PATH)
+        .unwrap();
+    file.write(CONTENTS).unwrap();
+
+    // Shrinking drops the trailing bytes.
+    file.set_len(4).unwrap();
+    file.set_cursor(0).unwrap();
+    assert_eq!(file.read_to_bytes().unwrap(), b"0123");
+
+    // Extending zero-fills the new region.
+    file.set_len(6).unwrap();
+    file.set_cursor(0).unwrap();
+    assert_eq!(file.read_to_bytes().unwrap(), b"0123\0\0");
+
+    drop(file);
+    rm(PATH).unwrap();
+}
+
+#[test_case]
+fn set_len_read_only() {
+    let file = OpenOptions::new().open(TEST_PATH).unwrap();
+    assert_err!(file.set_len(0), Errno::Ebadf);
+}
+
+#[test_case]
+fn hard_link_keeps_file_alive() {
+    const ORIGINAL: &str = "/tmp/tlenix_hard_link_original";
+    const NEW_NAME: &str = "/tmp/tlenix_hard_link_new";
+    const CONTENTS: &[u8] = b"hard links share an inode";
+
+    // Clean up any leftovers from previous runs.
+    let _ = rm(ORIGINAL);
+    let _ = rm(NEW_NAME);
+
+    let file = OpenOptions::new()
+        .read_write()
+        .create(true)
+        .open(ORIGINAL)
+        .unwrap();
+    file.write(CONTENTS).unwrap();
+    drop(file);
+
+    link(ORIGINAL, NEW_NAME).unwrap();
+
+    // Removing the original leaves the file readable through the new name.
+    rm(ORIGINAL).unwrap();
+    let contents = OpenOptions::new()
+        .open(NEW_NAME)
+        .unwrap()
+        .read_to_bytes()
+        .unwrap();
+
+    rm(NEW_NAME).unwrap();
+
+    assert_eq!(contents, CONTENTS);
+    assert_err!(OpenOptions::new().open(NEW_NAME), Errno::Enoent);
+}
+
 #[test_case]
 fn rm_eisdir() {
     assert_err!(rm("/"), Errno::Eisdir);
@@ -590,6 +1227,161 @@ fn is_dir_empty_not_dir() {
     );
 }
 
+#[test_case]
+fn read_to_bytes_from_fifo() {
+    const PATH: &str = "/tmp/tlenix_fifo_read";
+    const MESSAGE: &[u8] = b"through the fifo";
+
+    let _ = rm(PATH);
+    mkfifo(PATH, FilePermissions::default()).unwrap();
+
+    // SAFETY: No pointers are involved; parent and child both proceed to safe code.
+    let child_pid = unsafe { crate::syscall_result!(crate::SyscallNum::Fork) }.unwrap();
+    if child_pid == 0 {
+        // Child: open the write end (blocking until the parent opens the read end) and send the
+        // message.
+        let fifo = OpenOptions::new().write_only().open(PATH).unwrap();
+        fifo.write(MESSAGE).unwrap();
+        crate::process::exit(crate::process::ExitStatus::ExitSuccess);
+    }
+
+    // A FIFO's `st_size` is meaningless, so the size hint must be skipped rather than reserving a
+    // garbage capacity.
+    let bytes = OpenOptions::new().open(PATH).unwrap().read_to_bytes();
+
+    // Clean up after yourself before testing!
+    crate::process::wait_state(child_pid, crate::process::WaitOptions::WEXITED).unwrap();
+    rm(PATH).unwrap();
+
+    let bytes = bytes.unwrap();
+    assert_eq!(bytes, MESSAGE);
+    assert!(bytes.capacity() <= 2 * crate::PAGE_SIZE);
+}
+
+#[test_case]
+fn mkfifo_existing_path_rejected() {
+    assert_err!(
+        mkfifo(TEST_PATH, FilePermissions::default()),
+        Errno::Eexist
+    );
+}
+
+#[test_case]
+fn dir_open_root() {
+    let dir = Dir::open("/").unwrap();
+    let entries = dir.entries().unwrap();
+
+    assert!(entries.iter().any(|ent| ent.name == "."));
+    assert!(entries.iter().any(|ent| ent.name == ".."));
+    assert!(!dir.is_empty().unwrap());
+    assert_eq!(entries.len(), dir.entries_iter().unwrap().count());
+}
+
+#[test_case]
+fn dir_open_file_not_dir() {
+    assert_err!(Dir::open(THIS_PATH), Errno::Enotdir);
+}
+
+#[test_case]
+fn access_checks() {
+    // Bare existence, and the readability the test itself relies on.
+    access(THIS_PATH, AccessMode::F_OK).unwrap();
+    access(THIS_PATH, AccessMode::R_OK).unwrap();
+
+    // Source files aren't executable.
+    assert_err!(access("src/fs/file.rs", AccessMode::X_OK), Errno::Eacces);
+
+    // Missing paths fail every check.
+    assert_err!(
+        access("/tmp/tlenix_access_nonexistent", AccessMode::F_OK),
+        Errno::Enoent
+    );
+}
+
+#[test_case]
+fn file_stats_sparse_detection() {
+    let mut stats = FileStats::try_from_path(THIS_PATH).unwrap();
+
+    // 8 blocks of 512 bytes backing a 1 MiB apparent size: sparse.
+    stats.blocks = Some(8);
+    stats.size = Some(1 << 20);
+    assert_eq!(stats.disk_usage(), Some(4096));
+    assert_eq!(stats.is_sparse(), Some(true));
+
+    // An allocation covering the whole apparent size: dense.
+    stats.size = Some(8 * 512);
+    assert_eq!(stats.is_sparse(), Some(false));
+
+    // No block count means the disk usage (and hence sparseness) is unknowable.
+    stats.blocks = None;
+    assert_eq!(stats.disk_usage(), None);
+    assert_eq!(stats.is_sparse(), None);
+}
+
+#[test_case]
+fn statfs_root() {
+    let stats = statfs("/").unwrap();
+
+    // Any real root filesystem has a sensible block size and a nonzero capacity.
+    assert!(stats.block_size > 0);
+    assert!(stats.total_blocks > 0);
+    assert!(stats.free_blocks >= stats.available_blocks);
+    assert!(stats.free_inodes <= stats.total_inodes);
+}
+
+#[test_case]
+fn fstatfs_matches_statfs() {
+    let file = OpenOptions::new().open(THIS_PATH).unwrap();
+    let by_fd = fstatfs(&file).unwrap();
+    let by_path = statfs(THIS_PATH).unwrap();
+
+    // Same filesystem, so the static fields agree (the free counts may drift between calls).
+    assert_eq!(by_fd.fs_type, by_path.fs_type);
+    assert_eq!(by_fd.block_size, by_path.block_size);
+    assert_eq!(by_fd.total_blocks, by_path.total_blocks);
+}
+
+#[test_case]
+fn statfs_nonexistent_path() {
+    assert_err!(statfs("/tmp/tlenix_statfs_nonexistent"), Errno::Enoent);
+}
+
+#[test_case]
+fn open_dir_accepts_only_dirs() {
+    let dir = open_dir("/tmp").unwrap();
+    assert!(dir.entries().unwrap().iter().any(|ent| ent.name == "."));
+
+    // Regular files are turned away before they're ever opened.
+    assert_err!(open_dir(THIS_PATH), Errno::Enotdir);
+}
+
+#[test_case]
+fn dir_open_at() {
+    const DIR: &str = "/tmp/tlenix_dir_open_at";
+    const FILE: &str = "my_file";
+
+    let mut file_path = DIR.to_string();
+    file_path.push('/');
+    file_path.push_str(FILE);
+
+    mkdir(DIR, FilePermissions::default() | FilePermissions::S_IXUSR).unwrap();
+    let file = OpenOptions::new()
+        .create(true)
+        .open(file_path.clone())
+        .unwrap();
+    file.write(b"relative!").unwrap();
+    drop(file);
+
+    // Open the entry by name alone, relative to the directory handle.
+    let read_result = Dir::open(DIR).and_then(|dir| dir.open_at(FILE)?.read_to_string());
+
+    // Clean up after yourself before testing!
+    rm(file_path).unwrap();
+    rmdir(DIR).unwrap();
+
+    assert_eq!(read_result.unwrap(), "relative!");
+}
+
 #[test_case]
 fn read_to_string() {
     let file_contents = OpenOptions::new()
@@ -853,8 +1645,80 @@ fn dir_stats_read() {
     rmdir(PATH).unwrap();
 }
 
+#[test_case]
+fn raw_mode_regular_file() {
+    let mut raw = FileStatsRaw::default();
+    raw.mask = (FileStatsMask::TYPE | FileStatsMask::MODE).bits();
+    raw.mode = 0o100_644;
+
+    let stats = FileStats::try_from(raw).unwrap();
+
+    assert_eq!(stats.file_type, Some(FileType::RegularFile));
+    assert_eq!(stats.mode, Some(FilePermissions::from(0o644)));
+    assert_eq!(stats.raw_mode(), Some(0o100_644));
+}
+
+#[test_case]
+fn raw_mode_missing_mode() {
+    let mut raw = FileStatsRaw::default();
+    raw.mask = FileStatsMask::TYPE.bits();
+    raw.mode = 0o100_644;
+
+    assert!(FileStats::try_from(raw).unwrap().raw_mode().is_none());
+}
+
 #[test_case]
 fn char_dev_stats_read() {
     const PATH: &str = "/dev/tty";
     assert_is_file_type(PATH, FileType::CharacterDevice);
 }
+
+#[test_case]
+fn file_lease_cycle() {
+    const PATH: &str = "/tmp/tlenix_lease_test.txt";
+
+    // Leases interact with other processes' opens and may be restricted by sysctl; only exercise
+    // them when running as root.
+    if crate::process::effective_uid() != 0 {
+        return;
+    }
+
+    let file = OpenOptions::new().create(true).open(PATH).unwrap();
+    assert_eq!(file.get_lease().unwrap(), LeaseKind::Unlock);
+
+    file.set_lease(LeaseKind::Read).unwrap();
+    assert_eq!(file.get_lease().unwrap(), LeaseKind::Read);
+
+    file.set_lease(LeaseKind::Unlock).unwrap();
+    assert_eq!(file.get_lease().unwrap(), LeaseKind::Unlock);
+
+    drop(file);
+    rm(PATH).unwrap();
+}
+
+#[test_case]
+fn dev_null_major_minor() {
+    // `/dev/null` is always char device 1:3 on Linux.
+    let stats = FileStats::try_from_path("/dev/null").unwrap();
+    assert_eq!(stats.file_type, Some(FileType::CharacterDevice));
+    assert_eq!(stats.major_device_id, Some(1));
+    assert_eq!(stats.minor_device_id, Some(3));
+}
+
+#[test_case]
+fn regular_file_no_device_ids() {
+    let stats = FileStats::try_from_path(TEST_PATH).unwrap();
+    assert!(stats.major_device_id.is_none());
+    assert!(stats.minor_device_id.is_none());
+}
+
+#[test_case]
+fn device_id_decode() {
+    // 1:3 (`/dev/null`) packs to 0x103 in the Linux `dev_t` layout.
+    assert_eq!(device_major(0x103), 1);
+    assert_eq!(device_minor(0x103), 3);
+    // Large IDs spill into the extended high bits.
+    let dev = (0xabcd_e000_u64 << 32) | (0xfff << 8) | (0xfedc_ba00_u64 << 12) | 0x99;
+    assert_eq!(device_major(dev), 0xabcd_efff);
+    assert_eq!(device_minor(dev), 0xfedc_ba99);
+}
@@ -4,16 +4,24 @@ mod dir_ents;
 mod file_descriptor;
 mod file_stats;
 mod file_type;
+mod lease_kind;
 mod lseekwhence;
 mod rename_flags;
+mod seek_from;
+mod sync_range_flags;
 
 // RE-EXPORTS
 
 pub(crate) use dir_ents::DirEntRawHeader;
 pub use dir_ents::{DirEnt, DirEntType};
 pub use file_descriptor::FileDescriptor;
-pub use file_stats::{FileAttributes, FileStats, FileStatsMask};
+pub use file_stats::{
+    FileAttributes, FileStats, FileStatsMask, FileTimestamp, device_major, device_minor,
+};
 pub(crate) use file_stats::{FileStatsRaw, statx_get_all};
 pub use file_type::FileType;
+pub use lease_kind::LeaseKind;
 pub use lseekwhence::LseekWhence;
 pub use rename_flags::RenameFlags;
+pub use seek_from::SeekFrom;
+pub use sync_range_flags::SyncRangeFlags;
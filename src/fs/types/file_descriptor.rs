@@ -1,5 +1,7 @@
 //! The [`FileDescriptor`] type.
 
+use crate::{Errno, SyscallNum, syscall_result};
+
 /// Process-unique identifier for a file or other input/output resource.
 /// [Wikipedia](https://en.wikipedia.org/wiki/File_descriptor)
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -10,6 +12,39 @@ impl FileDescriptor {
     pub(crate) const fn define(value: usize) -> Self {
         Self(value)
     }
+
+    /// Duplicates this [`FileDescriptor`], returning the lowest-numbered unused descriptor.
+    ///
+    /// The two descriptors refer to the same open file description, so they share the file offset
+    /// and status flags, but each is independently closeable.
+    ///
+    /// Wrapper around the [`dup`](https://www.man7.org/linux/man-pages/man2/dup.2.html) Linux
+    /// syscall.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying `dup` syscall.
+    pub fn dup(&self) -> Result<Self, Errno> {
+        // SAFETY: No pointers are involved, and errors are handled gracefully.
+        unsafe { syscall_result!(SyscallNum::Dup, self.0).map(Self::define) }
+    }
+
+    /// Duplicates this [`FileDescriptor`] into `newfd`, silently closing whatever `newfd`
+    /// previously referred to.
+    ///
+    /// Afterwards, `newfd` refers to the same open file description as this descriptor; see
+    /// [`Self::dup`]. This is the core primitive behind shell redirection.
+    ///
+    /// Wrapper around the [`dup2`](https://www.man7.org/linux/man-pages/man2/dup.2.html) Linux
+    /// syscall.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying `dup2` syscall.
+    pub fn dup2(&self, newfd: &Self) -> Result<Self, Errno> {
+        // SAFETY: No pointers are involved, and errors are handled gracefully.
+        unsafe { syscall_result!(SyscallNum::Dup2, self.0, newfd.0).map(Self::define) }
+    }
 }
 impl From<usize> for FileDescriptor {
     fn from(value: usize) -> Self {
@@ -9,6 +9,9 @@ use crate::{
 /// Bit mask for the mode bit field.
 const MODE_MASK: u32 = 0o7_777;
 
+/// The size (in bytes) of the blocks counted by [`FileStats::blocks`].
+const BLOCK_BYTES: u64 = 512;
+
 /// Constant for the `statx` system call. If this flag is set, then if the given path name is an
 /// empty string or `NULL`, then operate on the file referred to by the given file descriptor.
 const AT_EMPTY_PATH: i32 = 0x1000;
@@ -17,6 +20,22 @@ const AT_EMPTY_PATH: i32 = 0x1000;
 /// for file syncing.
 const AT_STATX_SYNC_AS_STAT: i32 = 0;
 
+/// Extracts the major device ID from a combined `dev_t` value using the Linux bit layout
+/// (`MMMM_Mmmm_mmmM_MMmm`).
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub const fn device_major(dev: u64) -> u32 {
+    (((dev >> 32) & 0xffff_f000) | ((dev >> 8) & 0xfff)) as u32
+}
+
+/// Extracts the minor device ID from a combined `dev_t` value using the Linux bit layout
+/// (`MMMM_Mmmm_mmmM_MMmm`).
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub const fn device_minor(dev: u64) -> u32 {
+    (((dev >> 12) & 0xffff_ff00) | (dev & 0xff)) as u32
+}
+
 /// Wrapper around the [`statx`](https://man7.org/linux/man-pages/man2/statx.2.html) Linux system
 /// call. Gets all the available fields supported by [`FileStatsMask`].
 ///
@@ -87,6 +106,10 @@ pub struct FileStats {
     pub major_device_id: Option<u32>,
     /// Minor ID of the device containing this file.
     pub minor_device_id: Option<u32>,
+    /// Major ID of the device containing the filesystem where this file resides.
+    pub fs_device_major: u32,
+    /// Minor ID of the device containing the filesystem where this file resides.
+    pub fs_device_minor: u32,
     /// Mount ID of the mount containing the file.
     pub mount_id: Option<u64>,
     /// Alignment (in bytes) required for user memory buffers for direct I/O on this file, or 0 if
@@ -122,6 +145,43 @@ impl FileStats {
         statx_get_all(AT_FDCWD, path)
     }
 
+    /// Reconstructs the full `st_mode`-style value of the file: the [`FileType`] bits OR-ed
+    /// together with the [`FilePermissions`] bits.
+    ///
+    /// Useful when a complete mode must be handed to a system call such as `mknod` or `chmod`.
+    ///
+    /// Returns [`None`] if either the file type or the mode was not retrieved by the underlying
+    /// `statx` call.
+    #[must_use]
+    pub fn raw_mode(&self) -> Option<u32> {
+        match (self.file_type, self.mode) {
+            // OK to allow here. The permission bits are masked to `MODE_MASK`, far below the
+            // point of truncation.
+            #[allow(clippy::cast_possible_truncation)]
+            (Some(file_type), Some(mode)) => Some(file_type as u32 | mode.bits() as u32),
+            _ => None,
+        }
+    }
+
+    /// The actual disk usage of the file, in bytes (512 bytes per allocated block).
+    ///
+    /// For sparse files this is smaller than [`FileStats::size`], the apparent size.
+    ///
+    /// Returns [`None`] if the block count was not retrieved by the underlying `statx` call.
+    #[must_use]
+    pub fn disk_usage(&self) -> Option<u64> {
+        Some(self.blocks? * BLOCK_BYTES)
+    }
+
+    /// Whether the file is sparse, i.e. occupies less space on disk than its apparent size.
+    ///
+    /// Returns [`None`] if either the block count or the size was not retrieved by the underlying
+    /// `statx` call.
+    #[must_use]
+    pub fn is_sparse(&self) -> Option<bool> {
+        Some(self.disk_usage()? < self.size?)
+    }
+
     fn masked_stat<T>(stat: T, flag: FileStatsMask, mask: FileStatsMask) -> Option<T> {
         if mask.intersects(flag) {
             Some(stat)
@@ -160,16 +220,17 @@ impl TryFrom<FileStatsRaw> for FileStats {
             Self::masked_stat(value.ctime, FileStatsMask::CTIME, file_stats_mask);
         let modification_time =
             Self::masked_stat(value.mtime, FileStatsMask::MTIME, file_stats_mask);
-        let major_device_id = Self::masked_stat(
-            value.rdev_major,
-            FileStatsMask::MNT_ID_UNIQUE,
-            file_stats_mask,
-        );
-        let minor_device_id = Self::masked_stat(
-            value.rdev_minor,
-            FileStatsMask::MNT_ID_UNIQUE,
-            file_stats_mask,
+        // `statx` has no mask bit for the `rdev` fields; they're filled whenever the file is a
+        // device node.
+        let is_device = matches!(
+            file_type,
+            Some(FileType::BlockDevice | FileType::CharacterDevice)
         );
+        let major_device_id = is_device.then_some(value.rdev_major);
+        let minor_device_id = is_device.then_some(value.rdev_minor);
+        // Like the `rdev` fields, the `dev` fields have no mask bit; `statx` always fills them.
+        let fs_device_major = value.dev_major;
+        let fs_device_minor = value.dev_minor;
         let mount_id = Self::masked_stat(value.mnt_id, FileStatsMask::MNT_ID, file_stats_mask);
         let direct_io_memory_alignment = Self::masked_stat(
             value.dio_mem_align,
@@ -220,6 +281,8 @@ impl TryFrom<FileStatsRaw> for FileStats {
             modification_time,
             major_device_id,
             minor_device_id,
+            fs_device_major,
+            fs_device_minor,
             mount_id,
             direct_io_memory_alignment,
             direct_io_offset_alignment,
@@ -273,6 +336,10 @@ pub struct FileStatsRaw {
     pub rdev_major: u32,
     /// If this file is a device, this field contains the minor ID of the device.
     pub rdev_minor: u32,
+    /// Major ID of the device containing the filesystem where this file resides.
+    pub dev_major: u32,
+    /// Minor ID of the device containing the filesystem where this file resides.
+    pub dev_minor: u32,
     /// Mount ID.
     pub mnt_id: u64,
     /// Direct I/O memory restriction alignment.
@@ -290,7 +357,7 @@ pub struct FileStatsRaw {
     /// File offset alignment for direct I/O reads.
     pub dio_read_offset_align: u32,
     /// Padding.
-    _pad3: [u64; 12],
+    _pad3: [u64; 11],
 }
 
 /// Macro to impl the different fns retrieving the different [`FileAttributes`] values.
@@ -429,13 +496,29 @@ bitflags::bitflags! {
 /// A file timestamp. Directly corresponds to the
 /// [`statx_timestamp`](https://man7.org/linux/man-pages/man2/statx.2.html) type in `libc`.
 #[repr(C)]
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub struct FileTimestamp {
     /// Seconds since the epoch (UNIX time)
     pub sec: i64,
     /// Nanoseconds since [`FileStatsTimestampRaw::sec`]
     pub nsec: u32,
 }
+impl FileTimestamp {
+    /// When passed to [`crate::fs::set_times`], sets the timestamp to the current time.
+    ///
+    /// Corresponds to `UTIME_NOW` in C.
+    pub const NOW: Self = Self {
+        sec: 0,
+        nsec: (1 << 30) - 1,
+    };
+    /// When passed to [`crate::fs::set_times`], leaves the timestamp unchanged.
+    ///
+    /// Corresponds to `UTIME_OMIT` in C.
+    pub const OMIT: Self = Self {
+        sec: 0,
+        nsec: (1 << 30) - 2,
+    };
+}
 
 /// Information about a given [`crate::fs::File`]. Corresponds to the
 /// [`stat`](https://man7.org/linux/man-pages/man3/stat.3type.html) struct in `libc`.
@@ -0,0 +1,26 @@
+//! The [`LeaseKind`] type.
+
+use num_enum::TryFromPrimitive;
+
+use crate::SyscallArg;
+
+/// All possible kinds of file lease which can be set on or read back from a
+/// [`crate::fs::File`] via the `F_SETLEASE`/`F_GETLEASE`
+/// [`fcntl`](https://man7.org/linux/man-pages/man2/fcntl.2.html) commands.
+#[repr(usize)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, TryFromPrimitive)]
+pub enum LeaseKind {
+    /// A read lease (`F_RDLCK`). The holder is notified when another process opens the file for
+    /// writing or truncates it.
+    Read = 0,
+    /// A write lease (`F_WRLCK`). The holder is notified when another process opens the file for
+    /// reading or writing, or truncates it.
+    Write = 1,
+    /// No lease (`F_UNLCK`). Setting this kind removes the lease currently held on the file.
+    Unlock = 2,
+}
+impl From<LeaseKind> for SyscallArg {
+    fn from(value: LeaseKind) -> Self {
+        Self::from(value as usize)
+    }
+}
@@ -0,0 +1,16 @@
+//! The [`SeekFrom`] type.
+
+/// A position within a [`File`](crate::fs::File) to seek to, as accepted by
+/// [`File::seek`](crate::fs::File::seek).
+///
+/// Matches the ergonomics of the
+/// [standard library's `SeekFrom`](https://doc.rust-lang.org/std/io/enum.SeekFrom.html).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SeekFrom {
+    /// Sets the cursor to the provided number of bytes.
+    Start(u64),
+    /// Sets the cursor to its current location plus the provided number of bytes.
+    Current(i64),
+    /// Sets the cursor to the size of the file plus the provided number of bytes.
+    End(i64),
+}
@@ -0,0 +1,19 @@
+//! Module for the [`SyncRangeFlags`] type.
+
+bitflags::bitflags! {
+    /// The options which can be passed to the [`crate::fs::File::sync_range`] function.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct SyncRangeFlags: u32 {
+        /// Wait for any already-in-flight writeback of the range to finish before starting.
+        const WAIT_BEFORE = 1;
+        /// Start writeback of any dirty pages in the range.
+        const WRITE = 2;
+        /// Wait for the writeback started by this call to finish.
+        const WAIT_AFTER = 4;
+    }
+}
+impl Default for SyncRangeFlags {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
@@ -0,0 +1,257 @@
+//! Extended attribute operations, both path-based and on already-open [`File`]s.
+//!
+//! Extended attributes are `name:value` pairs associated with files, commonly namespaced as
+//! `user.*`, `system.*`, `security.*`, or `trusted.*`. See
+//! [`xattr(7)`](https://www.man7.org/linux/man-pages/man7/xattr.7.html) for more information.
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{Errno, NixString, SyscallNum, fs::File, syscall_result};
+
+bitflags::bitflags! {
+    /// Flags controlling how [`set_xattr`] (and [`File::set_xattr`]) treats an existing
+    /// attribute. By default, the attribute is created if missing and replaced if present.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+    pub struct XattrFlags: i32 {
+        /// Perform a pure create; fail with [`Errno::Eexist`] if the attribute already exists.
+        const CREATE = 0x1;
+        /// Perform a pure replace; fail with [`Errno::Enodata`] if the attribute doesn't exist.
+        const REPLACE = 0x2;
+    }
+}
+
+/// Gets the value of the extended attribute `name` of the file at the given path.
+///
+/// Wrapper around the
+/// [`getxattr`](https://www.man7.org/linux/man-pages/man2/getxattr.2.html) Linux system call.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying call to `getxattr`.
+/// Notably, [`Errno::Enodata`] is returned if the attribute doesn't exist and
+/// [`Errno::Eopnotsupp`] is returned if the filesystem doesn't support extended attributes.
+pub fn get_xattr<NA: Into<NixString>, NB: Into<NixString>>(
+    path: NA,
+    name: NB,
+) -> Result<Vec<u8>, Errno> {
+    let ns_path: NixString = path.into();
+    let ns_name: NixString = name.into();
+    // SAFETY: The path and name pointers point to valid null-terminated strings, and the buffer
+    // pointer (when non-null) points to a live allocation of the given size.
+    grow_to_fit(|buf, size| unsafe {
+        syscall_result!(
+            SyscallNum::Getxattr,
+            ns_path.as_ptr(),
+            ns_name.as_ptr(),
+            buf,
+            size
+        )
+    })
+}
+
+/// Sets the extended attribute `name` of the file at the given path to `value`.
+///
+/// Wrapper around the
+/// [`setxattr`](https://www.man7.org/linux/man-pages/man2/setxattr.2.html) Linux system call.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying call to `setxattr`.
+/// Notably, [`Errno::Eopnotsupp`] is returned if the filesystem doesn't support extended
+/// attributes, and [`XattrFlags`] add their own failure modes.
+pub fn set_xattr<NA: Into<NixString>, NB: Into<NixString>>(
+    path: NA,
+    name: NB,
+    value: &[u8],
+    flags: XattrFlags,
+) -> Result<(), Errno> {
+    let ns_path: NixString = path.into();
+    let ns_name: NixString = name.into();
+    // SAFETY: The path and name pointers point to valid null-terminated strings, and the value
+    // pointer is valid for the given length. All raw pointers are dropped right after the
+    // syscall.
+    unsafe {
+        syscall_result!(
+            SyscallNum::Setxattr,
+            ns_path.as_ptr(),
+            ns_name.as_ptr(),
+            value.as_ptr(),
+            value.len(),
+            flags.bits()
+        )?;
+    }
+    Ok(())
+}
+
+/// Lists the names of all extended attributes of the file at the given path.
+///
+/// Wrapper around the
+/// [`listxattr`](https://www.man7.org/linux/man-pages/man2/listxattr.2.html) Linux system call.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying call to `listxattr`.
+/// Notably, [`Errno::Eopnotsupp`] is returned if the filesystem doesn't support extended
+/// attributes.
+pub fn list_xattr<NS: Into<NixString>>(path: NS) -> Result<Vec<String>, Errno> {
+    let ns_path: NixString = path.into();
+    // SAFETY: The path pointer points to a valid null-terminated string, and the buffer pointer
+    // (when non-null) points to a live allocation of the given size.
+    let list = grow_to_fit(|buf, size| unsafe {
+        syscall_result!(SyscallNum::Listxattr, ns_path.as_ptr(), buf, size)
+    })?;
+    Ok(split_xattr_list(&list))
+}
+
+/// Removes the extended attribute `name` of the file at the given path.
+///
+/// Wrapper around the
+/// [`removexattr`](https://www.man7.org/linux/man-pages/man2/removexattr.2.html) Linux system
+/// call.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying call to `removexattr`.
+/// Notably, [`Errno::Enodata`] is returned if the attribute doesn't exist.
+pub fn remove_xattr<NA: Into<NixString>, NB: Into<NixString>>(
+    path: NA,
+    name: NB,
+) -> Result<(), Errno> {
+    let ns_path: NixString = path.into();
+    let ns_name: NixString = name.into();
+    // SAFETY: The path and name pointers point to valid null-terminated strings and are dropped
+    // right after the syscall.
+    unsafe {
+        syscall_result!(SyscallNum::Removexattr, ns_path.as_ptr(), ns_name.as_ptr())?;
+    }
+    Ok(())
+}
+
+impl File {
+    /// Gets the value of this file's extended attribute `name`.
+    ///
+    /// Wrapper around the
+    /// [`fgetxattr`](https://www.man7.org/linux/man-pages/man2/fgetxattr.2.html) Linux system
+    /// call. See [`get_xattr`] for the path-based variant.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying call to `fgetxattr`.
+    pub fn get_xattr<NS: Into<NixString>>(&self, name: NS) -> Result<Vec<u8>, Errno> {
+        let ns_name: NixString = name.into();
+        // SAFETY: The name pointer points to a valid null-terminated string, and the buffer
+        // pointer (when non-null) points to a live allocation of the given size.
+        grow_to_fit(|buf, size| unsafe {
+            syscall_result!(
+                SyscallNum::Fgetxattr,
+                self.file_descriptor(),
+                ns_name.as_ptr(),
+                buf,
+                size
+            )
+        })
+    }
+
+    /// Sets this file's extended attribute `name` to `value`.
+    ///
+    /// Wrapper around the
+    /// [`fsetxattr`](https://www.man7.org/linux/man-pages/man2/fsetxattr.2.html) Linux system
+    /// call. See [`set_xattr`] for the path-based variant.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying call to `fsetxattr`.
+    pub fn set_xattr<NS: Into<NixString>>(
+        &self,
+        name: NS,
+        value: &[u8],
+        flags: XattrFlags,
+    ) -> Result<(), Errno> {
+        let ns_name: NixString = name.into();
+        // SAFETY: The name pointer points to a valid null-terminated string, and the value
+        // pointer is valid for the given length. All raw pointers are dropped right after the
+        // syscall.
+        unsafe {
+            syscall_result!(
+                SyscallNum::Fsetxattr,
+                self.file_descriptor(),
+                ns_name.as_ptr(),
+                value.as_ptr(),
+                value.len(),
+                flags.bits()
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Lists the names of all of this file's extended attributes.
+    ///
+    /// Wrapper around the
+    /// [`flistxattr`](https://www.man7.org/linux/man-pages/man2/flistxattr.2.html) Linux system
+    /// call. See [`list_xattr`] for the path-based variant.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying call to `flistxattr`.
+    pub fn list_xattr(&self) -> Result<Vec<String>, Errno> {
+        // SAFETY: The buffer pointer (when non-null) points to a live allocation of the given
+        // size.
+        let list = grow_to_fit(|buf, size| unsafe {
+            syscall_result!(SyscallNum::Flistxattr, self.file_descriptor(), buf, size)
+        })?;
+        Ok(split_xattr_list(&list))
+    }
+
+    /// Removes this file's extended attribute `name`.
+    ///
+    /// Wrapper around the
+    /// [`fremovexattr`](https://www.man7.org/linux/man-pages/man2/fremovexattr.2.html) Linux
+    /// system call. See [`remove_xattr`] for the path-based variant.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying call to `fremovexattr`.
+    pub fn remove_xattr<NS: Into<NixString>>(&self, name: NS) -> Result<(), Errno> {
+        let ns_name: NixString = name.into();
+        // SAFETY: The name pointer points to a valid null-terminated string and is dropped right
+        // after the syscall.
+        unsafe {
+            syscall_result!(
+                SyscallNum::Fremovexattr,
+                self.file_descriptor(),
+                ns_name.as_ptr()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Calls `fetch` once with a null pointer to learn the required size, then again with a buffer of
+/// that size, retrying if the data grows in between.
+///
+/// This is the standard two-call pattern of the `getxattr`/`listxattr` family.
+fn grow_to_fit(
+    mut fetch: impl FnMut(*mut u8, usize) -> Result<usize, Errno>,
+) -> Result<Vec<u8>, Errno> {
+    loop {
+        let size = fetch(core::ptr::null_mut(), 0)?;
+        let mut buffer = alloc::vec![0_u8; size];
+        match fetch(buffer.as_mut_ptr(), size) {
+            Ok(len) => {
+                buffer.truncate(len);
+                return Ok(buffer);
+            }
+            // The data grew between the two calls; re-query the size.
+            Err(Errno::Erange) => {}
+            Err(errno) => return Err(errno),
+        }
+    }
+}
+
+/// Splits the null-terminated name list returned by the `listxattr` family into owned strings.
+fn split_xattr_list(list: &[u8]) -> Vec<String> {
+    list.split(|&byte| byte == 0)
+        .filter(|name| !name.is_empty())
+        .map(|name| String::from_utf8_lossy(name).into_owned())
+        .collect()
+}
@@ -4,6 +4,324 @@ use core::fmt::Display;
 
 use num_enum::TryFromPrimitive;
 
+use crate::{
+    Errno, SyscallNum,
+    fs::{File, FileDescriptor, OpenFlags},
+    syscall_result,
+};
+
+pub mod unix;
+
+/// Creates a pipe, returning its read end and write end as [`File`]s.
+///
+/// Bytes written to the write end can be read back from the read end in order. Once every copy of
+/// the write end is closed, reads from the read end return `Ok(0)` (EOF). Both descriptors are
+/// opened with [`OpenFlags::O_CLOEXEC`] so they don't leak into exec'd programs; use
+/// [`FileDescriptor::dup2`](crate::fs::FileDescriptor::dup2) to pass an end on deliberately.
+///
+/// Internally uses the [`pipe2`](https://www.man7.org/linux/man-pages/man2/pipe.2.html) Linux
+/// syscall.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying `pipe2` syscall.
+pub fn pipe() -> Result<(File, File), Errno> {
+    let mut fds: [i32; 2] = [0; 2];
+
+    // SAFETY: The array matches the layout `pipe2` expects. The mutable raw pointer to `fds` is
+    // dropped right after the syscall.
+    unsafe {
+        syscall_result!(
+            SyscallNum::Pipe2,
+            fds.as_mut_ptr(),
+            OpenFlags::O_CLOEXEC.bits()
+        )?;
+    }
+
+    // The kernel only hands back valid, non-negative descriptors on success.
+    #[allow(clippy::cast_sign_loss)]
+    Ok((
+        File::define(FileDescriptor::define(fds[0] as usize)),
+        File::define(FileDescriptor::define(fds[1] as usize)),
+    ))
+}
+
+/// `sa_flags` bit telling the kernel that `sa_restorer` holds the signal return trampoline.
+const SA_RESTORER: u64 = 0x0400_0000;
+/// The size (in bytes) of the kernel signal mask (`sigset_t`).
+const SIGSET_SIZE: usize = 8;
+/// `sa_handler` value for the default disposition (`SIG_DFL`).
+const SIG_DFL: usize = 0;
+/// `sa_handler` value for ignoring the signal (`SIG_IGN`).
+const SIG_IGN: usize = 1;
+
+// The kernel doesn't know how to return from a signal handler by itself; it jumps to the
+// registered `sa_restorer`, which must invoke `rt_sigreturn` (syscall 15 on x86_64) to restore the
+// interrupted context.
+core::arch::global_asm! {
+    ".global __sigreturn_trampoline",
+    "__sigreturn_trampoline:",
+    "mov rax, 15",
+    "syscall",
+}
+unsafe extern "C" {
+    /// The signal return trampoline registered as `sa_restorer` by [`sigaction`].
+    fn __sigreturn_trampoline();
+}
+
+/// How a signal should be handled. Used with [`sigaction`].
+#[derive(Copy, Clone, Debug)]
+pub enum SigHandler {
+    /// The default disposition of the signal (`SIG_DFL`).
+    Default,
+    /// Ignore the signal (`SIG_IGN`).
+    Ignore,
+    /// Invoke the given function, passing the number of the delivered signal.
+    Handler(extern "C" fn(i32)),
+}
+
+/// The signal action passed to the kernel. Directly corresponds to the `sigaction` struct used by
+/// the [`rt_sigaction`](https://www.man7.org/linux/man-pages/man2/sigaction.2.html) Linux syscall.
+#[repr(C)]
+pub(crate) struct SigactionRaw {
+    /// Handler address, or `SIG_DFL`/`SIG_IGN`.
+    handler: usize,
+    /// Behaviour flags.
+    flags: u64,
+    /// Address of the signal return trampoline.
+    restorer: usize,
+    /// Signals blocked during handler execution.
+    mask: u64,
+}
+
+/// Sets how the calling process handles the given signal.
+///
+/// Wrapper around the [`rt_sigaction`](https://www.man7.org/linux/man-pages/man2/sigaction.2.html)
+/// Linux syscall. This lets e.g. a shell ignore `SIGINT` at its prompt; the disposition is reset
+/// to [`SigHandler::Default`] in exec'd children, so they still receive the signal normally.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying `rt_sigaction` syscall.
+/// Notably, [`Errno::Einval`] is returned when trying to change the action of `SIGKILL` or
+/// `SIGSTOP`.
+pub fn sigaction(signo: Signo, handler: SigHandler) -> Result<(), Errno> {
+    let handler = match handler {
+        SigHandler::Default => SIG_DFL,
+        SigHandler::Ignore => SIG_IGN,
+        SigHandler::Handler(function) => function as usize,
+    };
+    let action = SigactionRaw {
+        handler,
+        flags: SA_RESTORER,
+        restorer: __sigreturn_trampoline as unsafe extern "C" fn() as usize,
+        mask: 0,
+    };
+
+    // SAFETY: The `SigactionRaw` type matches the layout expected by `rt_sigaction`, and the raw
+    // pointer to `action` is dropped right after the syscall. The registered restorer performs the
+    // `rt_sigreturn` the kernel requires on handler exit.
+    unsafe {
+        syscall_result!(
+            SyscallNum::RtSigaction,
+            signo as i32,
+            &raw const action,
+            core::ptr::null::<u8>(),
+            SIGSET_SIZE
+        )?;
+    }
+    Ok(())
+}
+
+/// How [`sigprocmask`] combines the given [`SignalSet`] with the current signal mask.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(i32)]
+pub enum SigmaskHow {
+    /// Add the signals in the set to the current mask (`SIG_BLOCK`).
+    Block = 0,
+    /// Remove the signals in the set from the current mask (`SIG_UNBLOCK`).
+    Unblock = 1,
+    /// Replace the current mask with the set (`SIG_SETMASK`).
+    SetMask = 2,
+}
+
+/// A set of [`Signo`]s, represented as the kernel `sigset_t` bitmask.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Hash)]
+#[repr(transparent)]
+pub struct SignalSet(u64);
+impl SignalSet {
+    /// Creates a new, empty [`SignalSet`].
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Adds the given [`Signo`] to the set.
+    pub const fn insert(&mut self, signo: Signo) {
+        self.0 |= Self::bit(signo);
+    }
+
+    /// Removes the given [`Signo`] from the set.
+    pub const fn remove(&mut self, signo: Signo) {
+        self.0 &= !Self::bit(signo);
+    }
+
+    /// Returns `true` if and only if the given [`Signo`] is in the set.
+    #[must_use]
+    pub const fn contains(&self, signo: Signo) -> bool {
+        (self.0 & Self::bit(signo)) != 0
+    }
+
+    /// The `sigset_t` bit corresponding to the given [`Signo`]. Signal 1 is the lowest bit.
+    const fn bit(signo: Signo) -> u64 {
+        1 << (signo as i32 - 1)
+    }
+}
+impl FromIterator<Signo> for SignalSet {
+    fn from_iter<T: IntoIterator<Item = Signo>>(iter: T) -> Self {
+        let mut set = Self::empty();
+        for signo in iter {
+            set.insert(signo);
+        }
+        set
+    }
+}
+
+/// Changes which signals are blocked from delivery to the calling process, returning the previous
+/// signal mask.
+///
+/// The given [`SignalSet`] is combined with the current mask according to the given
+/// [`SigmaskHow`]. Blocked signals stay pending until unblocked. [`Signo::SigKill`] and
+/// [`Signo::SigStop`] cannot be blocked; attempts to do so are silently ignored by the kernel.
+///
+/// Wrapper around the
+/// [`rt_sigprocmask`](https://www.man7.org/linux/man-pages/man2/sigprocmask.2.html) Linux syscall.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying `rt_sigprocmask` syscall.
+pub fn sigprocmask(how: SigmaskHow, set: &SignalSet) -> Result<SignalSet, Errno> {
+    let mut old_set = SignalSet::empty();
+
+    // SAFETY: The `SignalSet` type is a transparent wrapper around the `sigset_t` bitmask the
+    // kernel expects, and both raw pointers are dropped right after the syscall.
+    unsafe {
+        syscall_result!(
+            SyscallNum::RtSigprocmask,
+            how as i32,
+            &raw const *set,
+            &raw mut old_set,
+            SIGSET_SIZE
+        )?;
+    }
+    Ok(old_set)
+}
+
+/// Sends the given signal to the process with the given PID.
+///
+/// Wrapper around the [`kill`](https://www.man7.org/linux/man-pages/man2/kill.2.html) Linux
+/// syscall.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying `kill` syscall. Notably,
+/// [`Errno::Esrch`] is returned if no process with the given PID exists, and [`Errno::Eperm`] if
+/// the caller lacks permission to signal the target.
+pub fn kill(pid: i32, signo: Signo) -> Result<(), Errno> {
+    // SAFETY: No pointers are involved. The signal number is restricted to valid values by the
+    // `Signo` type.
+    unsafe {
+        syscall_result!(SyscallNum::Kill, pid, signo as i32)?;
+    }
+    Ok(())
+}
+
+/// A single region of memory, described by its starting address and length in bytes. Directly
+/// corresponds to the `iovec` struct used by vectored I/O syscalls.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IoVec {
+    /// The starting address of the region.
+    pub base: usize,
+    /// The length of the region in bytes.
+    pub len: usize,
+}
+
+/// Reads the given remote memory regions of the process with the given PID into the given local
+/// buffer, returning the number of bytes read.
+///
+/// This lets e.g. a privileged inspector read another process's memory without ptrace-stopping it.
+/// The caller must either share an effective UID with the target or hold `CAP_SYS_PTRACE`.
+///
+/// Wrapper around the
+/// [`process_vm_readv`](https://www.man7.org/linux/man-pages/man2/process_vm_readv.2.html) Linux
+/// syscall.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying `process_vm_readv` syscall.
+/// Notably, [`Errno::Esrch`] is returned if no process with the given PID exists, and
+/// [`Errno::Eperm`] if the caller lacks permission to read the target's memory.
+pub fn process_vm_read(pid: i32, remote: &[IoVec], local: &mut [u8]) -> Result<usize, Errno> {
+    let local_iov = IoVec {
+        base: local.as_mut_ptr() as usize,
+        len: local.len(),
+    };
+
+    // OK to lose sign here; the kernel reinterprets the bytes as a PID anyway.
+    #[allow(clippy::cast_sign_loss)]
+    // SAFETY: The `IoVec` type matches the `iovec` layout the kernel expects, the local region
+    // covers exactly the given buffer, and both raw pointers are dropped right after the syscall.
+    unsafe {
+        syscall_result!(
+            SyscallNum::ProcessVmReadv,
+            pid as usize,
+            &raw const local_iov as usize,
+            1_usize,
+            remote.as_ptr() as usize,
+            remote.len(),
+            0_usize
+        )
+    }
+}
+
+/// Writes the given local buffer into the given remote memory regions of the process with the
+/// given PID, returning the number of bytes written.
+///
+/// The caller must either share an effective UID with the target or hold `CAP_SYS_PTRACE`.
+///
+/// Wrapper around the
+/// [`process_vm_writev`](https://www.man7.org/linux/man-pages/man2/process_vm_readv.2.html) Linux
+/// syscall.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying `process_vm_writev` syscall.
+/// Notably, [`Errno::Esrch`] is returned if no process with the given PID exists, and
+/// [`Errno::Eperm`] if the caller lacks permission to write the target's memory.
+pub fn process_vm_write(pid: i32, remote: &[IoVec], local: &[u8]) -> Result<usize, Errno> {
+    let local_iov = IoVec {
+        base: local.as_ptr() as usize,
+        len: local.len(),
+    };
+
+    // OK to lose sign here; the kernel reinterprets the bytes as a PID anyway.
+    #[allow(clippy::cast_sign_loss)]
+    // SAFETY: The `IoVec` type matches the `iovec` layout the kernel expects, the local region
+    // covers exactly the given buffer, and both raw pointers are dropped right after the syscall.
+    unsafe {
+        syscall_result!(
+            SyscallNum::ProcessVmWritev,
+            pid as usize,
+            &raw const local_iov as usize,
+            1_usize,
+            remote.as_ptr() as usize,
+            remote.len(),
+            0_usize
+        )
+    }
+}
+
 /// The raw signal info obtained directly from the kernel.
 ///
 /// See [`sigaction(2)`](https://www.man7.org/linux/man-pages/man2/sigaction.2.html) for more
@@ -141,3 +459,108 @@ impl Display for Signo {
         write!(f, "{s}")
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn pipe_round_trip() {
+        const MESSAGE: &[u8] = b"through the pipe";
+
+        let (read_end, write_end) = pipe().unwrap();
+
+        write_end.write(MESSAGE).unwrap();
+        let mut buffer = [0; MESSAGE.len()];
+        read_end.read_exact(&mut buffer).unwrap();
+        assert_eq!(buffer, MESSAGE);
+
+        // Closing the write end makes the read end report EOF.
+        drop(write_end);
+        assert_eq!(read_end.read(&mut buffer), Ok(0));
+    }
+
+    #[test_case]
+    fn sigaction_ignore_and_default() {
+        // Ignore SIGCHLD, then restore the default disposition. Both syscalls must succeed.
+        sigaction(Signo::SigChld, SigHandler::Ignore).unwrap();
+        sigaction(Signo::SigChld, SigHandler::Default).unwrap();
+    }
+
+    #[test_case]
+    fn sigaction_kill_rejected() {
+        // The disposition of SIGKILL can't be changed.
+        crate::assert_err!(sigaction(Signo::SigKill, SigHandler::Ignore), Errno::Einval);
+    }
+
+    #[test_case]
+    fn sigprocmask_block_and_restore() {
+        let mut set = SignalSet::empty();
+        set.insert(Signo::SigUsr1);
+        assert!(set.contains(Signo::SigUsr1));
+        assert!(!set.contains(Signo::SigUsr2));
+
+        // Block SIGUSR1 and remember the original mask.
+        let original = sigprocmask(SigmaskHow::Block, &set).unwrap();
+
+        // Reading back the mask (a no-op block of the empty set) must show SIGUSR1 blocked.
+        let current = sigprocmask(SigmaskHow::Block, &SignalSet::empty()).unwrap();
+        assert!(current.contains(Signo::SigUsr1));
+
+        // Restore the original mask and confirm SIGUSR1 is no longer blocked.
+        sigprocmask(SigmaskHow::SetMask, &original).unwrap();
+        let restored = sigprocmask(SigmaskHow::Block, &SignalSet::empty()).unwrap();
+        assert_eq!(restored, original);
+    }
+
+    #[test_case]
+    fn kill_nonexistent_pid() {
+        // PIDs are capped well below `i32::MAX`, so no process can have this one.
+        crate::assert_err!(kill(i32::MAX, Signo::SigKill), Errno::Esrch);
+    }
+
+    #[test_case]
+    fn process_vm_read_child_value() {
+        const VALUE: u64 = 0x1DEA_F00D_CAFE_D00D;
+
+        // The child's copy-on-write address space keeps this value at the same address.
+        let value = VALUE;
+
+        // SAFETY: No pointers are involved; parent and child both proceed to safe code.
+        let child_pid = unsafe { syscall_result!(SyscallNum::Fork) }.unwrap();
+        if child_pid == 0 {
+            // Child: stay alive long enough for the parent to peek at our memory.
+            crate::thread::sleep(&core::time::Duration::from_millis(500)).unwrap();
+            crate::process::exit(crate::process::ExitStatus::ExitSuccess);
+        }
+
+        let remote = [IoVec {
+            base: &raw const value as usize,
+            len: size_of::<u64>(),
+        }];
+        let mut buffer = [0_u8; size_of::<u64>()];
+
+        // OK to allow; PIDs are far below the truncation/wrapping point.
+        #[allow(clippy::cast_possible_truncation)]
+        #[allow(clippy::cast_possible_wrap)]
+        let read_result = process_vm_read(child_pid as i32, &remote, &mut buffer);
+
+        // Clean up after yourself before testing!
+        kill(child_pid.try_into().unwrap(), Signo::SigKill).unwrap();
+        crate::process::wait_state(child_pid, crate::process::WaitOptions::WEXITED).unwrap();
+
+        assert_eq!(read_result.unwrap(), size_of::<u64>());
+        assert_eq!(u64::from_ne_bytes(buffer), VALUE);
+    }
+
+    #[test_case]
+    fn process_vm_read_nonexistent_pid() {
+        let remote = [IoVec { base: 0, len: 1 }];
+        let mut buffer = [0_u8; 1];
+        crate::assert_err!(
+            process_vm_read(i32::MAX, &remote, &mut buffer),
+            Errno::Esrch
+        );
+    }
+}
@@ -0,0 +1,280 @@
+//! Unix-domain stream sockets.
+//!
+//! Supports both regular pathname sockets and Linux's abstract socket namespace (a leading null
+//! byte in the address). Abstract sockets leave no socket file behind and disappear automatically
+//! when the last descriptor is closed, making them a good fit for local services. See
+//! [`unix(7)`](https://www.man7.org/linux/man-pages/man7/unix.7.html) for more information.
+
+use crate::{
+    Errno, SyscallNum,
+    fs::{File, FileDescriptor, OpenFlags},
+    syscall_result,
+};
+
+/// Address family for Unix-domain sockets (`AF_UNIX`).
+const AF_UNIX: u16 = 1;
+/// Socket type for connection-oriented byte streams (`SOCK_STREAM`).
+const SOCK_STREAM: usize = 1;
+/// The maximum length (in bytes) of a `sockaddr_un` path.
+const SUN_PATH_LEN: usize = 108;
+/// The size (in bytes) of the `sun_family` field preceding the path.
+const SUN_FAMILY_SIZE: usize = size_of::<u16>();
+/// The maximum number of pending connections queued on a listener.
+const LISTEN_BACKLOG: usize = 16;
+
+/// A Unix-domain socket address. Directly corresponds to the `sockaddr_un` struct used by the
+/// [`bind`](https://www.man7.org/linux/man-pages/man2/bind.2.html) and
+/// [`connect`](https://www.man7.org/linux/man-pages/man2/connect.2.html) Linux syscalls.
+#[repr(C)]
+struct SockaddrUn {
+    /// The address family; always [`AF_UNIX`].
+    family: u16,
+    /// The socket path. A leading null byte selects the abstract namespace.
+    path: [u8; SUN_PATH_LEN],
+}
+impl SockaddrUn {
+    /// Builds a pathname address, returning it along with the address length to pass to the
+    /// kernel.
+    fn pathname(path: &str) -> Result<(Self, usize), Errno> {
+        // Leave room for the trailing null terminator.
+        if path.is_empty() || path.len() >= SUN_PATH_LEN {
+            return Err(Errno::Einval);
+        }
+        let mut addr = Self {
+            family: AF_UNIX,
+            path: [0; SUN_PATH_LEN],
+        };
+        addr.path[..path.len()].copy_from_slice(path.as_bytes());
+        Ok((addr, SUN_FAMILY_SIZE + path.len() + 1))
+    }
+
+    /// Builds an abstract-namespace address (a leading null byte followed by the name, with no
+    /// terminator), returning it along with the address length to pass to the kernel.
+    fn abstract_name(name: &str) -> Result<(Self, usize), Errno> {
+        // The leading null byte takes one slot of the path.
+        if name.is_empty() || name.len() >= SUN_PATH_LEN {
+            return Err(Errno::Einval);
+        }
+        let mut addr = Self {
+            family: AF_UNIX,
+            path: [0; SUN_PATH_LEN],
+        };
+        addr.path[1..=name.len()].copy_from_slice(name.as_bytes());
+        Ok((addr, SUN_FAMILY_SIZE + 1 + name.len()))
+    }
+}
+
+/// Creates a new, unbound Unix-domain stream socket.
+fn stream_socket() -> Result<File, Errno> {
+    // `SOCK_CLOEXEC` shares its value with `O_CLOEXEC`; the descriptor shouldn't leak into exec'd
+    // programs.
+    let socket_type = SOCK_STREAM | OpenFlags::O_CLOEXEC.bits();
+
+    // SAFETY: No pointers are involved; the arguments are statically-chosen and correct.
+    let fd = unsafe {
+        syscall_result!(SyscallNum::Socket, usize::from(AF_UNIX), socket_type, 0_usize)?
+    };
+    Ok(File::define(FileDescriptor::define(fd)))
+}
+
+/// A Unix-domain stream socket listening for incoming connections.
+#[derive(Debug)]
+pub struct UnixListener(File);
+impl UnixListener {
+    /// Creates a listener bound to the given filesystem path.
+    ///
+    /// The socket file is created on bind but _not_ removed on drop; use [`crate::fs::rm`] to
+    /// clean it up. For a socket that cleans up after itself, use [`Self::bind_abstract`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`Errno::Einval`] if the path is empty or too long for a
+    /// `sockaddr_un`, and propagates any [`Errno`]s returned by the underlying `socket`, `bind`,
+    /// and `listen` syscalls. Notably, [`Errno::Eaddrinuse`] is returned if the path already
+    /// exists.
+    pub fn bind(path: &str) -> Result<Self, Errno> {
+        let (addr, addr_len) = SockaddrUn::pathname(path)?;
+        Self::bind_addr(&addr, addr_len)
+    }
+
+    /// Creates a listener bound to the given name in the abstract socket namespace.
+    ///
+    /// No socket file is created; the name vanishes automatically once the listener and all
+    /// connected sockets are closed.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`Errno::Einval`] if the name is empty or too long for a
+    /// `sockaddr_un`, and propagates any [`Errno`]s returned by the underlying `socket`, `bind`,
+    /// and `listen` syscalls. Notably, [`Errno::Eaddrinuse`] is returned if the name is already
+    /// bound.
+    pub fn bind_abstract(name: &str) -> Result<Self, Errno> {
+        let (addr, addr_len) = SockaddrUn::abstract_name(name)?;
+        Self::bind_addr(&addr, addr_len)
+    }
+
+    /// Shared implementation of [`Self::bind`] and [`Self::bind_abstract`].
+    fn bind_addr(addr: &SockaddrUn, addr_len: usize) -> Result<Self, Errno> {
+        let socket = stream_socket()?;
+
+        // SAFETY: The `SockaddrUn` type matches the layout expected by the kernel, the given
+        // length never exceeds its size, and the raw pointer is dropped right after the syscalls.
+        unsafe {
+            syscall_result!(
+                SyscallNum::Bind,
+                socket.file_descriptor(),
+                &raw const *addr as usize,
+                addr_len
+            )?;
+            syscall_result!(SyscallNum::Listen, socket.file_descriptor(), LISTEN_BACKLOG)?;
+        }
+        Ok(Self(socket))
+    }
+
+    /// Accepts an incoming connection, blocking until one arrives.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying `accept` syscall.
+    pub fn accept(&self) -> Result<UnixStream, Errno> {
+        // The peer address isn't needed, so null pointers are passed for it.
+        // SAFETY: No live pointers are involved; the arguments are statically-chosen and correct.
+        let fd = unsafe {
+            syscall_result!(
+                SyscallNum::Accept,
+                self.0.file_descriptor(),
+                0_usize,
+                0_usize
+            )?
+        };
+        Ok(UnixStream(File::define(FileDescriptor::define(fd))))
+    }
+}
+
+/// One end of a connected Unix-domain stream socket.
+#[derive(Debug)]
+pub struct UnixStream(File);
+impl UnixStream {
+    /// Connects to the listener bound to the given filesystem path.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`Errno::Einval`] if the path is empty or too long for a
+    /// `sockaddr_un`, and propagates any [`Errno`]s returned by the underlying `socket` and
+    /// `connect` syscalls. Notably, [`Errno::Econnrefused`] is returned if nobody is listening
+    /// there.
+    pub fn connect(path: &str) -> Result<Self, Errno> {
+        let (addr, addr_len) = SockaddrUn::pathname(path)?;
+        Self::connect_addr(&addr, addr_len)
+    }
+
+    /// Connects to the listener bound to the given name in the abstract socket namespace.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`Errno::Einval`] if the name is empty or too long for a
+    /// `sockaddr_un`, and propagates any [`Errno`]s returned by the underlying `socket` and
+    /// `connect` syscalls. Notably, [`Errno::Econnrefused`] is returned if nobody is listening
+    /// there.
+    pub fn connect_abstract(name: &str) -> Result<Self, Errno> {
+        let (addr, addr_len) = SockaddrUn::abstract_name(name)?;
+        Self::connect_addr(&addr, addr_len)
+    }
+
+    /// Shared implementation of [`Self::connect`] and [`Self::connect_abstract`].
+    fn connect_addr(addr: &SockaddrUn, addr_len: usize) -> Result<Self, Errno> {
+        let socket = stream_socket()?;
+
+        // SAFETY: The `SockaddrUn` type matches the layout expected by the kernel, the given
+        // length never exceeds its size, and the raw pointer is dropped right after the syscall.
+        unsafe {
+            syscall_result!(
+                SyscallNum::Connect,
+                socket.file_descriptor(),
+                &raw const *addr as usize,
+                addr_len
+            )?;
+        }
+        Ok(Self(socket))
+    }
+
+    /// Reads bytes from the socket into the provided buffer, returning the number of bytes read.
+    /// `Ok(0)` means the peer closed its end.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying [`File::read`].
+    pub fn read(&self, buffer: &mut [u8]) -> Result<usize, Errno> {
+        self.0.read(buffer)
+    }
+
+    /// Reads exactly enough bytes from the socket to fill the provided buffer.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying [`File::read_exact`].
+    pub fn read_exact(&self, buffer: &mut [u8]) -> Result<(), Errno> {
+        self.0.read_exact(buffer)
+    }
+
+    /// Writes the bytes of the provided buffer to the socket, returning the number of bytes
+    /// written.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying [`File::write`].
+    pub fn write(&self, buffer: &[u8]) -> Result<usize, Errno> {
+        self.0.write(buffer)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn abstract_round_trip() {
+        const NAME: &str = "tlenix_abstract_socket_test";
+        const MESSAGE: &[u8] = b"over the abstract socket";
+
+        let listener = UnixListener::bind_abstract(NAME).unwrap();
+
+        // The client runs on its own thread so connecting can overlap the blocking accept.
+        let client = crate::thread::spawn(|| {
+            let stream = UnixStream::connect_abstract(NAME).unwrap();
+            stream.write(MESSAGE).unwrap();
+        })
+        .unwrap();
+
+        let stream = listener.accept().unwrap();
+        let mut buffer = [0; MESSAGE.len()];
+        stream.read_exact(&mut buffer).unwrap();
+        assert_eq!(buffer, MESSAGE);
+
+        client.join().unwrap();
+    }
+
+    #[test_case]
+    fn abstract_name_in_use() {
+        const NAME: &str = "tlenix_abstract_in_use_test";
+
+        let _listener = UnixListener::bind_abstract(NAME).unwrap();
+        crate::assert_err!(UnixListener::bind_abstract(NAME), Errno::Eaddrinuse);
+    }
+
+    #[test_case]
+    fn connect_nobody_listening() {
+        crate::assert_err!(
+            UnixStream::connect_abstract("tlenix_abstract_nobody_test"),
+            Errno::Econnrefused
+        );
+    }
+
+    #[test_case]
+    fn oversized_name_rejected() {
+        let name = alloc::string::String::from_utf8(alloc::vec![b'a'; SUN_PATH_LEN]).unwrap();
+        crate::assert_err!(UnixListener::bind_abstract(&name), Errno::Einval);
+        crate::assert_err!(UnixStream::connect_abstract(""), Errno::Einval);
+    }
+}
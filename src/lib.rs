@@ -27,6 +27,7 @@ mod args;
 mod console;
 pub mod fs;
 pub mod ipc;
+pub mod memory;
 mod nix_bytes;
 mod nix_str;
 mod print;
@@ -34,6 +35,7 @@ pub mod process;
 pub mod streams;
 mod syscall;
 pub mod system;
+pub mod term;
 mod test_framework;
 pub mod thread;
 
@@ -41,12 +43,12 @@ pub mod thread;
 pub(crate) mod test_utils;
 
 // RE-EXPORTS
-pub use args::{EnvVar, parse_argv_envp};
+pub use args::{EnvVar, parse_argv_envp, split_fields};
 pub use console::Console;
 pub use nix_bytes::NixBytes;
 pub use nix_str::NixString;
 pub use print::{__format, __print_err, __print_str};
-pub use syscall::{Errno, SyscallArg, SyscallNum};
+pub use syscall::{Errno, SliceArg, SyscallArg, SyscallNum};
 pub(crate) use syscall::{syscall, syscall_result};
 pub use test_framework::custom_test_runner;
 
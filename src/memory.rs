@@ -0,0 +1,82 @@
+//! Failable manual memory allocation.
+//!
+//! The global allocator works out of a fixed arena, so large allocations through the usual
+//! [`alloc`] containers can fail — and when they do, the program aborts. For large, failable
+//! allocations (e.g. reading a file of unknown size into memory), [`try_alloc`] reports exhaustion
+//! as an [`Errno::Enomem`] instead, letting the caller degrade gracefully.
+
+use core::alloc::Layout;
+
+use crate::Errno;
+
+/// Allocates memory for the given [`Layout`] from the global allocator, returning
+/// [`Errno::Enomem`] instead of aborting if the arena is exhausted.
+///
+/// The returned pointer must eventually be released via [`free`] with the same [`Layout`].
+///
+/// # Errors
+///
+/// This function returns [`Errno::Einval`] if the layout is zero-sized, and [`Errno::Enomem`] if
+/// the global allocator can't satisfy the request.
+pub fn try_alloc(layout: Layout) -> Result<*mut u8, Errno> {
+    if layout.size() == 0 {
+        // Zero-sized allocation through the global allocator is undefined behaviour.
+        return Err(Errno::Einval);
+    }
+
+    // SAFETY: The layout is non-zero-sized, checked above.
+    let ptr = unsafe { alloc::alloc::alloc(layout) };
+    if ptr.is_null() {
+        Err(Errno::Enomem)
+    } else {
+        Ok(ptr)
+    }
+}
+
+/// Releases memory previously allocated via [`try_alloc`].
+///
+/// # Safety
+///
+/// `ptr` must have been returned by [`try_alloc`] with the same `layout`, and must not be used (or
+/// freed) again afterwards.
+pub unsafe fn free(ptr: *mut u8, layout: Layout) {
+    // SAFETY: The caller guarantees `ptr` and `layout` came from `try_alloc`.
+    unsafe {
+        alloc::alloc::dealloc(ptr, layout);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::assert_err;
+
+    #[test_case]
+    fn try_alloc_round_trips() {
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        let ptr = try_alloc(layout).unwrap();
+
+        // The memory is really ours: write and read it back.
+        // SAFETY: The pointer covers 64 freshly-allocated bytes.
+        unsafe {
+            ptr.write_bytes(0xAB, layout.size());
+            assert_eq!(*ptr, 0xAB);
+            free(ptr, layout);
+        }
+    }
+
+    #[test_case]
+    fn try_alloc_oversized_enomem() {
+        // Twice the entire arena can never fit; this must fail cleanly instead of aborting.
+        let layout = Layout::from_size_align(crate::allocator::ARENA_SIZE * 2, 8).unwrap();
+        assert_err!(try_alloc(layout), Errno::Enomem);
+    }
+
+    #[test_case]
+    fn try_alloc_zero_sized_rejected() {
+        let layout = Layout::from_size_align(0, 1).unwrap();
+        assert_err!(try_alloc(layout), Errno::Einval);
+    }
+}
@@ -26,9 +26,30 @@ impl NixBytes {
         self.0.as_ptr()
     }
 
+    /// Returns the byte slice of the [`NixBytes`], _including_ the trailing null terminator.
+    ///
+    /// Useful for FFI-style callers that need the full buffer, e.g. to pass a length alongside
+    /// the pointer.
+    #[must_use]
+    #[inline]
+    pub fn as_bytes_with_nul(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns the byte slice of the [`NixBytes`], _without_ the trailing null terminator.
+    #[must_use]
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0[..self.0.len() - 1]
+    }
+
     /// Returns the byte slice of the [`NixBytes`].
     #[must_use]
     #[inline]
+    #[deprecated(
+        since = "0.1.0-beta.4",
+        note = "ambiguous about the null terminator; use `as_bytes_with_nul` or `as_bytes`"
+    )]
     pub fn bytes(&self) -> &[u8] {
         &self.0
     }
@@ -93,12 +114,12 @@ impl TryFrom<NixBytes> for String {
 impl<'a> TryFrom<&'a NixBytes> for &'a str {
     type Error = core::str::Utf8Error;
     fn try_from(value: &'a NixBytes) -> Result<Self, Self::Error> {
-        str::from_utf8(value.bytes())
+        str::from_utf8(value.as_bytes_with_nul())
     }
 }
 impl<'a> From<&'a NixBytes> for &'a [u8] {
     fn from(value: &'a NixBytes) -> Self {
-        value.bytes()
+        value.as_bytes_with_nul()
     }
 }
 
@@ -14,7 +14,7 @@ fn nbytes_from_string() {
     let string = TEST_STR.to_string();
     let expected_bytes = TEST_NULL_TERM.as_bytes();
     let nbytes: NixBytes = string.into();
-    assert_eq!(nbytes.bytes(), expected_bytes);
+    assert_eq!(nbytes.as_bytes_with_nul(), expected_bytes);
 }
 
 #[test_case]
@@ -22,44 +22,44 @@ fn nbytes_from_byte_vec() {
     let bytes = Vec::from(TEST_STR.as_bytes());
     let expected_bytes = TEST_NULL_TERM.as_bytes();
     let nbytes: NixBytes = bytes.into();
-    assert_eq!(nbytes.bytes(), expected_bytes);
+    assert_eq!(nbytes.as_bytes_with_nul(), expected_bytes);
 }
 
 #[test_case]
 fn nbytes_from_byte_slice() {
     let nbytes = NixBytes::from(&TEST_BYTES[..]);
-    assert_eq!(nbytes.bytes(), TEST_NULL_TERM.as_bytes());
+    assert_eq!(nbytes.as_bytes_with_nul(), TEST_NULL_TERM.as_bytes());
 }
 
 #[test_case]
 fn nbytes_already_null_term() {
     let nbytes = NixBytes::from(TEST_NULL_TERM);
-    assert_eq!(nbytes.bytes(), TEST_NULL_TERM.as_bytes());
+    assert_eq!(nbytes.as_bytes_with_nul(), TEST_NULL_TERM.as_bytes());
 }
 
 #[test_case]
 fn null_nbytes() {
     let expected_bytes = *b"\0";
     let nbytes = NixBytes::null();
-    assert_eq!(nbytes.bytes(), expected_bytes);
+    assert_eq!(nbytes.as_bytes_with_nul(), expected_bytes);
 }
 
 #[test_case]
 fn nbytes_bytes() {
     let nbytes = NixBytes::from(TEST_STR);
-    assert_eq!(nbytes.bytes(), TEST_NULL_TERM.as_bytes());
+    assert_eq!(nbytes.as_bytes_with_nul(), TEST_NULL_TERM.as_bytes());
 }
 
 #[test_case]
 fn nbytes_to_str() {
     let nbytes = NixBytes::from(TEST_STR);
-    assert_eq!(str::from_utf8(nbytes.bytes()).unwrap(), TEST_NULL_TERM);
+    assert_eq!(str::from_utf8(nbytes.as_bytes_with_nul()).unwrap(), TEST_NULL_TERM);
 }
 
 #[test_case]
 fn nbytes_from_empty() {
     let nbytes = NixBytes::from(TEST_EMPTY);
-    assert_eq!(nbytes.bytes(), [b'\0']);
+    assert_eq!(nbytes.as_bytes_with_nul(), [b'\0']);
 }
 
 #[test_case]
@@ -67,13 +67,13 @@ fn nbytes_non_utf8() {
     let nbytes = NixBytes::from(&TEST_NON_UTF8[..]);
     let mut expected: Vec<u8> = Vec::from(TEST_NON_UTF8);
     expected.push(NULL_BYTE);
-    assert_eq!(nbytes.bytes(), expected);
+    assert_eq!(nbytes.as_bytes_with_nul(), expected);
 }
 
 #[test_case]
 fn nbytes_non_utf8_str_fails() {
     let nbytes = NixBytes::from(&TEST_NON_UTF8[..]);
-    assert_err!(str::from_utf8(nbytes.bytes()), core::str::Utf8Error { .. });
+    assert_err!(str::from_utf8(nbytes.as_bytes_with_nul()), core::str::Utf8Error { .. });
 }
 
 #[test_case]
@@ -89,3 +89,14 @@ fn null_nbytes_as_string() {
     let test_string = String::try_from(nbytes).unwrap();
     assert_eq!(&test_string, "\0");
 }
+
+#[test_case]
+fn nbytes_with_and_without_nul() {
+    let nbytes = NixBytes::from(TEST_STR);
+    assert_eq!(nbytes.as_bytes(), TEST_STR.as_bytes());
+    assert_eq!(nbytes.as_bytes_with_nul(), TEST_NULL_TERM.as_bytes());
+    assert_eq!(
+        nbytes.as_bytes_with_nul().len(),
+        nbytes.as_bytes().len() + 1
+    );
+}
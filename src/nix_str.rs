@@ -27,9 +27,30 @@ impl NixString {
         self.0.as_ptr()
     }
 
+    /// Returns the byte slice of the [`NixString`], _including_ the trailing null terminator.
+    ///
+    /// Useful for FFI-style callers that need the full buffer, e.g. to pass a length alongside
+    /// the pointer.
+    #[must_use]
+    #[inline]
+    pub fn as_bytes_with_nul(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns the byte slice of the [`NixString`], _without_ the trailing null terminator.
+    #[must_use]
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0[..self.0.len() - 1]
+    }
+
     /// Returns the byte slice of the [`NixString`].
     #[must_use]
     #[inline]
+    #[deprecated(
+        since = "0.1.0-beta.4",
+        note = "ambiguous about the null terminator; use `as_bytes_with_nul` or `as_bytes`"
+    )]
     pub fn bytes(&self) -> &[u8] {
         &self.0
     }
@@ -123,7 +144,7 @@ impl<'a> From<&'a NixString> for &'a str {
 }
 impl<'a> From<&'a NixString> for &'a [u8] {
     fn from(value: &'a NixString) -> Self {
-        value.bytes()
+        value.as_bytes_with_nul()
     }
 }
 
@@ -14,7 +14,7 @@ fn nstring_from_string() {
     let string = TEST_STR.to_string();
     let expected_bytes = TEST_NULL_TERM.as_bytes();
     let my_nstring: NixString = string.into();
-    assert_eq!(my_nstring.bytes(), expected_bytes);
+    assert_eq!(my_nstring.as_bytes_with_nul(), expected_bytes);
 }
 
 #[test_case]
@@ -22,44 +22,44 @@ fn nstring_from_byte_vec() {
     let bytes = Vec::from(TEST_STR.as_bytes());
     let expected_bytes = TEST_NULL_TERM.as_bytes();
     let my_nstring: NixString = bytes.try_into().unwrap();
-    assert_eq!(my_nstring.bytes(), expected_bytes);
+    assert_eq!(my_nstring.as_bytes_with_nul(), expected_bytes);
 }
 
 #[test_case]
 fn nstring_from_byte_slice() {
     let my_nstring = NixString::try_from(&TEST_BYTES[..]).unwrap();
-    assert_eq!(my_nstring.bytes(), TEST_NULL_TERM.as_bytes());
+    assert_eq!(my_nstring.as_bytes_with_nul(), TEST_NULL_TERM.as_bytes());
 }
 
 #[test_case]
 fn nstring_already_null_term() {
     let my_nstring = NixString::from(TEST_NULL_TERM);
-    assert_eq!(my_nstring.bytes(), TEST_NULL_TERM.as_bytes());
+    assert_eq!(my_nstring.as_bytes_with_nul(), TEST_NULL_TERM.as_bytes());
 }
 
 #[test_case]
 fn null_nstring() {
     let expected_bytes = *b"\0";
     let my_nstring = NixString::null();
-    assert_eq!(my_nstring.bytes(), expected_bytes);
+    assert_eq!(my_nstring.as_bytes_with_nul(), expected_bytes);
 }
 
 #[test_case]
 fn nstring_bytes() {
     let my_nstring = NixString::from(TEST_STR);
-    assert_eq!(my_nstring.bytes(), TEST_NULL_TERM.as_bytes());
+    assert_eq!(my_nstring.as_bytes_with_nul(), TEST_NULL_TERM.as_bytes());
 }
 
 #[test_case]
 fn nstring_to_str() {
     let my_nstring = NixString::from(TEST_STR);
-    assert_eq!(str::from_utf8(my_nstring.bytes()).unwrap(), TEST_NULL_TERM);
+    assert_eq!(str::from_utf8(my_nstring.as_bytes_with_nul()).unwrap(), TEST_NULL_TERM);
 }
 
 #[test_case]
 fn nstring_from_empty() {
     let my_nstring = NixString::from(TEST_EMPTY);
-    assert_eq!(my_nstring.bytes(), [b'\0']);
+    assert_eq!(my_nstring.as_bytes_with_nul(), [b'\0']);
 }
 
 #[test_case]
@@ -96,7 +96,18 @@ fn null_nstring_as_string() {
 fn nstring_trim_extra_null() {
     const TEST_BYTES: [u8; 3] = [0x4d, NULL_BYTE, NULL_BYTE];
     let nstring = NixString::try_from(&TEST_BYTES[..]).unwrap();
-    assert_eq!(nstring.bytes(), &TEST_BYTES[..TEST_BYTES.len() - 1]);
+    assert_eq!(nstring.as_bytes_with_nul(), &TEST_BYTES[..TEST_BYTES.len() - 1]);
     let result: String = nstring.into();
     assert_eq!(result, "M".to_string());
 }
+
+#[test_case]
+fn nstring_with_and_without_nul() {
+    let nstring = NixString::from(TEST_STR);
+    assert_eq!(nstring.as_bytes(), TEST_STR.as_bytes());
+    assert_eq!(nstring.as_bytes_with_nul(), TEST_NULL_TERM.as_bytes());
+    assert_eq!(
+        nstring.as_bytes_with_nul().len(),
+        nstring.as_bytes().len() + 1
+    );
+}
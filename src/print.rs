@@ -9,14 +9,23 @@ use crate::streams::{STDERR, STDOUT};
 #[doc(hidden)]
 pub fn __print_str(args: Arguments<'_>) {
     #[allow(clippy::unwrap_used)]
-    STDOUT.lock().write_fmt(args).unwrap();
+    write_batched(&mut *STDOUT.lock(), args).unwrap();
 }
 
 /// For [`eprint`] and [`eprintln`] use only.
 #[doc(hidden)]
 pub fn __print_err(args: Arguments<'_>) {
     #[allow(clippy::unwrap_used)]
-    STDERR.lock().write_fmt(args).unwrap();
+    write_batched(&mut *STDERR.lock(), args).unwrap();
+}
+
+/// Formats the given arguments into a single buffer, then hands that buffer to the sink as one
+/// write.
+///
+/// [`Write::write_fmt`] calls [`Write::write_str`] once per format fragment, which would issue
+/// multiple `write` syscalls per print and cause visible tearing under concurrent writers.
+fn write_batched<W: Write>(sink: &mut W, args: Arguments<'_>) -> core::fmt::Result {
+    sink.write_str(&__format(args))
 }
 
 /// For [`format`] use only.
@@ -1,5 +1,23 @@
+#![allow(clippy::unwrap_used)]
+
 use alloc::string::{String, ToString};
-use core::fmt::Display;
+use core::fmt::{Display, Write};
+
+use super::write_batched;
+
+/// A [`Write`] sink which records how many times it receives a string fragment.
+#[derive(Debug, Default)]
+struct CountingSink {
+    writes: usize,
+    buf: String,
+}
+impl Write for CountingSink {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.writes += 1;
+        self.buf.push_str(s);
+        Ok(())
+    }
+}
 
 // Used for debug print testing.
 #[derive(Debug)]
@@ -85,6 +103,25 @@ fn display_impl() {
     print!("{}", MyTestStruct::example());
 }
 
+#[test_case]
+fn write_fmt_fragments() {
+    // Sanity check: without batching, a multi-part format reaches the sink as several fragments.
+    let mut sink = CountingSink::default();
+    sink.write_fmt(format_args!("{} + {} = {}", 1, 1, 2))
+        .unwrap();
+    assert!(sink.writes > 1);
+    assert_eq!(sink.buf, "1 + 1 = 2");
+}
+
+#[test_case]
+fn write_batched_single_write() {
+    // The print path batches a multi-part format into exactly one underlying write.
+    let mut sink = CountingSink::default();
+    write_batched(&mut sink, format_args!("{} + {} = {}", 1, 1, 2)).unwrap();
+    assert_eq!(sink.writes, 1);
+    assert_eq!(sink.buf, "1 + 1 = 2");
+}
+
 #[test_case]
 fn format_empty() {
     assert_eq!(format!(""), "");
@@ -3,11 +3,29 @@
 use alloc::vec::Vec;
 use core::ptr;
 
-use crate::{Errno, NixString, SyscallNum, ipc::SigInfoRaw, syscall, syscall_result};
+use crate::{
+    Errno, NixString, SyscallNum,
+    fs::{File, FileDescriptor},
+    ipc::SigInfoRaw,
+    syscall, syscall_result,
+};
 
 mod types;
 
-pub use types::{ExitStatus, WaitIdType, WaitInfo, WaitOptions};
+pub use types::{
+    CloseRangeFlags, ExitStatus, Personality, RUsage, WaitIdType, WaitInfo, WaitOptions,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The lowest file descriptor which isn't one of the standard streams.
+const FIRST_NON_STANDARD_FD: u32 = 3;
+
+/// The standard input file descriptor.
+const STDIN_FD: usize = 0;
+/// The standard output file descriptor.
+const STDOUT_FD: usize = 1;
 
 /// Arguments formatted for `execve`.
 struct ExecArgs {
@@ -107,6 +125,9 @@ pub fn execve<NA: Into<NixString> + Clone, NB: Into<NixString> + Clone>(
 ///
 /// `envp` is a list of environment variables, conventionally of the form `key=value`.
 ///
+/// Any non-standard file descriptors (i.e. everything besides stdin/stdout/stderr) are closed in
+/// the child before the new program starts, so inherited descriptors can't leak into it.
+///
 /// # Errors
 ///
 /// This function returns [`Errno::Enoent`] if `argv` is empty.
@@ -129,33 +150,188 @@ pub fn execute_process<NA: Into<NixString> + Clone, NB: Into<NixString> + Clone>
     #[allow(clippy::unwrap_used)]
     let filename = argv_exec_args.ptr_to_string(0).unwrap();
 
+    fork_exec_wait(filename, &argv_exec_args, &envp_exec_args)
+}
+
+/// Like [`execute_process`], but executes the program at `path` while passing `argv` verbatim as
+/// the new program's argument vector.
+///
+/// This lets callers set `argv[0]` independently of the executed path, as shells conventionally do
+/// (e.g. a leading `-` for login shells).
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying calls to
+/// [`fork`](https://www.man7.org/linux/man-pages/man2/fork.2.html) and
+/// [`execve`](https://man7.org/linux/man-pages/man2/execve.2.html).
+pub fn execute_process_argv0<NA, NB, NC>(
+    path: NA,
+    argv: &[NB],
+    envp: &[NC],
+) -> Result<ExitStatus, Errno>
+where
+    NA: Into<NixString>,
+    NB: Into<NixString> + Clone,
+    NC: Into<NixString> + Clone,
+{
+    let path_ns: NixString = path.into();
+    let argv_exec_args = ExecArgs::from_slice(argv);
+    let envp_exec_args = ExecArgs::from_slice(envp);
+
+    fork_exec_wait(path_ns.as_ptr(), &argv_exec_args, &envp_exec_args)
+}
+
+/// Creates a child process running the executable at the given file name, returning the child's
+/// PID immediately instead of waiting for it to finish.
+///
+/// This is the building block for `&` background jobs in a shell: the caller keeps the PID and
+/// later reaps the child with [`try_wait`] or [`wait_state`]. In every other respect this behaves
+/// like [`execute_process`]: `argv[0]` names the program, non-standard file descriptors are
+/// closed in the child, and the child exits with the errno if `execve` fails.
+///
+/// # Errors
+///
+/// This function returns [`Errno::Enoent`] if `argv` is empty.
+///
+/// This function propagates any [`Errno`]s returned by the underlying call to
+/// [`fork`](https://www.man7.org/linux/man-pages/man2/fork.2.html). Failures of
+/// [`execve`](https://man7.org/linux/man-pages/man2/execve.2.html) happen in the child, so they
+/// only surface through its exit status.
+// Function won't panic. See below.
+#[allow(clippy::missing_panics_doc)]
+pub fn spawn_process<NA: Into<NixString> + Clone, NB: Into<NixString> + Clone>(
+    argv: &[NA],
+    envp: &[NB],
+) -> Result<i32, Errno> {
+    if argv.is_empty() {
+        return Err(Errno::Enoent);
+    }
+    let argv_exec_args = ExecArgs::from_slice(argv);
+    let envp_exec_args = ExecArgs::from_slice(envp);
+    // OK to unwrap here- we already made sure argv wasn't empty.
+    #[allow(clippy::unwrap_used)]
+    let filename = argv_exec_args.ptr_to_string(0).unwrap();
+
+    let child_pid = fork_exec(filename, &argv_exec_args, &envp_exec_args)?;
+    // OK to truncate; the kernel returns PIDs well within i32 range.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    Ok(child_pid as i32)
+}
+
+/// Like [`spawn_process`], but with the child's standard input and/or output redirected to the
+/// given files before `execve`.
+///
+/// This is the primitive behind shell pipelines and redirection: the caller opens the pipe ends
+/// or target files, and each one handed in here is `dup2`ed onto the child's stdin/stdout. The
+/// parent keeps its own standard streams untouched and remains responsible for closing its copies
+/// of the files.
+///
+/// # Errors
+///
+/// This function returns [`Errno::Enoent`] if `argv` is empty.
+///
+/// This function propagates any [`Errno`]s returned by the underlying call to
+/// [`fork`](https://www.man7.org/linux/man-pages/man2/fork.2.html). Failures of `dup2` and
+/// [`execve`](https://man7.org/linux/man-pages/man2/execve.2.html) happen in the child, so they
+/// only surface through its exit status.
+// Function won't panic. See below.
+#[allow(clippy::missing_panics_doc)]
+pub fn spawn_process_redirected<NA: Into<NixString> + Clone, NB: Into<NixString> + Clone>(
+    argv: &[NA],
+    envp: &[NB],
+    stdin: Option<&File>,
+    stdout: Option<&File>,
+) -> Result<i32, Errno> {
+    if argv.is_empty() {
+        return Err(Errno::Enoent);
+    }
+    let argv_exec_args = ExecArgs::from_slice(argv);
+    let envp_exec_args = ExecArgs::from_slice(envp);
+    // OK to unwrap here- we already made sure argv wasn't empty.
+    #[allow(clippy::unwrap_used)]
+    let filename = argv_exec_args.ptr_to_string(0).unwrap();
+
     match fork()? {
         0 => {
-            // Child process; start the given program
-
-            // SAFETY: On success, `execve` does not return, so the pointers only need to be valid
-            // at the moment of the syscall (which they are). Furthermore, the child process
-            // immediately exits if `execve` fails, avoiding UB there.
-            if let Err(errno) = unsafe {
-                syscall_result!(
-                    SyscallNum::Execve,
-                    filename,
-                    argv_exec_args.as_ptr(),
-                    envp_exec_args.as_ptr()
-                )
-            } {
+            // Child process; wire up the standard streams, then start the given program.
+            if let Some(file) = stdin
+                && let Err(errno) = file.file_descriptor().dup2(&FileDescriptor::from(STDIN_FD))
+            {
                 exit(ExitStatus::ExitFailure(errno as i32));
             }
-            unreachable!("execve doesn't return on success");
+            if let Some(file) = stdout
+                && let Err(errno) = file.file_descriptor().dup2(&FileDescriptor::from(STDOUT_FD))
+            {
+                exit(ExitStatus::ExitFailure(errno as i32));
+            }
+            exec_or_exit(filename, &argv_exec_args, &envp_exec_args);
         }
         child_pid => {
-            // Parent process; wait for child to finish
-            let wait_info = wait(child_pid, WaitIdType::Pid, WaitOptions::WEXITED)?;
-            wait_info.try_into()
+            // OK to truncate; the kernel returns PIDs well within i32 range.
+            #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+            Ok(child_pid as i32)
         }
     }
 }
 
+/// Shared implementation of [`execute_process`] and [`execute_process_argv0`]: forks, execs
+/// `filename` with the given argument and environment vectors in the child, and waits for the
+/// child to finish.
+fn fork_exec_wait(
+    filename: *const u8,
+    argv_exec_args: &ExecArgs,
+    envp_exec_args: &ExecArgs,
+) -> Result<ExitStatus, Errno> {
+    let child_pid = fork_exec(filename, argv_exec_args, envp_exec_args)?;
+    let wait_info = wait(child_pid, WaitIdType::Pid, WaitOptions::WEXITED)?;
+    wait_info.try_into()
+}
+
+/// Forks and execs `filename` with the given argument and environment vectors in the child,
+/// returning the child's PID in the parent. If `execve` fails, the child exits with the errno.
+fn fork_exec(
+    filename: *const u8,
+    argv_exec_args: &ExecArgs,
+    envp_exec_args: &ExecArgs,
+) -> Result<usize, Errno> {
+    match fork()? {
+        // Child process; start the given program
+        0 => exec_or_exit(filename, argv_exec_args, envp_exec_args),
+        child_pid => Ok(child_pid),
+    }
+}
+
+/// Closes non-standard file descriptors and execs `filename` in the current (child) process,
+/// exiting with the errno if `execve` fails.
+fn exec_or_exit(
+    filename: *const u8,
+    argv_exec_args: &ExecArgs,
+    envp_exec_args: &ExecArgs,
+) -> ! {
+    // Don't leak any non-standard file descriptors into the new program. Best-effort; an
+    // old kernel without `close_range` shouldn't stop the exec.
+    let _ = close_range(
+        FIRST_NON_STANDARD_FD,
+        u32::MAX,
+        CloseRangeFlags::default(),
+    );
+
+    // SAFETY: On success, `execve` does not return, so the pointers only need to be valid
+    // at the moment of the syscall (which they are). Furthermore, the child process
+    // immediately exits if `execve` fails, avoiding UB there.
+    if let Err(errno) = unsafe {
+        syscall_result!(
+            SyscallNum::Execve,
+            filename,
+            argv_exec_args.as_ptr(),
+            envp_exec_args.as_ptr()
+        )
+    } {
+        exit(ExitStatus::ExitFailure(errno as i32));
+    }
+    unreachable!("execve doesn't return on success");
+}
+
 /// Waits for the given process (or group of processes) to change state.
 ///
 /// Internally uses the [`waitid`](https://man7.org/linux/man-pages/man2/waitid.2.html) Linux
@@ -183,6 +359,265 @@ pub fn wait(id: usize, id_type: WaitIdType, wait_options: WaitOptions) -> Result
     WaitInfo::try_from(sig_info_raw)
 }
 
+/// Waits for the process with the given PID like [`wait`], additionally returning the child's
+/// [`RUsage`] measured atomically with the state change — no separate
+/// `getrusage(Children)`-style call (and the race it would bring) is needed.
+///
+/// Internally uses the [`wait4`](https://man7.org/linux/man-pages/man2/wait4.2.html) Linux
+/// syscall, which always waits for terminated children: [`WaitOptions::WEXITED`] is implied, and
+/// only the [`WaitOptions::WNOHANG`], [`WaitOptions::WSTOPPED`], and [`WaitOptions::WCONTINUED`]
+/// bits are passed through. Since `wait4` doesn't report the child's user ID, the returned
+/// [`WaitInfo::child_uid`] is always zero.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying call to `wait4`.
+pub fn wait4(pid: usize, wait_options: WaitOptions) -> Result<(WaitInfo, RUsage), Errno> {
+    // `wait4`'s option bits differ from `waitid`'s: exited children are always waited for, and
+    // the remaining flags happen to share `waitid`'s values.
+    const WAIT4_OPTIONS: WaitOptions = WaitOptions::WNOHANG
+        .union(WaitOptions::WSTOPPED)
+        .union(WaitOptions::WCONTINUED);
+
+    let mut wstatus: i32 = 0;
+    let mut rusage_raw = types::RUsageRaw::default();
+
+    // SAFETY: Both pointers are valid for writes for the duration of the call, and `RUsageRaw`
+    // matches the layout of the C `rusage` type.
+    let child_pid = unsafe {
+        syscall_result!(
+            SyscallNum::Wait4,
+            pid,
+            &raw mut wstatus as usize,
+            (wait_options & WAIT4_OPTIONS).bits(),
+            &raw mut rusage_raw as usize
+        )?
+    };
+
+    // OK to allow here; PIDs fit comfortably in an i32.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    let wait_info = WaitInfo::from_wait4_status(child_pid as i32, wstatus);
+    Ok((wait_info, rusage_raw.into()))
+}
+
+/// Waits for the process with the given PID to change to one of the states selected by
+/// `wait_options`, reporting the change as an [`ExitStatus`].
+///
+/// Unlike [`execute_process`], which only waits with [`WaitOptions::WEXITED`], this function lets
+/// job control callers also observe [`ExitStatus::Stopped`] and [`ExitStatus::Continued`] children
+/// by passing [`WaitOptions::WSTOPPED`] and/or [`WaitOptions::WCONTINUED`].
+///
+/// Convenience wrapper around [`wait`].
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying call to [`wait`].
+pub fn wait_state(pid: usize, wait_options: WaitOptions) -> Result<ExitStatus, Errno> {
+    wait(pid, WaitIdType::Pid, wait_options)?.try_into()
+}
+
+/// Checks whether the child with the given PID has exited, without blocking.
+///
+/// Returns `Ok(None)` if the child is still running, and `Ok(Some(_))` with its [`ExitStatus`]
+/// once it has been reaped. This is the building block for shell job control, where the shell
+/// polls its background jobs between prompts instead of blocking on them.
+///
+/// Internally uses the [`waitid`](https://man7.org/linux/man-pages/man2/waitid.2.html) Linux
+/// system call with [`WaitOptions::WNOHANG`].
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying call to `waitid`. Notably,
+/// [`Errno::Echild`] is returned if no child with the given PID exists.
+pub fn try_wait(pid: i32) -> Result<Option<ExitStatus>, Errno> {
+    let mut sig_info_raw = SigInfoRaw::default();
+
+    // OK to lose sign here; `waitid` only accepts positive PIDs and rejects the rest gracefully.
+    #[allow(clippy::cast_sign_loss)]
+    // SAFETY: WaitIdType restricts the given values to valid ones. SigInfoRaw matches the layout
+    // of `siginfo_t`. A null pointer is given for the last argument.
+    unsafe {
+        syscall_result!(
+            SyscallNum::Waitid,
+            WaitIdType::Pid as u32,
+            pid as usize,
+            &raw mut sig_info_raw,
+            (WaitOptions::WEXITED | WaitOptions::WNOHANG).bits(),
+            core::ptr::null::<u8>()
+        )?;
+    }
+
+    // With `WNOHANG`, a still-running child leaves the signal info zeroed.
+    if sig_info_raw.pid == 0 {
+        return Ok(None);
+    }
+
+    WaitInfo::try_from(sig_info_raw)?.try_into().map(Some)
+}
+
+// Macro to implement the infallible id-getter syscall wrappers, which share a shape: no
+// arguments, always succeed, and return a u32 id.
+macro_rules! id_getters {
+    (
+        $(
+            $(#[$outer:meta])*
+            $fn_name:ident => $syscall_num:ident;
+        )*
+    ) => {
+        $(
+            $(#[$outer])*
+            #[must_use]
+            pub fn $fn_name() -> u32 {
+                // SAFETY: This syscall has no arguments and is always successful.
+                // OK to truncate; the kernel returns ids well within u32 range.
+                #[allow(clippy::cast_possible_truncation)]
+                unsafe {
+                    syscall!(SyscallNum::$syscall_num) as u32
+                }
+            }
+        )*
+    };
+}
+id_getters!(
+    /// Returns the real user ID of the calling process. Wrapper around the
+    /// [`getuid`](https://man7.org/linux/man-pages/man2/getuid.2.html) Linux system call.
+    real_uid => Getuid;
+
+    /// Returns the effective user ID of the calling process. Wrapper around the
+    /// [`geteuid`](https://man7.org/linux/man-pages/man2/geteuid.2.html) Linux system call.
+    ///
+    /// This is the ID the kernel checks for most permission decisions. It matches [`real_uid`]
+    /// unless the process is running a set-user-ID binary (or changed its ids itself).
+    effective_uid => Geteuid;
+
+    /// Returns the real group ID of the calling process. Wrapper around the
+    /// [`getgid`](https://man7.org/linux/man-pages/man2/getgid.2.html) Linux system call.
+    real_gid => Getgid;
+
+    /// Returns the effective group ID of the calling process. Wrapper around the
+    /// [`getegid`](https://man7.org/linux/man-pages/man2/getegid.2.html) Linux system call.
+    ///
+    /// This matches [`real_gid`] unless the process is running a set-group-ID binary (or changed
+    /// its ids itself).
+    effective_gid => Getegid;
+);
+
+/// Returns the process ID of the calling process.
+///
+/// Wrapper around the [`getpid`](https://man7.org/linux/man-pages/man2/getpid.2.html) Linux
+/// system call, which always succeeds.
+#[must_use]
+pub fn getpid() -> i32 {
+    pid_syscall(SyscallNum::Getpid)
+}
+
+/// Returns the process ID of the parent of the calling process.
+///
+/// Wrapper around the [`getppid`](https://man7.org/linux/man-pages/man2/getppid.2.html) Linux
+/// system call, which always succeeds.
+#[must_use]
+pub fn getppid() -> i32 {
+    pid_syscall(SyscallNum::Getppid)
+}
+
+/// Returns the thread ID of the calling thread.
+///
+/// In a single-threaded process this matches [`getpid`]; in a thread spawned by
+/// [`thread::spawn`](crate::thread::spawn) it's the thread's own ID.
+///
+/// Wrapper around the [`gettid`](https://man7.org/linux/man-pages/man2/gettid.2.html) Linux
+/// system call, which always succeeds.
+#[must_use]
+pub fn gettid() -> i32 {
+    pid_syscall(SyscallNum::Gettid)
+}
+
+/// Returns the session ID of the process with the given PID. A `pid` of 0 queries the calling
+/// process itself.
+///
+/// Wrapper around the [`getsid`](https://man7.org/linux/man-pages/man2/getsid.2.html) Linux system
+/// call.
+///
+/// # Errors
+///
+/// This function returns [`Errno::Esrch`] if no process with the given PID exists.
+pub fn getsid(pid: i32) -> Result<i32, Errno> {
+    // OK to lose sign here; the kernel reinterprets the bytes as a PID anyway.
+    #[allow(clippy::cast_sign_loss)]
+    // SAFETY: No pointers are involved, and a bad PID is rejected gracefully by the kernel.
+    let sid = unsafe { syscall_result!(SyscallNum::Getsid, pid as usize)? };
+    // OK to truncate; the kernel returns session IDs well within i32 range.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    Ok(sid as i32)
+}
+
+/// Shared implementation of the infallible PID-getter wrappers.
+fn pid_syscall(syscall_num: SyscallNum) -> i32 {
+    // OK to truncate; the kernel returns PIDs well within i32 range.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    // SAFETY: These syscalls have no arguments and are always successful.
+    unsafe {
+        syscall!(syscall_num) as i32
+    }
+}
+
+/// The `personality` argument meaning "query the current personality without changing it".
+const PERSONALITY_QUERY: usize = 0xFFFF_FFFF;
+
+/// Returns the calling process's current execution domain (personality) flags.
+///
+/// Wrapper around the
+/// [`personality`](https://www.man7.org/linux/man-pages/man2/personality.2.html) Linux system
+/// call.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying call to `personality`.
+pub fn get_personality() -> Result<Personality, Errno> {
+    // Passing 0xFFFFFFFF queries the personality without changing it.
+    // SAFETY: No pointers are involved; the argument is statically-chosen and correct.
+    let old = unsafe { syscall_result!(SyscallNum::Personality, PERSONALITY_QUERY)? };
+    // OK to truncate; personalities only occupy the low 32 bits.
+    #[allow(clippy::cast_possible_truncation)]
+    Ok(Personality::from_bits_truncate(old as u32))
+}
+
+/// Sets the calling process's execution domain (personality) flags, returning the previous ones.
+///
+/// The personality survives `execve`, so e.g. [`Personality::ADDR_NO_RANDOMIZE`] set before an
+/// exec disables address-space-layout randomisation in the new program.
+///
+/// Wrapper around the
+/// [`personality`](https://www.man7.org/linux/man-pages/man2/personality.2.html) Linux system
+/// call.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying call to `personality`.
+pub fn set_personality(personality: Personality) -> Result<Personality, Errno> {
+    // SAFETY: No pointers are involved; unsupported flags are rejected gracefully by the kernel.
+    let old = unsafe { syscall_result!(SyscallNum::Personality, personality.bits())? };
+    // OK to truncate; personalities only occupy the low 32 bits.
+    #[allow(clippy::cast_possible_truncation)]
+    Ok(Personality::from_bits_truncate(old as u32))
+}
+
+/// Closes all file descriptors from `first` to `last` (inclusive).
+///
+/// Wrapper around the
+/// [`close_range`](https://man7.org/linux/man-pages/man2/close_range.2.html) Linux system call.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying call to `close_range`.
+pub fn close_range(first: u32, last: u32, flags: CloseRangeFlags) -> Result<(), Errno> {
+    // SAFETY: No pointers are involved; invalid ranges are rejected gracefully by the kernel.
+    unsafe {
+        syscall_result!(SyscallNum::CloseRange, first, last, flags.bits())?;
+    }
+    Ok(())
+}
+
 /// Causes normal process termination. Wrapper around the
 /// [exit](https://www.man7.org/linux/man-pages/man3/exit.3.html) Linux syscall.
 ///
@@ -0,0 +1,266 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::{assert_err, format, fs::OpenOptions};
+
+use super::*;
+
+const THIS_PATH: &str = "src/process.rs";
+
+#[test_case]
+fn close_range_closes_fds() {
+    let first = OpenOptions::new().open(THIS_PATH).unwrap();
+    let second = OpenOptions::new().open(THIS_PATH).unwrap();
+
+    let first_fd: usize = first.file_descriptor().into();
+    let second_fd: usize = second.file_descriptor().into();
+
+    #[allow(clippy::cast_possible_truncation)]
+    close_range(first_fd as u32, second_fd as u32, CloseRangeFlags::default()).unwrap();
+
+    let mut buf = [0u8; 1];
+    assert_err!(first.read(&mut buf), Errno::Ebadf);
+    assert_err!(second.read(&mut buf), Errno::Ebadf);
+
+    // Stop `Drop` from closing the (now possibly reused) descriptors again.
+    core::mem::forget(first);
+    core::mem::forget(second);
+}
+
+#[test_case]
+fn continued_wait_info_maps_to_continued() {
+    use crate::ipc::Signo;
+
+    let wait_info = WaitInfo {
+        child_pid: 1234,
+        child_uid: 1000,
+        status: Signo::SigCont as i32,
+        child_code: types::ChildCode::Continued,
+    };
+
+    let status = ExitStatus::try_from(wait_info).unwrap();
+    assert_eq!(status, ExitStatus::Continued);
+    assert_eq!(i32::from(status), 0);
+    assert_eq!(format!("{status}"), "continued");
+}
+
+#[test_case]
+fn wait4_reports_status_and_rusage() {
+    let child_pid = fork().unwrap();
+    if child_pid == 0 {
+        // Child; burn a little CPU so there's some usage to report.
+        let mut total = 0_u64;
+        for i in 0..1_000_000_u64 {
+            total = total.wrapping_add(i);
+        }
+        core::hint::black_box(total);
+        exit(ExitStatus::ExitSuccess);
+    }
+
+    let (wait_info, rusage) = wait4(child_pid, WaitOptions::WEXITED).unwrap();
+
+    // OK to allow here; PIDs fit comfortably in an i32.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    {
+        assert_eq!(wait_info.child_pid, child_pid as i32);
+    }
+    assert_eq!(ExitStatus::try_from(wait_info).unwrap(), ExitStatus::ExitSuccess);
+
+    // The usage numbers can legitimately be tiny, but never insane.
+    assert!(rusage.user_time + rusage.system_time < core::time::Duration::from_secs(60));
+}
+
+#[test_case]
+fn real_and_effective_ids_match() {
+    // The test runner isn't a setuid/setgid binary, so real and effective ids must agree.
+    assert_eq!(real_uid(), effective_uid());
+    assert_eq!(real_gid(), effective_gid());
+}
+
+#[test_case]
+fn pid_getters() {
+    let pid = getpid();
+    assert!(pid > 0);
+    assert!(getppid() > 0);
+    // The test runner is single-threaded, so the thread ID is the process ID.
+    assert_eq!(gettid(), pid);
+
+    let child_pid = fork().unwrap();
+    if child_pid == 0 {
+        // Child; report via the exit code whether its own view of the PIDs is consistent.
+        let ok = getpid() != pid && getppid() == pid;
+        exit(if ok {
+            ExitStatus::ExitSuccess
+        } else {
+            ExitStatus::ExitFailure(1)
+        });
+    }
+
+    // Parent; the child's checks surface through its exit status.
+    let status = wait_state(child_pid, WaitOptions::WEXITED).unwrap();
+    assert_eq!(status, ExitStatus::ExitSuccess);
+
+    // PIDs fit comfortably in an i32.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    {
+        assert_ne!(child_pid as i32, pid);
+    }
+}
+
+#[test_case]
+fn getsid_self_and_child() {
+    let sid = getsid(0).unwrap();
+    assert!(sid > 0);
+    // Querying by explicit PID agrees with the self-query.
+    assert_eq!(getsid(getpid()), Ok(sid));
+
+    let child_pid = fork().unwrap();
+    if child_pid == 0 {
+        // Child; a plain fork stays in the parent's session.
+        let ok = getsid(0) == Ok(sid);
+        exit(if ok {
+            ExitStatus::ExitSuccess
+        } else {
+            ExitStatus::ExitFailure(1)
+        });
+    }
+
+    let status = wait_state(child_pid, WaitOptions::WEXITED).unwrap();
+    assert_eq!(status, ExitStatus::ExitSuccess);
+}
+
+#[test_case]
+fn getsid_nonexistent_pid() {
+    // PID `i32::MAX` is above the default `pid_max`, so no such process can exist.
+    assert_err!(getsid(i32::MAX), Errno::Esrch);
+}
+
+#[test_case]
+fn personality_set_and_restore() {
+    let original = get_personality().unwrap();
+
+    // Setting the flag reports the old personality and is visible on read-back.
+    let previous = set_personality(original | Personality::ADDR_NO_RANDOMIZE).unwrap();
+    assert_eq!(previous, original);
+    assert!(
+        get_personality()
+            .unwrap()
+            .contains(Personality::ADDR_NO_RANDOMIZE)
+    );
+
+    // Clear the flag again so the rest of the test run isn't affected.
+    let previous = set_personality(original).unwrap();
+    assert!(previous.contains(Personality::ADDR_NO_RANDOMIZE));
+    assert_eq!(get_personality().unwrap(), original);
+}
+
+#[test_case]
+fn wait_state_reports_stopped_child() {
+    use crate::ipc::{Signo, kill};
+
+    let child_pid = fork().unwrap();
+    if child_pid == 0 {
+        // Child; spin until the parent reaps us.
+        loop {
+            core::hint::spin_loop();
+        }
+    }
+
+    // PIDs fit comfortably in an i32.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    let child_pid_i32 = child_pid as i32;
+
+    kill(child_pid_i32, Signo::SigStop).unwrap();
+    let status = wait_state(child_pid, WaitOptions::WSTOPPED).unwrap();
+    assert_eq!(status, ExitStatus::Stopped(Signo::SigStop));
+
+    // Clean up: kill the child and reap it so it doesn't linger as a zombie.
+    kill(child_pid_i32, Signo::SigKill).unwrap();
+    let status = wait_state(child_pid, WaitOptions::WEXITED).unwrap();
+    assert_eq!(status, ExitStatus::Terminated(Signo::SigKill));
+}
+
+#[test_case]
+fn try_wait_polls_background_child() {
+    use core::time::Duration;
+
+    let child_pid = fork().unwrap();
+    if child_pid == 0 {
+        // Child; stay alive long enough for the parent's first poll to see us running.
+        let _ = crate::thread::sleep(&Duration::from_millis(500));
+        exit(ExitStatus::ExitSuccess);
+    }
+
+    // PIDs fit comfortably in an i32.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    let child_pid_i32 = child_pid as i32;
+
+    // The child is still sleeping, so the first poll must not block or reap it.
+    assert_eq!(try_wait(child_pid_i32), Ok(None));
+
+    // Poll until the child exits.
+    loop {
+        match try_wait(child_pid_i32).unwrap() {
+            None => crate::thread::sleep(&Duration::from_millis(50)).unwrap(),
+            Some(status) => {
+                assert_eq!(status, ExitStatus::ExitSuccess);
+                break;
+            }
+        }
+    }
+
+    // The child has been reaped, so it's no longer our child.
+    assert_err!(try_wait(child_pid_i32), Errno::Echild);
+}
+
+#[test_case]
+fn spawn_process_runs_in_background() {
+    use core::time::Duration;
+
+    // The shell exits immediately, but the spawning call itself must not block on it.
+    let child_pid = spawn_process(&["/bin/sh", "-c", "exit 7"], &[""; 0]).unwrap();
+    assert!(child_pid > 0);
+
+    // Reap the background child and check its exit code.
+    loop {
+        match try_wait(child_pid).unwrap() {
+            None => crate::thread::sleep(&Duration::from_millis(10)).unwrap(),
+            Some(status) => {
+                assert_eq!(status, ExitStatus::ExitFailure(7));
+                break;
+            }
+        }
+    }
+}
+
+#[test_case]
+fn spawn_process_empty_argv() {
+    assert_err!(spawn_process(&[""; 0], &[""; 0]), Errno::Enoent);
+}
+
+#[test_case]
+fn execute_process_argv0_overrides_name() {
+    // The shell's `argv[0]` (the first NUL-terminated string in its cmdline) must be the custom
+    // name, not the executed path.
+    let status = execute_process_argv0(
+        "/bin/sh",
+        &[
+            "tlenix_argv0_test",
+            "-c",
+            "grep -q ^tlenix_argv0_test /proc/self/cmdline",
+        ],
+        &[""; 0],
+    )
+    .unwrap();
+    assert_eq!(status, ExitStatus::ExitSuccess);
+}
+
+#[test_case]
+fn execute_process_closes_inherited_fds() {
+    // This descriptor must not survive into the child.
+    let file = OpenOptions::new().open(THIS_PATH).unwrap();
+    let fd: usize = file.file_descriptor().into();
+
+    let check = format!("test ! -e /proc/self/fd/{fd}");
+    let status = execute_process(&["/bin/sh", "-c", &check], &[""; 0]).unwrap();
+    assert_eq!(status, ExitStatus::ExitSuccess);
+}
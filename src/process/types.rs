@@ -2,6 +2,8 @@
 
 use num_enum::TryFromPrimitive;
 
+use core::time::Duration;
+
 use crate::{
     Errno,
     ipc::{SigInfoRaw, Signo},
@@ -18,6 +20,8 @@ pub enum ExitStatus {
     Terminated(Signo),
     /// The process was stopped by a signal.
     Stopped(Signo),
+    /// The process was resumed by [`Signo::SigCont`].
+    Continued,
 }
 impl From<ExitStatus> for i32 {
     fn from(value: ExitStatus) -> Self {
@@ -25,7 +29,7 @@ impl From<ExitStatus> for i32 {
         use ExitStatus::*;
 
         match value {
-            ExitSuccess => 0,
+            ExitSuccess | Continued => 0,
             ExitFailure(val) => val,
             Terminated(signo) | Stopped(signo) => signo as i32,
         }
@@ -41,6 +45,7 @@ impl core::fmt::Display for ExitStatus {
             ExitFailure(code) => write!(f, "exited with code {code}"),
             Terminated(signo) => write!(f, "killed by signal {signo}"),
             Stopped(signo) => write!(f, "stopped by signal {signo}"),
+            Continued => write!(f, "continued"),
         }
     }
 }
@@ -92,6 +97,40 @@ impl WaitInfo {
             _ => None,
         }
     }
+
+    /// Decodes the raw `wstatus` word reported by
+    /// [`wait4`](https://man7.org/linux/man-pages/man2/wait4.2.html)-style system calls.
+    ///
+    /// `wait4` doesn't report the child's user ID, so [`Self::child_uid`] is always zero.
+    pub(crate) fn from_wait4_status(child_pid: i32, wstatus: i32) -> Self {
+        /// The low bits holding the terminating/stopping signal.
+        const SIGNAL_MASK: i32 = 0x7f;
+        /// A stopped child reports this in its low byte.
+        const STOPPED_BYTE: i32 = 0x7f;
+        /// A continued child reports exactly this status.
+        const CONTINUED_STATUS: i32 = 0xffff;
+        /// Set if the terminated child dumped core.
+        const CORE_DUMP_BIT: i32 = 0x80;
+
+        let (child_code, status) = if wstatus == CONTINUED_STATUS {
+            (ChildCode::Continued, Signo::SigCont as i32)
+        } else if wstatus & SIGNAL_MASK == 0 {
+            (ChildCode::Exited, (wstatus >> 8) & 0xff)
+        } else if wstatus & 0xff == STOPPED_BYTE {
+            (ChildCode::Stopped, (wstatus >> 8) & 0xff)
+        } else if wstatus & CORE_DUMP_BIT != 0 {
+            (ChildCode::Dumped, wstatus & SIGNAL_MASK)
+        } else {
+            (ChildCode::Killed, wstatus & SIGNAL_MASK)
+        };
+
+        Self {
+            child_pid,
+            child_uid: 0,
+            status,
+            child_code,
+        }
+    }
 }
 impl TryFrom<WaitInfo> for ExitStatus {
     type Error = Errno;
@@ -102,6 +141,7 @@ impl TryFrom<WaitInfo> for ExitStatus {
         match (value.child_code, value.status) {
             (Killed | Dumped, s) => Ok(Self::Terminated(s.try_into().map_err(|_| Errno::Einval)?)),
             (Stopped, s) => Ok(Self::Stopped(s.try_into().map_err(|_| Errno::Einval)?)),
+            (Continued, _) => Ok(Self::Continued),
             (_, 0) => Ok(Self::ExitSuccess),
             (_, s) => Ok(Self::ExitFailure(s)),
         }
@@ -147,6 +187,120 @@ impl Default for WaitOptions {
     }
 }
 
+bitflags::bitflags! {
+    /// All the different option flags which can be passed to [`crate::process::close_range`].
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+    pub struct CloseRangeFlags: u32 {
+        /// Unshare the file descriptor table before closing, avoiding any effect on other threads
+        /// sharing it.
+        const UNSHARE = 0x2;
+        /// Set the close-on-exec flag on the file descriptors instead of closing them.
+        const CLOEXEC = 0x4;
+    }
+}
+
+bitflags::bitflags! {
+    /// Execution-domain (personality) flags, as read and written by
+    /// [`crate::process::get_personality`] and [`crate::process::set_personality`].
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+    pub struct Personality: u32 {
+        /// Disable address-space-layout randomisation for this process and its children.
+        const ADDR_NO_RANDOMIZE = 0x0004_0000;
+        /// Map page 0 as readable, like some old binaries expect.
+        const MMAP_PAGE_ZERO = 0x0010_0000;
+        /// Use the legacy virtual address space layout.
+        const ADDR_COMPAT_LAYOUT = 0x0020_0000;
+        /// Make `PROT_READ` mappings implicitly executable.
+        const READ_IMPLIES_EXEC = 0x0040_0000;
+        /// Stop `select` and friends from modifying the returned timeout.
+        const STICKY_TIMEOUTS = 0x0400_0000;
+    }
+}
+
+/// Corresponds to the [timeval](https://www.man7.org/linux/man-pages/man3/timeval.3type.html)
+/// type in C.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+struct TimevalRaw {
+    /// Seconds.
+    sec: i64,
+    /// Microseconds.
+    usec: i64,
+}
+impl From<TimevalRaw> for Duration {
+    fn from(value: TimevalRaw) -> Self {
+        // OK to lose sign here; the kernel never reports negative time intervals.
+        #[allow(clippy::cast_sign_loss)]
+        (Self::from_secs(value.sec as u64) + Self::from_micros(value.usec as u64))
+    }
+}
+
+/// Corresponds to the `rusage` datatype described in the
+/// [`getrusage` manpage](https://man7.org/linux/man-pages/man2/getrusage.2.html).
+///
+/// Several fields are unmaintained on Linux; only the maintained ones make it into [`RUsage`].
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct RUsageRaw {
+    utime: TimevalRaw,
+    stime: TimevalRaw,
+    maxrss: i64,
+    ixrss: i64,
+    idrss: i64,
+    isrss: i64,
+    minflt: i64,
+    majflt: i64,
+    nswap: i64,
+    inblock: i64,
+    oublock: i64,
+    msgsnd: i64,
+    msgrcv: i64,
+    nsignals: i64,
+    nvcsw: i64,
+    nivcsw: i64,
+}
+
+/// Resource usage measurements for a process, as reported by
+/// [`getrusage`](https://man7.org/linux/man-pages/man2/getrusage.2.html)-style system calls.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RUsage {
+    /// Time spent executing in user mode.
+    pub user_time: Duration,
+    /// Time spent executing in kernel mode.
+    pub system_time: Duration,
+    /// Maximum resident set size, in KiB.
+    pub max_rss_kib: u64,
+    /// Page faults serviced without I/O.
+    pub minor_page_faults: u64,
+    /// Page faults which required I/O.
+    pub major_page_faults: u64,
+    /// Times the filesystem had to perform input.
+    pub block_reads: u64,
+    /// Times the filesystem had to perform output.
+    pub block_writes: u64,
+    /// Context switches due to voluntarily waiting.
+    pub voluntary_context_switches: u64,
+    /// Context switches forced by the scheduler.
+    pub involuntary_context_switches: u64,
+}
+// OK to lose sign here; the kernel never reports negative resource counts.
+#[allow(clippy::cast_sign_loss)]
+impl From<RUsageRaw> for RUsage {
+    fn from(value: RUsageRaw) -> Self {
+        Self {
+            user_time: value.utime.into(),
+            system_time: value.stime.into(),
+            max_rss_kib: value.maxrss as u64,
+            minor_page_faults: value.minflt as u64,
+            major_page_faults: value.majflt as u64,
+            block_reads: value.inblock as u64,
+            block_writes: value.oublock as u64,
+            voluntary_context_switches: value.nvcsw as u64,
+            involuntary_context_switches: value.nivcsw as u64,
+        }
+    }
+}
+
 /// Denotes which child state changes to wait for.
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
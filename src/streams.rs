@@ -105,6 +105,53 @@ impl Stream<Input> {
     pub fn read_to_string(&self) -> Result<String, Errno> {
         self.file.read_to_string()
     }
+
+    /// Returns an iterator over the bytes of this stream, ending cleanly at EOF.
+    ///
+    /// Reads are buffered internally, so tools consuming their input byte-by-byte don't issue one
+    /// `read` syscall per byte.
+    #[must_use]
+    pub fn bytes(&self) -> Bytes<'_> {
+        Bytes {
+            stream: self,
+            buffer: [0; BYTES_BUF_SIZE],
+            pos: 0,
+            len: 0,
+        }
+    }
+}
+
+/// Size of the internal buffer used by [`Bytes`].
+const BYTES_BUF_SIZE: usize = 1 << 10;
+
+/// A buffered iterator over the bytes of an input stream. Created by [`Stream::bytes`].
+#[derive(Debug)]
+pub struct Bytes<'a> {
+    stream: &'a Stream<Input>,
+    buffer: [u8; BYTES_BUF_SIZE],
+    pos: usize,
+    len: usize,
+}
+impl Iterator for Bytes<'_> {
+    type Item = Result<u8, Errno>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.len {
+            // Buffer exhausted; refill it.
+            match self.stream.read(&mut self.buffer) {
+                // EOF.
+                Ok(0) => return None,
+                Ok(bytes_read) => {
+                    self.pos = 0;
+                    self.len = bytes_read;
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        let byte = self.buffer[self.pos];
+        self.pos += 1;
+        Some(Ok(byte))
+    }
 }
 impl Stream<Output> {
     /// Writes bytes from the provided buffer into the stream, returning the number of bytes
@@ -125,3 +172,109 @@ impl core::fmt::Write for Stream<Output> {
         Ok(())
     }
 }
+
+/// An output sink which discards everything written to it, like `/dev/null`.
+///
+/// Mirrors the [standard library's `sink`](https://doc.rust-lang.org/std/io/fn.sink.html). Handy
+/// for exercising writing code in tests without touching a real file or pipe.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Sink;
+impl Sink {
+    /// "Writes" the bytes of the provided buffer by discarding them, returning the number of
+    /// bytes consumed (always the whole buffer).
+    ///
+    /// # Errors
+    ///
+    /// This function never fails; the signature matches [`Stream::write`] so the two are
+    /// interchangeable.
+    // OK to allow; the signature intentionally matches `Stream::write`.
+    #[allow(clippy::unused_self, clippy::missing_const_for_fn)]
+    pub fn write(&self, buffer: &[u8]) -> Result<usize, Errno> {
+        Ok(buffer.len())
+    }
+}
+impl core::fmt::Write for Sink {
+    fn write_str(&mut self, _s: &str) -> core::fmt::Result {
+        Ok(())
+    }
+}
+
+/// An input source which yields the contained byte forever.
+///
+/// Mirrors the [standard library's `repeat`](https://doc.rust-lang.org/std/io/fn.repeat.html).
+/// Handy for exercising reading code in tests without touching a real file or pipe.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Repeat(pub u8);
+impl Repeat {
+    /// Fills the entire provided buffer with the repeated byte, returning the buffer's length.
+    ///
+    /// # Errors
+    ///
+    /// This function never fails; the signature matches [`Stream::read`] so the two are
+    /// interchangeable.
+    pub fn read(&self, buffer: &mut [u8]) -> Result<usize, Errno> {
+        buffer.fill(self.0);
+        Ok(buffer.len())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    use crate::fs::{OpenOptions, rm};
+
+    #[test_case]
+    fn bytes_sequence_and_clean_eof() {
+        const TEST_PATH: &str = "/tmp/tlenix_stream_bytes_test";
+        const CONTENTS: &[u8] = b"fed a known string";
+
+        let _ = rm(TEST_PATH);
+        let file = OpenOptions::new()
+            .read_write()
+            .create(true)
+            .open(TEST_PATH)
+            .unwrap();
+        file.write(CONTENTS).unwrap();
+        file.set_cursor(0).unwrap();
+
+        // Treat the file's descriptor as an input stream, like a pipe feeding stdin.
+        let stream = Stream::<Input>::define(usize::from(file.file_descriptor()));
+
+        let mut collected = Vec::new();
+        let mut bytes = stream.bytes();
+        for byte in bytes.by_ref() {
+            collected.push(byte.unwrap());
+        }
+        assert_eq!(collected, CONTENTS);
+        // A finished iterator keeps reporting EOF.
+        assert!(bytes.next().is_none());
+
+        // `file` owns the descriptor; don't let `stream` close it a second time.
+        core::mem::forget(stream);
+        drop(file);
+        rm(TEST_PATH).unwrap();
+    }
+
+    #[test_case]
+    fn sink_reports_full_count() {
+        let sink = Sink;
+        assert_eq!(sink.write(b"discarded"), Ok(9));
+        assert_eq!(sink.write(&[]), Ok(0));
+    }
+
+    #[test_case]
+    fn repeat_fills_bounded_reads() {
+        let repeat = Repeat(b'y');
+
+        let mut buffer = [0_u8; 64];
+        assert_eq!(repeat.read(&mut buffer), Ok(64));
+        assert_eq!(buffer, [b'y'; 64]);
+
+        // The source never runs dry; a second read yields just as much.
+        let mut small = [0_u8; 3];
+        assert_eq!(repeat.read(&mut small), Ok(3));
+        assert_eq!(small, [b'y'; 3]);
+    }
+}
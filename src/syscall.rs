@@ -9,7 +9,7 @@ mod types;
 // RE-EXPORTS
 pub use errno::Errno;
 pub use nums::SyscallNum;
-pub use types::SyscallArg;
+pub use types::{SliceArg, SyscallArg};
 
 /// Invoke a Linux syscall, getting a [`usize`] in return denoting the result status.
 ///
@@ -1,12 +1,43 @@
 use crate::{
+    console::PollFdRaw,
     fs::{FileDescriptor, FileStatsRaw},
-    ipc::SigInfoRaw,
+    ipc::{SigInfoRaw, SigactionRaw, SignalSet},
     process::ExitStatus,
+    term::{Termios, WindowSize},
 };
 
+/// A pointer/length pair of syscall arguments describing a single slice. Keeping the two together
+/// avoids mismatched pointer/length pairs that cause out-of-bounds kernel reads.
+pub type SliceArg = (SyscallArg, SyscallArg);
+
 /// A syscall argument. A newtype wrapper around the [`core::usize`] type.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct SyscallArg(usize);
+impl SyscallArg {
+    /// Creates a [`SyscallArg`] from a const pointer to any type.
+    #[must_use]
+    pub fn from_ptr<T>(ptr: *const T) -> Self {
+        Self(ptr as usize)
+    }
+
+    /// Creates a [`SyscallArg`] from a mutable pointer to any type.
+    #[must_use]
+    pub fn from_mut_ptr<T>(ptr: *mut T) -> Self {
+        Self(ptr as usize)
+    }
+
+    /// Creates a pointer argument and its companion length argument from the given slice.
+    #[must_use]
+    pub fn from_slice<T>(slice: &[T]) -> SliceArg {
+        (Self::from_ptr(slice.as_ptr()), Self(slice.len()))
+    }
+
+    /// Creates a pointer argument and its companion length argument from the given mutable slice.
+    #[must_use]
+    pub fn from_mut_slice<T>(slice: &mut [T]) -> SliceArg {
+        (Self::from_mut_ptr(slice.as_mut_ptr()), Self(slice.len()))
+    }
+}
 impl From<SyscallArg> for usize {
     fn from(value: SyscallArg) -> Self {
         value.0
@@ -67,9 +98,47 @@ impl_from_syscallarg_for_as_usize![
     *const u8,
     *const *const u8,
     *mut u8,
+    *mut i32,
     *mut FileStatsRaw,
     *mut SigInfoRaw,
+    *mut PollFdRaw,
+    *const SigactionRaw,
+    *const SignalSet,
+    *mut SignalSet,
+    *const Termios,
+    *mut Termios,
+    *mut WindowSize,
     *const usize,
     *mut usize
 ];
 impl_from_syscallarg_for_as_isize![i8, i16, i32, i64, i128, isize];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn from_slice_matches_slice() {
+        let buf = [1_u64, 2, 3];
+        let (ptr, len) = SyscallArg::from_slice(&buf);
+        assert_eq!(usize::from(ptr), buf.as_ptr() as usize);
+        assert_eq!(usize::from(len), buf.len());
+    }
+
+    #[test_case]
+    fn from_mut_slice_matches_slice() {
+        let mut buf = [0_u8; 16];
+        let (ptr, len) = SyscallArg::from_mut_slice(&mut buf);
+        assert_eq!(usize::from(ptr), buf.as_ptr() as usize);
+        assert_eq!(usize::from(len), 16);
+    }
+
+    #[test_case]
+    fn from_ptr_round_trips() {
+        let value = 42_i32;
+        assert_eq!(
+            usize::from(SyscallArg::from_ptr(&raw const value)),
+            &raw const value as usize
+        );
+    }
+}
@@ -1,6 +1,9 @@
 //! Functionality related to the computer system itself.
 
-use crate::{Errno, SyscallNum, syscall_result};
+use alloc::string::String;
+use core::time::Duration;
+
+use crate::{Errno, NixString, SyscallNum, syscall_result};
 
 const LINUX_REBOOT_MAGIC1: usize = 0xfee1_dead;
 const LINUX_REBOOT_MAGIC2C: usize = 0x2011_2000;
@@ -51,6 +54,363 @@ pub fn power_off() -> Result<!, Errno> {
     reboot_syscall(RebootCmd::PowerOff)
 }
 
+/// The clocks readable by [`clock_gettime`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(usize)]
+pub enum ClockId {
+    /// Wall-clock time, measured since the Unix epoch. Jumps when the system time is changed.
+    Realtime = 0,
+    /// Time since some unspecified starting point, guaranteed never to jump backwards.
+    Monotonic = 1,
+}
+
+/// Gets the current time of the given clock as a [`Duration`] since that clock's epoch.
+///
+/// Wrapper around the
+/// [`clock_gettime`](https://man7.org/linux/man-pages/man2/clock_gettime.2.html) Linux syscall.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying `clock_gettime` syscall.
+pub fn clock_gettime(clock: ClockId) -> Result<Duration, Errno> {
+    /// A `timespec`-shaped timestamp as returned by `clock_gettime`.
+    #[repr(C)]
+    struct TimespecRaw {
+        /// Seconds.
+        sec: i64,
+        /// Nanoseconds.
+        nsec: i64,
+    }
+
+    let mut timespec = TimespecRaw { sec: 0, nsec: 0 };
+
+    // SAFETY: The `TimespecRaw` type matches the layout expected by `clock_gettime`, the clock is
+    // restricted to valid values by the `ClockId` enum, and the mutable raw pointer is dropped
+    // right after the syscall.
+    unsafe {
+        syscall_result!(
+            SyscallNum::ClockGettime,
+            clock as usize,
+            &raw mut timespec as usize
+        )?;
+    }
+
+    // OK to lose sign/truncate here. The kernel never reports negative times for these clocks, and
+    // the nanoseconds field is always below one billion.
+    #[allow(clippy::cast_sign_loss)]
+    #[allow(clippy::cast_possible_truncation)]
+    Ok(Duration::new(timespec.sec as u64, timespec.nsec as u32))
+}
+
+/// The length of each field in the kernel `utsname` struct, including its null terminator.
+const UTSNAME_FIELD_LEN: usize = 65;
+
+/// System identification as reported by [`uname`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UtsName {
+    /// The operating system name (e.g. `"Linux"`).
+    pub sysname: String,
+    /// The network node hostname.
+    pub nodename: String,
+    /// The operating system release (e.g. `"6.8.0"`).
+    pub release: String,
+    /// The operating system version.
+    pub version: String,
+    /// The hardware identifier (e.g. `"x86_64"`).
+    pub machine: String,
+    /// The NIS or YP domain name.
+    pub domainname: String,
+}
+
+/// Gets name and information about the running kernel.
+///
+/// Wrapper around the [`uname`](https://man7.org/linux/man-pages/man2/uname.2.html) Linux syscall.
+///
+/// # Errors
+///
+/// This function returns [`Errno::Eilseq`] if a `utsname` field isn't valid UTF-8, and propagates
+/// any [`Errno`]s returned by the underlying `uname` syscall.
+pub fn uname() -> Result<UtsName, Errno> {
+    /// A `utsname`-shaped buffer as filled in by `uname`.
+    #[repr(C)]
+    struct UtsNameRaw {
+        /// The operating system name.
+        sysname: [u8; UTSNAME_FIELD_LEN],
+        /// The network node hostname.
+        nodename: [u8; UTSNAME_FIELD_LEN],
+        /// The operating system release.
+        release: [u8; UTSNAME_FIELD_LEN],
+        /// The operating system version.
+        version: [u8; UTSNAME_FIELD_LEN],
+        /// The hardware identifier.
+        machine: [u8; UTSNAME_FIELD_LEN],
+        /// The NIS or YP domain name.
+        domainname: [u8; UTSNAME_FIELD_LEN],
+    }
+
+    /// Converts a null-padded `utsname` field into an owned [`String`], trimmed at the first null
+    /// byte.
+    fn field_string(field: &[u8]) -> Result<String, Errno> {
+        let len = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+        String::from_utf8(field[..len].to_vec()).map_err(|_| Errno::Eilseq)
+    }
+
+    let mut raw = UtsNameRaw {
+        sysname: [0; UTSNAME_FIELD_LEN],
+        nodename: [0; UTSNAME_FIELD_LEN],
+        release: [0; UTSNAME_FIELD_LEN],
+        version: [0; UTSNAME_FIELD_LEN],
+        machine: [0; UTSNAME_FIELD_LEN],
+        domainname: [0; UTSNAME_FIELD_LEN],
+    };
+
+    // SAFETY: The `UtsNameRaw` type matches the layout expected by `uname`, and the mutable raw
+    // pointer to `raw` is dropped right after the syscall.
+    unsafe {
+        syscall_result!(SyscallNum::Uname, &raw mut raw as usize)?;
+    }
+
+    Ok(UtsName {
+        sysname: field_string(&raw.sysname)?,
+        nodename: field_string(&raw.nodename)?,
+        release: field_string(&raw.release)?,
+        version: field_string(&raw.version)?,
+        machine: field_string(&raw.machine)?,
+        domainname: field_string(&raw.domainname)?,
+    })
+}
+
+/// Gets the system hostname.
+///
+/// Reads the `nodename` field reported by [`uname`], matching what
+/// [`gethostname`](https://man7.org/linux/man-pages/man2/gethostname.2.html) returns.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying call to [`uname`].
+pub fn get_hostname() -> Result<String, Errno> {
+    Ok(uname()?.nodename)
+}
+
+/// Sets the system hostname.
+///
+/// Wrapper around the
+/// [`sethostname`](https://man7.org/linux/man-pages/man2/sethostname.2.html) Linux syscall.
+///
+/// # Errors
+///
+/// This function returns [`Errno::Eperm`] if the caller has insufficient privileges to set the
+/// hostname, and [`Errno::Einval`] if the name is too long.
+pub fn set_hostname<NS: Into<NixString>>(name: NS) -> Result<(), Errno> {
+    let ns_name = name.into();
+
+    // SAFETY: The pointer and length describe a valid byte buffer which outlives the syscall.
+    unsafe {
+        syscall_result!(
+            SyscallNum::Sethostname,
+            ns_name.as_ptr(),
+            ns_name.as_bytes().len()
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Overall system statistics as reported by [`sysinfo`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SysInfo {
+    /// Time since boot.
+    pub uptime: Duration,
+    /// The 1-, 5-, and 15-minute load averages, in the kernel's fixed-point representation
+    /// (`1 << 16` per runnable process).
+    pub loads: [u64; 3],
+    /// The total usable main memory size, in bytes.
+    pub total_ram: u64,
+    /// The amount of currently-available memory, in bytes.
+    pub free_ram: u64,
+    /// The number of current processes.
+    pub procs: u16,
+}
+
+/// Gets overall system statistics: uptime, load averages, memory amounts, and the process count.
+///
+/// Wrapper around the [`sysinfo`](https://man7.org/linux/man-pages/man2/sysinfo.2.html) Linux
+/// syscall.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying `sysinfo` syscall.
+pub fn sysinfo() -> Result<SysInfo, Errno> {
+    /// A `sysinfo`-shaped buffer as filled in by the syscall.
+    #[repr(C)]
+    #[derive(Default)]
+    #[allow(dead_code)]
+    struct SysInfoRaw {
+        /// Seconds since boot.
+        uptime: i64,
+        /// The 1-, 5-, and 15-minute load averages.
+        loads: [u64; 3],
+        /// Total usable main memory size, in memory units.
+        total_ram: u64,
+        /// Available memory size, in memory units.
+        free_ram: u64,
+        /// Amount of shared memory, in memory units.
+        shared_ram: u64,
+        /// Memory used by buffers, in memory units.
+        buffer_ram: u64,
+        /// Total swap space size, in memory units.
+        total_swap: u64,
+        /// Swap space still available, in memory units.
+        free_swap: u64,
+        /// The number of current processes.
+        procs: u16,
+        /// Explicit padding, matching the kernel struct.
+        pad: u16,
+        /// Total high memory size, in memory units (always 0 on `x86_64`).
+        total_high: u64,
+        /// Available high memory size, in memory units (always 0 on `x86_64`).
+        free_high: u64,
+        /// The size of a memory unit, in bytes.
+        mem_unit: u32,
+    }
+
+    let mut raw = SysInfoRaw::default();
+
+    // SAFETY: The `SysInfoRaw` type matches the layout expected by `sysinfo`, and the mutable raw
+    // pointer to `raw` is dropped right after the syscall.
+    unsafe {
+        syscall_result!(SyscallNum::Sysinfo, &raw mut raw as usize)?;
+    }
+
+    // OK to lose sign here; the kernel never reports a negative uptime.
+    #[allow(clippy::cast_sign_loss)]
+    Ok(SysInfo {
+        uptime: Duration::from_secs(raw.uptime as u64),
+        loads: raw.loads,
+        total_ram: raw.total_ram * u64::from(raw.mem_unit),
+        free_ram: raw.free_ram * u64::from(raw.mem_unit),
+        procs: raw.procs,
+    })
+}
+
+bitflags::bitflags! {
+    /// Flags for [`getrandom`]. See
+    /// [`getrandom(2)`](https://man7.org/linux/man-pages/man2/getrandom.2.html) for more details.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+    pub struct GetRandomFlags: u32 {
+        /// Don't block waiting for entropy; return [`Errno::Eagain`] instead.
+        const NONBLOCK = 0x1;
+        /// Draw from the `random` source (à la `/dev/random`) instead of the `urandom` source.
+        const RANDOM = 0x2;
+    }
+}
+
+/// Fills the given buffer with random bytes, returning the number of bytes written.
+///
+/// Wrapper around the [`getrandom`](https://man7.org/linux/man-pages/man2/getrandom.2.html) Linux
+/// syscall. Without [`GetRandomFlags::NONBLOCK`], short reads are retried until the whole buffer
+/// is filled, so the returned count equals `buf.len()`.
+///
+/// # Errors
+///
+/// This function returns [`Errno::Eagain`] if [`GetRandomFlags::NONBLOCK`] is set and no entropy
+/// is available, and propagates any other [`Errno`]s returned by the underlying `getrandom`
+/// syscall.
+pub fn getrandom(buf: &mut [u8], flags: GetRandomFlags) -> Result<usize, Errno> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        let rest = &mut buf[filled..];
+
+        // SAFETY: The pointer and length describe a valid, writable buffer which outlives the
+        // syscall.
+        filled += unsafe {
+            syscall_result!(
+                SyscallNum::Getrandom,
+                rest.as_mut_ptr(),
+                rest.len(),
+                flags.bits()
+            )?
+        };
+
+        if flags.contains(GetRandomFlags::NONBLOCK) {
+            // Non-blocking callers asked not to wait around for the rest.
+            break;
+        }
+    }
+    Ok(filled)
+}
+
+/// A single segment of a kernel image to be loaded by [`kexec_load`]. Directly corresponds to the
+/// `kexec_segment` struct used by the
+/// [`kexec_load`](https://man7.org/linux/man-pages/man2/kexec_load.2.html) Linux syscall.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct KexecSegment {
+    /// Pointer to the segment data in the calling process's memory.
+    pub buf: *const u8,
+    /// The size (in bytes) of the segment data.
+    pub bufsz: usize,
+    /// The physical address the segment is loaded to.
+    pub mem: *const u8,
+    /// The size (in bytes) of the destination region. Must be at least `bufsz`; any excess is
+    /// zeroed.
+    pub memsz: usize,
+}
+
+/// Loads a new kernel image for a later [`kexec_reboot`].
+///
+/// The image is described by its entry point and a list of [`KexecSegment`]s to be copied into
+/// physical memory. Calling with an empty segment list unloads any currently-loaded image.
+/// Wrapper around the [`kexec_load`](https://man7.org/linux/man-pages/man2/kexec_load.2.html)
+/// Linux syscall.
+///
+/// # Safety
+///
+/// The kernel copies and validates the segment buffers at load time, but it cannot tell a real
+/// kernel image from garbage. The caller must ensure `entry` and `segments` describe a valid,
+/// self-contained kernel image at the physical addresses it expects to run from; otherwise the
+/// machine executes arbitrary bytes at the subsequent [`kexec_reboot`].
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying `kexec_load` syscall.
+/// Notably, [`Errno::Eperm`] is returned if the caller lacks the privilege to load kernels, and
+/// [`Errno::Einval`] if `flags` is invalid or there are too many segments.
+pub unsafe fn kexec_load(
+    entry: *const u8,
+    segments: &[KexecSegment],
+    flags: usize,
+) -> Result<(), Errno> {
+    // SAFETY: The `KexecSegment` type matches the layout expected by `kexec_load`, and the
+    // segment slice outlives the syscall. The caller guarantees the image itself is sane.
+    unsafe {
+        syscall_result!(
+            SyscallNum::KexecLoad,
+            entry,
+            segments.len(),
+            segments.as_ptr() as usize,
+            flags
+        )?;
+    }
+    Ok(())
+}
+
+/// Attempts to reboot directly into the kernel image loaded by [`kexec_load`], skipping the
+/// firmware and boot loader.
+///
+/// # Errors
+///
+/// This function returns [`Errno::Eperm`] if the caller has insufficient privileges to reboot the
+/// system, and [`Errno::Einval`] if no kernel image has been loaded.
+///
+/// # Panics
+///
+/// This function panics if the underlying system call somehow returns a success but fails to
+/// reboot the system.
+pub fn kexec_reboot() -> Result<!, Errno> {
+    reboot_syscall(RebootCmd::Kexec)
+}
+
 /// Wrapper for the [reboot](https://man7.org/linux/man-pages/man2/reboot.2.html) syscall.
 ///
 /// Performs the given [`RebootCmd`].
@@ -84,6 +444,8 @@ fn reboot_syscall(operation: RebootCmd) -> Result<!, Errno> {
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
+
     use super::*;
     use crate::assert_err;
 
@@ -96,4 +458,99 @@ mod tests {
     fn power_off_eperm() {
         assert_err!(power_off(), Errno::Eperm);
     }
+
+    #[test_case]
+    fn realtime_clock_past_2020() {
+        // The realtime clock reports seconds since the Unix epoch, so any sane system clock lands
+        // well past the start of 2020.
+        let now = clock_gettime(ClockId::Realtime).unwrap();
+        assert!(now.as_secs() > 1_577_836_800);
+    }
+
+    #[test_case]
+    fn monotonic_clock_never_goes_backwards() {
+        let first = clock_gettime(ClockId::Monotonic).unwrap();
+        let second = clock_gettime(ClockId::Monotonic).unwrap();
+        assert!(second >= first);
+    }
+
+    #[test_case]
+    fn uname_linux_x86_64() {
+        // tlenix is built on the Linux kernel and only targets x86_64, so both of these are
+        // invariants rather than assumptions about the test machine.
+        let uts = uname().unwrap();
+        assert_eq!(&uts.sysname, "Linux");
+        assert_eq!(&uts.machine, "x86_64");
+        assert!(!uts.release.is_empty());
+    }
+
+    #[test_case]
+    fn get_hostname_matches_uname() {
+        let hostname = get_hostname().unwrap();
+        assert!(!hostname.is_empty());
+        assert_eq!(hostname, uname().unwrap().nodename);
+    }
+
+    #[test_case]
+    fn set_hostname_requires_privilege() {
+        // Setting the hostname to its current value is a no-op even if it succeeds, so this is
+        // safe to run privileged too.
+        let current = get_hostname().unwrap();
+        assert!(matches!(set_hostname(current), Ok(()) | Err(Errno::Eperm)));
+    }
+
+    #[test_case]
+    fn sysinfo_sane_values() {
+        let info = sysinfo().unwrap();
+
+        // A machine that's been up for zero seconds or has no RAM couldn't be running this test,
+        // so these catch a mislaid struct field.
+        assert!(info.uptime > Duration::ZERO);
+        assert!(info.total_ram > 0);
+        assert!(info.free_ram <= info.total_ram);
+        assert!(info.procs > 0);
+    }
+
+    #[test_case]
+    fn getrandom_fills_and_differs() {
+        let mut first = [0_u8; 32];
+        let mut second = [0_u8; 32];
+        assert_eq!(getrandom(&mut first, GetRandomFlags::default()), Ok(32));
+        assert_eq!(getrandom(&mut second, GetRandomFlags::default()), Ok(32));
+
+        // Two independent 32-byte draws colliding is (256^32)-to-one against; a collision here
+        // means the buffer isn't actually being filled with randomness.
+        assert_ne!(first, second);
+    }
+
+    #[test_case]
+    fn getrandom_nonblock() {
+        // The `urandom` source never blocks once the entropy pool is initialized (which it is,
+        // long before userspace runs), so non-blocking draws succeed too.
+        let mut buf = [0_u8; 8];
+        assert_eq!(getrandom(&mut buf, GetRandomFlags::NONBLOCK), Ok(8));
+    }
+
+    #[test_case]
+    fn kexec_load_rejected() {
+        let segment = KexecSegment {
+            buf: core::ptr::null(),
+            bufsz: 0,
+            mem: core::ptr::null(),
+            memsz: 0,
+        };
+
+        // Exercise the signatures without ever loading anything: unprivileged callers are turned
+        // away with `Eperm`, and privileged ones reject the undefined flag bit with `Einval`.
+        // SAFETY: The undefined flag bit guarantees the kernel refuses to load the "image".
+        let result = unsafe { kexec_load(core::ptr::null(), &[segment], 1 << 8) };
+        assert!(matches!(result, Err(Errno::Eperm | Errno::Einval)));
+    }
+
+    #[test_case]
+    fn kexec_reboot_rejected() {
+        // No image is ever loaded in the test environment, so this can't actually reboot:
+        // unprivileged callers get `Eperm`, privileged ones `Einval`.
+        assert!(matches!(kexec_reboot(), Err(Errno::Eperm | Errno::Einval)));
+    }
 }
@@ -0,0 +1,780 @@
+//! Functionality related to terminal control.
+
+use alloc::format;
+use core::time::Duration;
+
+use crate::{
+    Console, Errno, SyscallNum,
+    fs::{File, OpenOptions},
+    syscall_result,
+};
+
+/// `ioctl` request to get the current terminal attributes.
+const TCGETS: usize = 0x5401;
+/// `ioctl` request to set the terminal attributes immediately.
+const TCSETS: usize = 0x5402;
+/// `ioctl` request to get the terminal window size.
+const TIOCGWINSZ: usize = 0x5413;
+/// `ioctl` request to get a pseudoterminal master's slave number.
+const TIOCGPTN: usize = 0x8004_5430;
+/// `ioctl` request to lock/unlock a pseudoterminal master's slave.
+const TIOCSPTLCK: usize = 0x4004_5431;
+
+/// Path to the pseudoterminal multiplexer device.
+const PTMX_PATH: &str = "/dev/ptmx";
+
+/// The number of control characters in the kernel `termios` struct.
+const NCCS: usize = 19;
+
+/// Index of the `VTIME` control character: the timeout (in deciseconds) for non-canonical reads.
+const VTIME: usize = 5;
+/// Index of the `VMIN` control character: the minimum number of characters for non-canonical
+/// reads.
+const VMIN: usize = 6;
+
+bitflags::bitflags! {
+    /// Input mode flags of a [`Termios`]. See
+    /// [`termios(3)`](https://www.man7.org/linux/man-pages/man3/termios.3.html) for more details.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct InputFlags: u32 {
+        /// Ignore BREAK condition on input.
+        const IGNBRK = 0o1;
+        /// A BREAK condition causes a `SIGINT` to be sent.
+        const BRKINT = 0o2;
+        /// Ignore framing and parity errors.
+        const IGNPAR = 0o4;
+        /// Mark bytes with parity or framing errors.
+        const PARMRK = 0o10;
+        /// Enable input parity checking.
+        const INPCK = 0o20;
+        /// Strip off the eighth bit.
+        const ISTRIP = 0o40;
+        /// Translate NL to CR on input.
+        const INLCR = 0o100;
+        /// Ignore carriage return on input.
+        const IGNCR = 0o200;
+        /// Translate carriage return to newline on input.
+        const ICRNL = 0o400;
+        /// Enable XON/XOFF flow control on output.
+        const IXON = 0o2000;
+        /// Enable XON/XOFF flow control on input.
+        const IXOFF = 0o10000;
+    }
+}
+
+bitflags::bitflags! {
+    /// Output mode flags of a [`Termios`]. See
+    /// [`termios(3)`](https://www.man7.org/linux/man-pages/man3/termios.3.html) for more details.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct OutputFlags: u32 {
+        /// Enable implementation-defined output processing.
+        const OPOST = 0o1;
+        /// Map NL to CR-NL on output.
+        const ONLCR = 0o4;
+    }
+}
+
+bitflags::bitflags! {
+    /// Control mode flags of a [`Termios`]. See
+    /// [`termios(3)`](https://www.man7.org/linux/man-pages/man3/termios.3.html) for more details.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct ControlFlags: u32 {
+        /// Character size mask.
+        const CSIZE = 0o60;
+        /// 8-bit characters.
+        const CS8 = 0o60;
+        /// Enable parity generation on output and parity checking for input.
+        const PARENB = 0o400;
+    }
+}
+
+bitflags::bitflags! {
+    /// Local mode flags of a [`Termios`]. See
+    /// [`termios(3)`](https://www.man7.org/linux/man-pages/man3/termios.3.html) for more details.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct LocalFlags: u32 {
+        /// Generate the corresponding signals when `INTR`, `QUIT`, `SUSP`, or `DSUSP` characters
+        /// are received.
+        const ISIG = 0o1;
+        /// Enable canonical (line-buffered) mode.
+        const ICANON = 0o2;
+        /// Echo input characters.
+        const ECHO = 0o10;
+        /// If [`LocalFlags::ICANON`] is also set, the ERASE character erases the preceding input
+        /// character.
+        const ECHOE = 0o20;
+        /// If [`LocalFlags::ICANON`] is also set, the KILL character erases the current line.
+        const ECHOK = 0o40;
+        /// If [`LocalFlags::ICANON`] is also set, echo the NL character even if ECHO is not set.
+        const ECHONL = 0o100;
+        /// Enable implementation-defined input processing.
+        const IEXTEN = 0o100_000;
+    }
+}
+
+/// The attributes of a terminal. Directly corresponds to the kernel `termios` struct used by the
+/// [`TCGETS`/`TCSETS`](https://www.man7.org/linux/man-pages/man2/TCSETS.2const.html) `ioctl`
+/// requests.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct Termios {
+    /// Input mode flags. Interpreted through [`InputFlags`].
+    pub input_flags: u32,
+    /// Output mode flags. Interpreted through [`OutputFlags`].
+    pub output_flags: u32,
+    /// Control mode flags. Interpreted through [`ControlFlags`].
+    pub control_flags: u32,
+    /// Local mode flags. Interpreted through [`LocalFlags`].
+    pub local_flags: u32,
+    /// Line discipline.
+    pub line_discipline: u8,
+    /// Control characters.
+    pub control_chars: [u8; NCCS],
+}
+
+/// The size of a terminal window. Directly corresponds to the kernel `winsize` struct used by the
+/// [`TIOCGWINSZ`](https://www.man7.org/linux/man-pages/man2/TIOCGWINSZ.2const.html) `ioctl`
+/// request.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct WindowSize {
+    /// The number of rows of the window.
+    pub rows: u16,
+    /// The number of columns of the window.
+    pub cols: u16,
+    /// The width of the window in pixels (unused by the kernel).
+    pub x_pixels: u16,
+    /// The height of the window in pixels (unused by the kernel).
+    pub y_pixels: u16,
+}
+
+/// Gets the [`WindowSize`] of the given terminal [`File`].
+///
+/// Wrapper around the
+/// [`TIOCGWINSZ`](https://www.man7.org/linux/man-pages/man2/TIOCGWINSZ.2const.html) `ioctl`
+/// request.
+///
+/// # Errors
+///
+/// This function returns [`Errno::Enotty`] if the given [`File`] is not a terminal.
+///
+/// This function propagates any other [`Errno`]s returned by the underlying `ioctl` call.
+pub fn window_size(file: &File) -> Result<WindowSize, Errno> {
+    let mut window_size = WindowSize::default();
+
+    // SAFETY: The `WindowSize` type matches the layout expected by `TIOCGWINSZ`. The raw pointer
+    // to `window_size` is dropped right after the syscall.
+    unsafe {
+        syscall_result!(
+            SyscallNum::Ioctl,
+            file.file_descriptor(),
+            TIOCGWINSZ,
+            &raw mut window_size
+        )?;
+    }
+
+    Ok(window_size)
+}
+
+/// Allocates a pseudoterminal pair, returning the `(master, slave)` [`File`]s.
+///
+/// Opens the [`/dev/ptmx` multiplexer](https://www.man7.org/linux/man-pages/man4/pts.4.html) for
+/// the master end, unlocks the slave via the `TIOCSPTLCK` `ioctl` request, and opens the
+/// corresponding `/dev/pts/<n>` device (looked up via `TIOCGPTN`) for the slave end. Bytes written
+/// to the master appear as input on the slave, and vice versa.
+///
+/// Neither end becomes the calling process's controlling terminal; a child that wants the slave as
+/// its controlling terminal should start a new session and reopen it.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying `open` and `ioctl` calls.
+/// Notably, [`Errno::Enoent`] is returned if the `devpts` filesystem isn't mounted.
+pub fn openpty() -> Result<(File, File), Errno> {
+    let master = OpenOptions::new()
+        .read_write()
+        .no_controlling_terminal(true)
+        .open(PTMX_PATH)?;
+
+    // Look up which `/dev/pts/<n>` device belongs to this master.
+    let mut pty_num = 0_u32;
+    // SAFETY: `TIOCGPTN` expects a pointer to a C `unsigned int`. The raw pointer to `pty_num` is
+    // dropped right after the syscall.
+    unsafe {
+        syscall_result!(
+            SyscallNum::Ioctl,
+            master.file_descriptor(),
+            TIOCGPTN,
+            &raw mut pty_num as usize
+        )?;
+    }
+
+    // Unlock the slave; it can't be opened until this is done.
+    let mut unlock = 0_i32;
+    // SAFETY: `TIOCSPTLCK` expects a pointer to a C `int` (zero meaning "unlock"). The raw pointer
+    // to `unlock` is dropped right after the syscall.
+    unsafe {
+        syscall_result!(
+            SyscallNum::Ioctl,
+            master.file_descriptor(),
+            TIOCSPTLCK,
+            &raw mut unlock as usize
+        )?;
+    }
+
+    let slave = OpenOptions::new()
+        .read_write()
+        .no_controlling_terminal(true)
+        .open(format!("/dev/pts/{pty_num}"))?;
+
+    Ok((master, slave))
+}
+
+/// The Device Status Report escape sequence asking the terminal to report its cursor position.
+const DSR_CURSOR_REQUEST: &[u8] = b"\x1b[6n";
+/// How long to wait for each byte of the DSR reply before giving up.
+const DSR_REPLY_TIMEOUT: Duration = Duration::from_millis(100);
+/// The longest well-formed DSR reply: `ESC [ 65535 ; 65535 R`.
+const DSR_REPLY_MAX_LEN: usize = 14;
+
+/// Gets the current cursor position of the given [`Console`] as a 1-based `(row, column)` pair.
+///
+/// Writes the `ESC [ 6 n` Device Status Report request to the console and parses the terminal's
+/// `ESC [ row ; col R` reply from the console input. Since the reply arrives as ordinary input
+/// bytes, the console should be out of canonical mode (and ideally have no pending input) when
+/// this is called.
+///
+/// # Errors
+///
+/// This function returns [`Errno::Etime`] if the terminal doesn't reply in time, and
+/// [`Errno::Einval`] if the reply is malformed.
+///
+/// Any [`Errno`]s returned while reading from or writing to the console are propagated.
+pub fn cursor_position(console: &Console) -> Result<(u16, u16), Errno> {
+    for &byte in DSR_CURSOR_REQUEST {
+        console.write_byte(byte)?;
+    }
+
+    let mut reply = [0_u8; DSR_REPLY_MAX_LEN];
+    let mut len = 0;
+    while len < reply.len() {
+        let Some(byte) = console.read_byte_timeout(DSR_REPLY_TIMEOUT)? else {
+            return Err(Errno::Etime);
+        };
+        reply[len] = byte;
+        len += 1;
+        if byte == b'R' {
+            return parse_dsr_reply(&reply[..len]);
+        }
+    }
+
+    // The reply never terminated within the longest well-formed length.
+    Err(Errno::Einval)
+}
+
+/// Parses a Device Status Report reply of the form `ESC [ row ; col R` into a `(row, column)`
+/// pair.
+fn parse_dsr_reply(bytes: &[u8]) -> Result<(u16, u16), Errno> {
+    let inner = bytes
+        .strip_prefix(b"\x1b[")
+        .and_then(|rest| rest.strip_suffix(b"R"))
+        .ok_or(Errno::Einval)?;
+    let text = core::str::from_utf8(inner).map_err(|_| Errno::Einval)?;
+    let (row, col) = text.split_once(';').ok_or(Errno::Einval)?;
+    Ok((
+        row.parse().map_err(|_| Errno::Einval)?,
+        col.parse().map_err(|_| Errno::Einval)?,
+    ))
+}
+
+/// Terminal mode presets usable with [`set_mode`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TermMode {
+    /// Canonical ("cooked") mode: input is line-buffered, echoed, and signal characters generate
+    /// signals.
+    Cooked,
+    /// Raw mode: input is available byte-by-byte, echoing is disabled, and all special processing
+    /// of input and output is disabled.
+    Raw,
+    /// Cbreak ("rare") mode: input is available byte-by-byte and echoing is disabled, but signal
+    /// characters (e.g. Ctrl-C) still work.
+    Cbreak,
+}
+
+/// Gets the current [`Termios`] attributes of the given terminal [`File`].
+///
+/// Wrapper around the [`TCGETS`](https://www.man7.org/linux/man-pages/man2/TCSETS.2const.html)
+/// `ioctl` request.
+///
+/// # Errors
+///
+/// This function returns [`Errno::Enotty`] if the given [`File`] is not a terminal.
+///
+/// This function propagates any other [`Errno`]s returned by the underlying `ioctl` call.
+pub fn tcgetattr(file: &File) -> Result<Termios, Errno> {
+    let mut termios = Termios::default();
+
+    // SAFETY: The `Termios` type matches the layout expected by `TCGETS`. The raw pointer to
+    // `termios` is dropped right after the syscall.
+    unsafe {
+        syscall_result!(
+            SyscallNum::Ioctl,
+            file.file_descriptor(),
+            TCGETS,
+            &raw mut termios
+        )?;
+    }
+
+    Ok(termios)
+}
+
+/// Sets the [`Termios`] attributes of the given terminal [`File`] immediately.
+///
+/// Wrapper around the [`TCSETS`](https://www.man7.org/linux/man-pages/man2/TCSETS.2const.html)
+/// `ioctl` request.
+///
+/// # Errors
+///
+/// This function returns [`Errno::Enotty`] if the given [`File`] is not a terminal.
+///
+/// This function propagates any other [`Errno`]s returned by the underlying `ioctl` call.
+pub fn tcsetattr(file: &File, termios: &Termios) -> Result<(), Errno> {
+    // SAFETY: The `Termios` type matches the layout expected by `TCSETS`. The raw pointer to
+    // `termios` is dropped right after the syscall.
+    unsafe {
+        syscall_result!(
+            SyscallNum::Ioctl,
+            file.file_descriptor(),
+            TCSETS,
+            core::ptr::from_ref(termios)
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Puts the given terminal [`File`] into the given [`TermMode`], returning the previous
+/// [`Termios`] so callers can restore it later via [`tcsetattr`].
+///
+/// # Errors
+///
+/// This function returns [`Errno::Enotty`] if the given [`File`] is not a terminal.
+///
+/// This function propagates any other [`Errno`]s returned by the underlying calls to [`tcgetattr`]
+/// and [`tcsetattr`].
+pub fn set_mode(file: &File, mode: TermMode) -> Result<Termios, Errno> {
+    let saved = tcgetattr(file)?;
+
+    let mut termios = saved;
+    match mode {
+        TermMode::Cooked => {
+            termios.input_flags |= (InputFlags::ICRNL | InputFlags::IXON).bits();
+            termios.output_flags |= (OutputFlags::OPOST | OutputFlags::ONLCR).bits();
+            termios.local_flags |= (LocalFlags::ISIG
+                | LocalFlags::ICANON
+                | LocalFlags::ECHO
+                | LocalFlags::ECHOE
+                | LocalFlags::ECHOK
+                | LocalFlags::IEXTEN)
+                .bits();
+        }
+        TermMode::Raw => {
+            termios.input_flags &= !(InputFlags::IGNBRK
+                | InputFlags::BRKINT
+                | InputFlags::PARMRK
+                | InputFlags::ISTRIP
+                | InputFlags::INLCR
+                | InputFlags::IGNCR
+                | InputFlags::ICRNL
+                | InputFlags::IXON)
+                .bits();
+            termios.output_flags &= !OutputFlags::OPOST.bits();
+            termios.control_flags &= !(ControlFlags::CSIZE | ControlFlags::PARENB).bits();
+            termios.control_flags |= ControlFlags::CS8.bits();
+            termios.local_flags &= !(LocalFlags::ISIG
+                | LocalFlags::ICANON
+                | LocalFlags::ECHO
+                | LocalFlags::ECHONL
+                | LocalFlags::IEXTEN)
+                .bits();
+            termios.control_chars[VMIN] = 1;
+            termios.control_chars[VTIME] = 0;
+        }
+        TermMode::Cbreak => {
+            // Keep ISIG so that e.g. Ctrl-C still works.
+            termios.local_flags &= !(LocalFlags::ICANON | LocalFlags::ECHO).bits();
+            termios.control_chars[VMIN] = 1;
+            termios.control_chars[VTIME] = 0;
+        }
+    }
+
+    tcsetattr(file, &termios)?;
+
+    Ok(saved)
+}
+
+/// A single decoded keypress, as produced by [`KeyReader`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// A regular (possibly multi-byte UTF-8) character.
+    Char(char),
+    /// The up arrow key.
+    Up,
+    /// The down arrow key.
+    Down,
+    /// The left arrow key.
+    Left,
+    /// The right arrow key.
+    Right,
+    /// The Home key.
+    Home,
+    /// The End key.
+    End,
+    /// The Page Up key.
+    PageUp,
+    /// The Page Down key.
+    PageDown,
+    /// The Insert key.
+    Insert,
+    /// The Delete key.
+    Delete,
+    /// The Backspace key.
+    Backspace,
+    /// The Enter/Return key.
+    Enter,
+    /// The Tab key.
+    Tab,
+    /// Ctrl-C (ETX).
+    CtrlC,
+    /// Ctrl-D (EOT).
+    CtrlD,
+    /// A lone Escape keypress.
+    Esc,
+    /// The end of the input was reached.
+    Eof,
+}
+
+/// Decodes raw terminal input bytes — including multi-byte escape sequences — into [`Key`] events.
+///
+/// The underlying [`File`] is typically a terminal in [`TermMode::Raw`] or [`TermMode::Cbreak`],
+/// but any byte source works, which keeps the decoder testable against regular files.
+#[derive(Debug)]
+pub struct KeyReader<'a> {
+    file: &'a File,
+}
+impl<'a> KeyReader<'a> {
+    /// Creates a [`KeyReader`] decoding key events from the given [`File`].
+    #[must_use]
+    pub const fn new(file: &'a File) -> Self {
+        Self { file }
+    }
+
+    /// Reads and decodes the next [`Key`] from the underlying [`File`], blocking until a full key
+    /// is available. Returns [`Key::Eof`] if the input ends.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`Errno::Eilseq`] if the input contains invalid UTF-8.
+    ///
+    /// Any [`Errno`]s returned while reading from the underlying [`File`] are propagated.
+    pub fn next_key(&self) -> Result<Key, Errno> {
+        let Some(byte) = self.file.read_byte()? else {
+            return Ok(Key::Eof);
+        };
+
+        Ok(match byte {
+            b'\r' | b'\n' => Key::Enter,
+            b'\t' => Key::Tab,
+            // ETX; the byte Ctrl-C produces.
+            0x03 => Key::CtrlC,
+            // EOT; the byte Ctrl-D produces.
+            0x04 => Key::CtrlD,
+            // DEL; the byte the Backspace key produces on modern terminals.
+            0x7f => Key::Backspace,
+            // ESC; either a lone Escape keypress or the start of an escape sequence.
+            0x1b => self.escape_sequence()?,
+            _ => self.utf8_char(byte)?,
+        })
+    }
+
+    /// Decodes the remainder of an escape sequence after the initial `ESC` byte. A lone `ESC`
+    /// (i.e. one followed by the end of the input) decodes to [`Key::Esc`], as does any
+    /// unrecognized sequence.
+    fn escape_sequence(&self) -> Result<Key, Errno> {
+        // Both CSI (`ESC [`) and SS3 (`ESC O`) sequences are decoded; terminals differ in which
+        // they send for Home/End.
+        match self.file.read_byte()? {
+            Some(b'[' | b'O') => {}
+            _ => return Ok(Key::Esc),
+        }
+
+        let Some(byte) = self.file.read_byte()? else {
+            return Ok(Key::Esc);
+        };
+        Ok(match byte {
+            b'A' => Key::Up,
+            b'B' => Key::Down,
+            b'C' => Key::Right,
+            b'D' => Key::Left,
+            b'H' => Key::Home,
+            b'F' => Key::End,
+            // `ESC [ n ~` sequences.
+            b'1'..=b'8' => match (byte, self.file.read_byte()?) {
+                (b'1' | b'7', Some(b'~')) => Key::Home,
+                (b'2', Some(b'~')) => Key::Insert,
+                (b'3', Some(b'~')) => Key::Delete,
+                (b'4' | b'8', Some(b'~')) => Key::End,
+                (b'5', Some(b'~')) => Key::PageUp,
+                (b'6', Some(b'~')) => Key::PageDown,
+                _ => Key::Esc,
+            },
+            _ => Key::Esc,
+        })
+    }
+
+    /// Decodes a UTF-8 character beginning with the given byte, reading any continuation bytes
+    /// from the underlying [`File`].
+    fn utf8_char(&self, first: u8) -> Result<Key, Errno> {
+        let mut buffer = [first, 0, 0, 0];
+
+        // The leading byte determines the total length of the encoded character.
+        let len = match first {
+            0x00..=0x7f => 1,
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf7 => 4,
+            _ => return Err(Errno::Eilseq),
+        };
+        for slot in buffer.iter_mut().take(len).skip(1) {
+            *slot = self.file.read_byte()?.ok_or(Errno::Eilseq)?;
+        }
+
+        core::str::from_utf8(&buffer[..len])
+            .map_err(|_| Errno::Eilseq)?
+            .chars()
+            .next()
+            .map(Key::Char)
+            .ok_or(Errno::Eilseq)
+    }
+}
+
+/// Puts a terminal [`File`] into [`TermMode::Raw`] for as long as it exists, restoring the
+/// original [`Termios`] attributes when dropped.
+#[derive(Debug)]
+pub struct RawModeGuard<'a> {
+    file: &'a File,
+    saved: Termios,
+}
+impl<'a> RawModeGuard<'a> {
+    /// Puts the given terminal [`File`] into [`TermMode::Raw`], remembering the previous
+    /// [`Termios`] attributes so they can be restored on drop.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any [`Errno`]s returned by the underlying call to [`set_mode`].
+    pub fn new(file: &'a File) -> Result<Self, Errno> {
+        let saved = set_mode(file, TermMode::Raw)?;
+        Ok(Self { file, saved })
+    }
+}
+impl Drop for RawModeGuard<'_> {
+    fn drop(&mut self) {
+        // Last-ditch effort to restore the original terminal attributes; nothing to be done if it
+        // fails.
+        let _ = tcsetattr(self.file, &self.saved);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::{assert_err, fs::OpenOptions};
+
+    const TTY_PATH: &str = "/dev/tty";
+
+    fn tty() -> File {
+        OpenOptions::new().read_write().open(TTY_PATH).unwrap()
+    }
+
+    #[test_case]
+    fn raw_clears_flags() {
+        let tty = tty();
+        let saved = set_mode(&tty, TermMode::Raw).unwrap();
+        let current = tcgetattr(&tty).unwrap();
+
+        // Restore the original attributes before possibly panicking!
+        let restore_result = tcsetattr(&tty, &saved);
+
+        assert_eq!(
+            current.local_flags
+                & (LocalFlags::ICANON | LocalFlags::ECHO | LocalFlags::ISIG).bits(),
+            0
+        );
+        assert_eq!(current.output_flags & OutputFlags::OPOST.bits(), 0);
+        restore_result.unwrap();
+    }
+
+    #[test_case]
+    fn cbreak_keeps_isig() {
+        let tty = tty();
+        let saved = set_mode(&tty, TermMode::Cbreak).unwrap();
+        let current = tcgetattr(&tty).unwrap();
+
+        // Restore the original attributes before possibly panicking!
+        let restore_result = tcsetattr(&tty, &saved);
+
+        assert_eq!(
+            current.local_flags & (LocalFlags::ICANON | LocalFlags::ECHO).bits(),
+            0
+        );
+        assert_eq!(
+            current.local_flags & LocalFlags::ISIG.bits(),
+            LocalFlags::ISIG.bits()
+        );
+        restore_result.unwrap();
+    }
+
+    #[test_case]
+    fn cooked_restores_canonical() {
+        let tty = tty();
+        let saved = set_mode(&tty, TermMode::Raw).unwrap();
+        set_mode(&tty, TermMode::Cooked).unwrap();
+        let current = tcgetattr(&tty).unwrap();
+
+        // Restore the original attributes before possibly panicking!
+        let restore_result = tcsetattr(&tty, &saved);
+
+        let canonical = (LocalFlags::ICANON | LocalFlags::ECHO | LocalFlags::ISIG).bits();
+        assert_eq!(current.local_flags & canonical, canonical);
+        restore_result.unwrap();
+    }
+
+    #[test_case]
+    fn set_mode_returns_previous() {
+        let tty = tty();
+        let orig = tcgetattr(&tty).unwrap();
+        let saved = set_mode(&tty, TermMode::Raw).unwrap();
+
+        // Restore the original attributes before possibly panicking!
+        let restore_result = tcsetattr(&tty, &orig);
+
+        assert_eq!(saved, orig);
+        restore_result.unwrap();
+    }
+
+    #[test_case]
+    fn key_reader_decodes_sequences() {
+        const PATH: &str = "/tmp/tlenix_key_reader_test";
+        // A mix of plain characters, control bytes, and escape sequences.
+        const INPUT: &[u8] = b"a\x1b[A\x1b[B\x1b[C\x1b[D\x7f\x03\r\t\x1b[H\x1bOF\x1b[3~\x1b[5~\x1b";
+
+        let _ = crate::fs::rm(PATH);
+        let file = OpenOptions::new()
+            .read_write()
+            .create(true)
+            .open(PATH)
+            .unwrap();
+        file.write(INPUT).unwrap();
+        file.set_cursor(0).unwrap();
+
+        let reader = KeyReader::new(&file);
+        let expected = [
+            Key::Char('a'),
+            Key::Up,
+            Key::Down,
+            Key::Right,
+            Key::Left,
+            Key::Backspace,
+            Key::CtrlC,
+            Key::Enter,
+            Key::Tab,
+            Key::Home,
+            Key::End,
+            Key::Delete,
+            Key::PageUp,
+            // A trailing lone ESC decodes as an Escape keypress.
+            Key::Esc,
+            Key::Eof,
+        ];
+        for key in expected {
+            assert_eq!(reader.next_key().unwrap(), key);
+        }
+
+        drop(file);
+        crate::fs::rm(PATH).unwrap();
+    }
+
+    #[test_case]
+    fn key_reader_decodes_utf8() {
+        const PATH: &str = "/tmp/tlenix_key_reader_utf8_test";
+        const INPUT: &str = "马é";
+
+        let _ = crate::fs::rm(PATH);
+        let file = OpenOptions::new()
+            .read_write()
+            .create(true)
+            .open(PATH)
+            .unwrap();
+        file.write(INPUT.as_bytes()).unwrap();
+        file.set_cursor(0).unwrap();
+
+        let reader = KeyReader::new(&file);
+        assert_eq!(reader.next_key().unwrap(), Key::Char('马'));
+        assert_eq!(reader.next_key().unwrap(), Key::Char('é'));
+        assert_eq!(reader.next_key().unwrap(), Key::Eof);
+
+        // A lone continuation byte is invalid UTF-8.
+        file.set_cursor(0).unwrap();
+        file.write(&[0x80]).unwrap();
+        file.set_cursor(0).unwrap();
+        assert_eq!(reader.next_key(), Err(Errno::Eilseq));
+
+        drop(file);
+        crate::fs::rm(PATH).unwrap();
+    }
+
+    #[test_case]
+    fn dsr_reply_parsed() {
+        assert_eq!(parse_dsr_reply(b"\x1b[12;34R").unwrap(), (12, 34));
+        assert_eq!(parse_dsr_reply(b"\x1b[1;1R").unwrap(), (1, 1));
+    }
+
+    #[test_case]
+    fn dsr_reply_malformed() {
+        // Missing terminator, missing prefix, missing column, and non-numeric fields.
+        assert_err!(parse_dsr_reply(b"\x1b[12;34"), Errno::Einval);
+        assert_err!(parse_dsr_reply(b"12;34R"), Errno::Einval);
+        assert_err!(parse_dsr_reply(b"\x1b[12R"), Errno::Einval);
+        assert_err!(parse_dsr_reply(b"\x1b[a;bR"), Errno::Einval);
+        assert_err!(parse_dsr_reply(b""), Errno::Einval);
+    }
+
+    #[test_case]
+    fn openpty_round_trip() {
+        let (master, slave) = openpty().unwrap();
+
+        // Both ends are real terminals.
+        tcgetattr(&master).unwrap();
+        tcgetattr(&slave).unwrap();
+
+        // Bytes written to the master appear as input on the slave. The newline matters: the
+        // slave starts out in canonical mode, so its input is line-buffered.
+        master.write(b"hi\n").unwrap();
+        let mut buffer = [0_u8; 3];
+        slave.read_exact(&mut buffer).unwrap();
+        assert_eq!(&buffer, b"hi\n");
+    }
+
+    #[test_case]
+    fn raw_mode_guard_restores_on_drop() {
+        let tty = tty();
+        let orig = tcgetattr(&tty).unwrap();
+        {
+            let _guard = RawModeGuard::new(&tty).unwrap();
+            let raw = tcgetattr(&tty).unwrap();
+            assert_eq!(raw.local_flags & LocalFlags::ICANON.bits(), 0);
+        }
+        assert_eq!(tcgetattr(&tty).unwrap(), orig);
+    }
+}
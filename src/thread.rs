@@ -1,14 +1,57 @@
 //! Thread control.
 
-use core::time::Duration;
+use alloc::boxed::Box;
+use core::{
+    sync::atomic::{AtomicI32, Ordering},
+    time::Duration,
+};
 
-use crate::{Errno, SyscallNum, syscall_result};
+use crate::{Errno, SyscallNum, syscall, syscall_result};
+
+mod mutex;
+
+// RE-EXPORTS
+pub use mutex::{Mutex, MutexGuard};
 
 /// Intel 8253/8254 sends an IRQ0 (timer interrupt) once every ~52.9254 ms.
 ///
 /// This is used for sleep loop timing.
 pub const PIT_IRQ_PERIOD: u64 = 54_925_400;
 
+/// The size (in bytes) of each spawned thread's stack.
+const THREAD_STACK_SIZE: usize = 1 << 20; // 1 MiB
+
+/// `mmap` protection bit allowing reads.
+const PROT_READ: usize = 0x1;
+/// `mmap` protection bit allowing writes.
+const PROT_WRITE: usize = 0x2;
+/// `mmap` flag for a private copy-on-write mapping.
+const MAP_PRIVATE: usize = 0x02;
+/// `mmap` flag for a mapping backed by zeroed pages instead of a file.
+const MAP_ANONYMOUS: usize = 0x20;
+/// `mmap` flag marking the mapping as a thread stack.
+const MAP_STACK: usize = 0x2_0000;
+
+/// `clone` flag: share the parent's address space.
+const CLONE_VM: u64 = 0x100;
+/// `clone` flag: share the parent's filesystem information (root, CWD, umask).
+const CLONE_FS: u64 = 0x200;
+/// `clone` flag: share the parent's file descriptor table.
+const CLONE_FILES: u64 = 0x400;
+/// `clone` flag: share the parent's signal handlers.
+const CLONE_SIGHAND: u64 = 0x800;
+/// `clone` flag: place the child in the parent's thread group.
+const CLONE_THREAD: u64 = 0x1_0000;
+/// `clone` flag: clear the child TID word and wake its futex when the child exits.
+const CLONE_CHILD_CLEARTID: u64 = 0x20_0000;
+
+/// `futex` operation: wait while the futex word holds the given value.
+const FUTEX_WAIT: usize = 0;
+/// `futex` operation: wake up to the given number of waiters on the futex word.
+const FUTEX_WAKE: usize = 1;
+/// `futex` operation bit: the futex is private to this process.
+const FUTEX_PRIVATE_FLAG: usize = 128;
+
 /// Corresponds to the [timespec](https://www.man7.org/linux/man-pages/man3/timespec.3type.html)
 /// type in C.
 #[derive(Debug, Default)]
@@ -29,6 +72,195 @@ impl From<&Duration> for Timespec {
     }
 }
 
+// `clone` can't be wrapped like other syscalls: the child wakes up on a brand-new stack, so it
+// must immediately jump to its entry point instead of returning into the caller's (now
+// meaningless) stack frame. The entry point and its argument are stashed on the child stack before
+// the syscall; the child pops them off and calls through.
+core::arch::global_asm! {
+    ".global __clone_thread",
+    "__clone_thread:",
+    // Arguments: rdi = flags, rsi = child stack top, rdx = child TID pointer, rcx = entry point,
+    // r8 = entry argument.
+    "sub rsi, 16",
+    "mov [rsi], rcx",
+    "mov [rsi + 8], r8",
+    // Rearrange into `clone` syscall argument registers: the parent TID pointer (rdx) and TLS
+    // (r8) are unused, and the child TID pointer belongs in r10.
+    "mov r10, rdx",
+    "xor edx, edx",
+    "xor r8d, r8d",
+    "mov rax, 56",
+    "syscall",
+    "test rax, rax",
+    "jnz 2f",
+    // Child: call the entry point with its argument. The entry point never returns.
+    "pop rax",
+    "pop rdi",
+    "call rax",
+    "ud2",
+    // Parent: hand the kernel's return value (child TID or negated errno) back to the caller.
+    "2:",
+    "ret",
+}
+unsafe extern "C" {
+    /// Invokes the `clone` syscall, starting the child at the given entry point on the given
+    /// stack. Returns the raw syscall return value in the parent.
+    fn __clone_thread(
+        flags: u64,
+        stack_top: *mut u8,
+        child_tid: *mut i32,
+        entry: extern "C" fn(*mut u8) -> !,
+        arg: *mut u8,
+    ) -> usize;
+}
+
+/// The first Rust code executed by a spawned thread. Runs the boxed closure from [`spawn`], then
+/// exits the thread directly so the parent's stack frames and destructors are never touched.
+extern "C" fn thread_entry(arg: *mut u8) -> ! {
+    {
+        // OK to allow this; the pointer originated as a properly-aligned `Box` pointer in
+        // `spawn` and only crossed the `extern "C"` boundary as `*mut u8`.
+        #[allow(clippy::cast_ptr_alignment)]
+        // SAFETY: `arg` came from `Box::into_raw` in `spawn` and is reconstructed exactly once.
+        let closure = unsafe { Box::from_raw(arg.cast::<Box<dyn FnOnce() + Send>>()) };
+        closure();
+    }
+    // `exit` (not `exit_group`) terminates only the calling thread. The kernel then clears the
+    // join word and wakes the joiner, thanks to `CLONE_CHILD_CLEARTID`.
+    // SAFETY: No pointers are involved.
+    unsafe {
+        syscall!(SyscallNum::Exit, 0_usize);
+    }
+    unreachable!("failed to exit thread somehow")
+}
+
+/// An owned permission to join on a thread spawned by [`spawn`], blocking until it finishes.
+///
+/// Dropping a [`JoinHandle`] without calling [`JoinHandle::join`] leaks the thread's stack.
+#[derive(Debug)]
+#[must_use = "dropping a `JoinHandle` without joining leaks the thread's stack"]
+pub struct JoinHandle {
+    /// The base of the thread's `mmap`ed stack.
+    stack: *mut u8,
+    /// The futex word the kernel clears and wakes when the thread exits.
+    join_word: *mut AtomicI32,
+}
+impl JoinHandle {
+    /// Blocks until the associated thread finishes, then frees its stack.
+    ///
+    /// Internally waits on a futex word which the kernel clears when the thread exits, so the
+    /// joiner sleeps instead of spinning.
+    ///
+    /// # Errors
+    ///
+    /// This function propagates any unexpected [`Errno`]s returned by the underlying `futex` and
+    /// `munmap` syscalls.
+    pub fn join(self) -> Result<(), Errno> {
+        // SAFETY: The join word stays allocated until it's reclaimed below, after the loop.
+        let word = unsafe { &*self.join_word };
+        loop {
+            let value = word.load(Ordering::Acquire);
+            if value == 0 {
+                // The kernel cleared the word; the thread is gone.
+                break;
+            }
+            // OK to allow this; the futex word is only ever the sentinel `1` or `0` here.
+            #[allow(clippy::cast_sign_loss)]
+            // SAFETY: The futex word outlives the wait, and `FUTEX_WAIT` takes no other pointers
+            // (the null timeout means "wait forever").
+            match unsafe {
+                syscall_result!(
+                    SyscallNum::Futex,
+                    self.join_word as usize,
+                    FUTEX_WAIT | FUTEX_PRIVATE_FLAG,
+                    value as usize,
+                    0_usize,
+                    0_usize,
+                    0_usize
+                )
+            } {
+                // Either woken, the word already changed, or interrupted; re-check the word.
+                Ok(_) | Err(Errno::Eagain | Errno::Eintr) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        // The thread has fully exited, so its stack and join word can be reclaimed.
+        // SAFETY: Both allocations were handed out by `spawn` and are reclaimed exactly once;
+        // consuming `self` means no further access is possible.
+        unsafe {
+            drop(Box::from_raw(self.join_word));
+            syscall_result!(SyscallNum::Munmap, self.stack, THREAD_STACK_SIZE)?;
+        }
+        Ok(())
+    }
+}
+
+/// Spawns a new thread running the given closure, returning a [`JoinHandle`] for it.
+///
+/// The thread shares this process's address space, file descriptor table, filesystem information,
+/// and signal handlers, like a thread created by
+/// [`pthread_create(3)`](https://www.man7.org/linux/man-pages/man3/pthread_create.3.html). Its
+/// stack is a fresh `mmap`ed region freed by [`JoinHandle::join`].
+///
+/// Internally uses the [`clone`](https://www.man7.org/linux/man-pages/man2/clone.2.html) Linux
+/// syscall.
+///
+/// # Errors
+///
+/// This function propagates any [`Errno`]s returned by the underlying `mmap` and `clone`
+/// syscalls.
+pub fn spawn<F: FnOnce() + Send + 'static>(f: F) -> Result<JoinHandle, Errno> {
+    // SAFETY: A fresh anonymous mapping is requested, so no existing memory is affected.
+    let stack = unsafe {
+        syscall_result!(
+            SyscallNum::Mmap,
+            0_usize,
+            THREAD_STACK_SIZE,
+            PROT_READ | PROT_WRITE,
+            MAP_PRIVATE | MAP_ANONYMOUS | MAP_STACK,
+            usize::MAX, // fd -1; ignored for anonymous mappings
+            0_usize
+        )?
+    } as *mut u8;
+
+    // The join word starts at a nonzero sentinel; the kernel clears it (and wakes its futex) when
+    // the thread exits.
+    let join_word = Box::into_raw(Box::new(AtomicI32::new(1)));
+    // Double-boxed so a thin pointer can cross the `extern "C"` boundary.
+    let closure: *mut Box<dyn FnOnce() + Send> = Box::into_raw(Box::new(Box::new(f)));
+
+    let flags =
+        CLONE_VM | CLONE_FS | CLONE_FILES | CLONE_SIGHAND | CLONE_THREAD | CLONE_CHILD_CLEARTID;
+
+    // SAFETY: The stack top points just past a fresh, exclusively-owned mapping, the join word
+    // stays allocated until `join` reclaims it, and the entry point takes ownership of the boxed
+    // closure.
+    let ret = unsafe {
+        __clone_thread(
+            flags,
+            stack.add(THREAD_STACK_SIZE),
+            join_word.cast::<i32>(),
+            thread_entry,
+            closure.cast::<u8>(),
+        )
+    };
+
+    if let Err(e) = Errno::__from_ret(ret) {
+        // The thread never started; reclaim everything handed out above.
+        // SAFETY: Each allocation is reclaimed exactly once, and nothing else holds a pointer to
+        // them.
+        unsafe {
+            drop(Box::from_raw(closure));
+            drop(Box::from_raw(join_word));
+            let _ = syscall_result!(SyscallNum::Munmap, stack, THREAD_STACK_SIZE);
+        }
+        return Err(e);
+    }
+
+    Ok(JoinHandle { stack, join_word })
+}
+
 /// Endlessly loops, sleeping the thread.
 ///
 /// # Errors
@@ -94,6 +326,33 @@ pub fn sleep(duration: &Duration) -> Result<(), Errno> {
     }
 }
 
+/// Determines which CPU and NUMA node the calling thread is currently running on, returned as the
+/// pair `(cpu, node)`.
+///
+/// The answer is only a snapshot: the scheduler may migrate the thread to another CPU at any time.
+///
+/// Internally uses the [getcpu](https://www.man7.org/linux/man-pages/man2/getcpu.2.html) Linux
+/// syscall.
+///
+/// # Errors
+///
+/// This function can return any errors generated by the underlying `getcpu` Linux syscall.
+pub fn getcpu() -> Result<(u32, u32), Errno> {
+    let mut cpu: u32 = 0;
+    let mut node: u32 = 0;
+    // SAFETY: Both pointers are valid for writes for the duration of the call; the third
+    // (historical cache) argument is unused and null.
+    unsafe {
+        syscall_result!(
+            SyscallNum::Getcpu,
+            &raw mut cpu as usize,
+            &raw mut node as usize,
+            0_usize
+        )?;
+    }
+    Ok((cpu, node))
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -108,4 +367,100 @@ mod tests {
     fn nsecs() {
         sleep(&Duration::from_nanos(500_000)).unwrap();
     }
+
+    #[test_case]
+    fn spawn_and_join() {
+        use core::sync::atomic::AtomicUsize;
+
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let handle = spawn(|| {
+            COUNTER.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+        handle.join().unwrap();
+
+        assert_eq!(COUNTER.load(Ordering::SeqCst), 1);
+    }
+
+    #[test_case]
+    fn spawn_moves_capture() {
+        use core::sync::atomic::AtomicUsize;
+
+        static TOTAL: AtomicUsize = AtomicUsize::new(0);
+        const THREADS: usize = 4;
+        const INCREMENTS: usize = 1000;
+
+        let handles = (0..THREADS)
+            .map(|_| {
+                spawn(move || {
+                    for _ in 0..INCREMENTS {
+                        TOTAL.fetch_add(1, Ordering::SeqCst);
+                    }
+                })
+                .unwrap()
+            })
+            .collect::<alloc::vec::Vec<_>>();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(TOTAL.load(Ordering::SeqCst), THREADS * INCREMENTS);
+    }
+
+    #[test_case]
+    fn getcpu_within_affinity_mask() {
+        let (cpu, _node) = getcpu().unwrap();
+
+        // Fetch this thread's affinity mask straight from the kernel; the current CPU must be a
+        // member of it.
+        let mut mask = [0_u64; 16]; // room for 1024 CPUs
+        // SAFETY: PID 0 means "the calling thread", and the mask buffer is valid for writes.
+        unsafe {
+            syscall_result!(
+                SyscallNum::SchedGetaffinity,
+                0_usize,
+                core::mem::size_of_val(&mask),
+                mask.as_mut_ptr() as usize
+            )
+            .unwrap();
+        }
+        let word = mask[(cpu / 64) as usize];
+        assert_eq!((word >> (cpu % 64)) & 1, 1);
+    }
+
+    #[test_case]
+    fn mutex_try_lock() {
+        let mutex = Mutex::new(5);
+
+        let guard = mutex.lock();
+        assert!(mutex.try_lock().is_none());
+        drop(guard);
+
+        *mutex.try_lock().unwrap() = 6;
+        assert_eq!(*mutex.lock(), 6);
+    }
+
+    #[test_case]
+    fn mutex_contended_counter() {
+        static COUNTER: Mutex<usize> = Mutex::new(0);
+        const THREADS: usize = 2;
+        const INCREMENTS: usize = 10_000;
+
+        let handles = (0..THREADS)
+            .map(|_| {
+                spawn(|| {
+                    for _ in 0..INCREMENTS {
+                        *COUNTER.lock() += 1;
+                    }
+                })
+                .unwrap()
+            })
+            .collect::<alloc::vec::Vec<_>>();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(*COUNTER.lock(), THREADS * INCREMENTS);
+    }
 }
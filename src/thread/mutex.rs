@@ -0,0 +1,137 @@
+//! A futex-based blocking mutex.
+
+use core::{
+    cell::UnsafeCell,
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicI32, Ordering},
+};
+
+use crate::{SyscallNum, syscall_result};
+
+use super::{FUTEX_PRIVATE_FLAG, FUTEX_WAIT, FUTEX_WAKE};
+
+/// Futex state: no thread holds the lock.
+const UNLOCKED: i32 = 0;
+/// Futex state: a thread holds the lock and nobody is waiting.
+const LOCKED: i32 = 1;
+/// Futex state: a thread holds the lock and at least one thread may be parked.
+const CONTENDED: i32 = 2;
+
+/// A mutual exclusion primitive protecting the contained value, usable across threads spawned by
+/// [`spawn`](super::spawn).
+///
+/// Uncontended locking and unlocking are single atomic operations with no syscalls; under
+/// contention, waiters are parked in the kernel via the
+/// [`futex`](https://www.man7.org/linux/man-pages/man2/futex.2.html) Linux syscall instead of
+/// spinning.
+#[derive(Debug)]
+pub struct Mutex<T> {
+    /// The futex word tracking the lock state.
+    state: AtomicI32,
+    /// The protected value.
+    value: UnsafeCell<T>,
+}
+// SAFETY: The lock protocol guarantees at most one thread accesses the value at a time, so
+// sharing the mutex between threads is safe whenever moving the value between threads is.
+unsafe impl<T: Send> Send for Mutex<T> {}
+// SAFETY: See above.
+unsafe impl<T: Send> Sync for Mutex<T> {}
+impl<T> Mutex<T> {
+    /// Creates a new, unlocked [`Mutex`] protecting the given value.
+    #[must_use]
+    pub const fn new(value: T) -> Self {
+        Self {
+            state: AtomicI32::new(UNLOCKED),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Acquires the lock, blocking the calling thread until it's available. The lock is released
+    /// when the returned [`MutexGuard`] is dropped.
+    #[must_use]
+    pub fn lock(&self) -> MutexGuard<'_, T> {
+        if self
+            .state
+            .compare_exchange(UNLOCKED, LOCKED, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            self.lock_contended();
+        }
+        MutexGuard { mutex: self }
+    }
+
+    /// Attempts to acquire the lock without blocking, returning `None` if it's already held.
+    #[must_use]
+    pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+        self.state
+            .compare_exchange(UNLOCKED, LOCKED, Ordering::Acquire, Ordering::Relaxed)
+            .ok()
+            .map(|_| MutexGuard { mutex: self })
+    }
+
+    /// The slow path of [`Mutex::lock`]: mark the lock contended and park until it's handed over.
+    fn lock_contended(&self) {
+        // Taking the lock from the contended state keeps it marked contended, so the eventual
+        // unlock wakes any other parked waiters too.
+        while self.state.swap(CONTENDED, Ordering::Acquire) != UNLOCKED {
+            // OK to allow this; the futex word only ever holds the small state constants.
+            #[allow(clippy::cast_sign_loss)]
+            // SAFETY: The futex word lives as long as the mutex, and `FUTEX_WAIT` takes no other
+            // pointers (the null timeout means "wait forever"). A stale wait returns immediately
+            // with `Eagain`; any error simply re-checks the state.
+            let _ = unsafe {
+                syscall_result!(
+                    SyscallNum::Futex,
+                    &raw const self.state as usize,
+                    FUTEX_WAIT | FUTEX_PRIVATE_FLAG,
+                    CONTENDED as usize,
+                    0_usize,
+                    0_usize,
+                    0_usize
+                )
+            };
+        }
+    }
+}
+
+/// An RAII guard granting access to the value protected by a [`Mutex`]. The lock is released on
+/// drop.
+#[derive(Debug)]
+pub struct MutexGuard<'a, T> {
+    /// The [`Mutex`] this guard has locked.
+    mutex: &'a Mutex<T>,
+}
+impl<T> Deref for MutexGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: Holding the guard means holding the lock, so no other thread can access the
+        // value.
+        unsafe { &*self.mutex.value.get() }
+    }
+}
+impl<T> DerefMut for MutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: See above; the exclusive borrow of the guard extends to the value.
+        unsafe { &mut *self.mutex.value.get() }
+    }
+}
+impl<T> Drop for MutexGuard<'_, T> {
+    fn drop(&mut self) {
+        if self.mutex.state.swap(UNLOCKED, Ordering::Release) == CONTENDED {
+            // Somebody may be parked; wake one waiter to take the lock.
+            // SAFETY: The futex word lives as long as the mutex, and `FUTEX_WAKE` takes no other
+            // pointers.
+            let _ = unsafe {
+                syscall_result!(
+                    SyscallNum::Futex,
+                    &raw const self.mutex.state as usize,
+                    FUTEX_WAKE | FUTEX_PRIVATE_FLAG,
+                    1_usize,
+                    0_usize,
+                    0_usize,
+                    0_usize
+                )
+            };
+        }
+    }
+}